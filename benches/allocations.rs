@@ -0,0 +1,65 @@
+//! Allocations and time taken by calls from one wasm function to another. Before the vectors
+//! for locals, stacks and results were reused, each call allocated four of them, so 100000
+//! calls made 400004 allocations. Now the count stays the same however many calls are made.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use wasm_interpreter::parser::parse_wasm_bytes;
+use wasm_interpreter::wasm::{Module, Value};
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Calls `add` the given number of times from a loop
+const CALLS_MODULE: &str = r#"(module
+    (func $add (param i32 i32) (result i32) (i32.add (local.get 0) (local.get 1)))
+    (func (export "run") (param $n i32) (result i32) (local $acc i32)
+        (block $done
+            (br_if $done (i32.eqz (local.get $n)))
+            (loop $again
+                (local.set $acc (call $add (local.get $acc) (local.get $n)))
+                (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                (br_if $again (local.get $n))))
+        (local.get $acc)))"#;
+
+fn calls(c: &mut Criterion) {
+    let bytes = wat::parse_str(CALLS_MODULE).unwrap();
+    let mut module: Module = parse_wasm_bytes(&bytes).unwrap();
+    module.instantiate().unwrap();
+
+    for n in [1_000, 100_000] {
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        module.call("run", vec![Value::i32(n)]).unwrap();
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+        println!("{} calls: {} allocations", n, allocations);
+    }
+
+    c.bench_function("100000 calls", |b| {
+        b.iter(|| {
+            module
+                .call("run", vec![Value::i32(black_box(100_000))])
+                .unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, calls);
+criterion_main!(benches);
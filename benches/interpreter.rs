@@ -0,0 +1,52 @@
+//! Parse and execution time of the Fibonacci test module. `FIB_INDEX` sets which Fibonacci
+//! number each call computes, 100000 by default. Also times parsing a module with many
+//! functions of one type.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use wasm_interpreter::parser::parse_wasm_bytes;
+use wasm_interpreter::wasm::Value;
+
+const FIB_MODULE: &[u8] = include_bytes!("../test_inputs/fib_O3.wasm");
+
+fn fib_index() -> i64 {
+    match std::env::var("FIB_INDEX") {
+        Ok(s) => s.parse().expect("FIB_INDEX should be an integer"),
+        Err(_) => 100_000,
+    }
+}
+
+fn parse(c: &mut Criterion) {
+    c.bench_function("parse fib_O3", |b| {
+        b.iter(|| parse_wasm_bytes(black_box(FIB_MODULE)).unwrap())
+    });
+}
+
+fn parse_shared_types(c: &mut Criterion) {
+    let mut text = String::from("(module (type $t (func (param i32) (result i32)))");
+    for _ in 0..5000 {
+        text.push_str("(func (type $t) local.get 0)");
+    }
+    text.push(')');
+    let bytes = wat::parse_str(&text).unwrap();
+    c.bench_function("parse 5000 functions of one type", |b| {
+        b.iter(|| parse_wasm_bytes(black_box(&bytes)).unwrap())
+    });
+}
+
+fn execute(c: &mut Criterion) {
+    let index = fib_index();
+    let mut module = parse_wasm_bytes(FIB_MODULE).unwrap();
+    module.instantiate().unwrap();
+    c.bench_function(&format!("fib_dispatch({})", index), |b| {
+        b.iter(|| {
+            module
+                .call("fib_dispatch", vec![Value::i64(black_box(index))])
+                .unwrap()
+        })
+    });
+}
+
+criterion_group!(benches, parse, parse_shared_types, execute);
+criterion_main!(benches);
@@ -0,0 +1,10 @@
+//! Prints every opcode the parser currently accepts
+
+fn main() {
+    for (opcode, name) in wasm_interpreter::supported_opcodes() {
+        println!("0x{:02X}       {}", opcode, name);
+    }
+    for (prefix, opcode, name) in wasm_interpreter::supported_secondary_opcodes() {
+        println!("0x{:02X} 0x{:02X}  {}", prefix, opcode, name);
+    }
+}
@@ -0,0 +1,14 @@
+//! Feeds arbitrary bytes to the parser, which must return an `Err` rather than panic.
+//!
+//! Run it with cargo-fuzz (needs a nightly toolchain), using the modules in `test_inputs`
+//! as a seed corpus:
+//!
+//!     cargo +nightly fuzz run parse_wasm_bytes fuzz/corpus/parse_wasm_bytes test_inputs
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+use wasm_interpreter::parser::parse_wasm_bytes;
+
+fuzz_target!(|data: &[u8]| {
+    let _ = parse_wasm_bytes(data);
+});
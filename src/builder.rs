@@ -0,0 +1,95 @@
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::error::Error;
+use crate::wasm::*;
+
+/// Assembles a `Module` in code, as an alternative to parsing one from a binary.
+/// Functions are numbered in the order they're added, starting at 0.
+#[derive(Default)]
+pub struct ModuleBuilder {
+    module: Module,
+    exports: Vec<(String, Export)>,
+}
+
+impl ModuleBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a function with the given signature, extra locals, and body
+    pub fn function(
+        mut self,
+        signature: FunctionType,
+        locals: Vec<PrimitiveType>,
+        instructions: Vec<Box<dyn Instruction>>,
+    ) -> Self {
+        self.module.add_function_type(signature.clone());
+        let mut function = Function::new(signature);
+        for t in locals {
+            function.new_locals(1, t);
+        }
+        for i in instructions {
+            function.push_inst(i);
+        }
+        self.module.add_function(function);
+        self
+    }
+
+    pub fn memory(mut self, min_pages: u32, max_pages: u32) -> Self {
+        self.module.add_memory(Memory::new(min_pages, max_pages));
+        self
+    }
+
+    pub fn global(mut self, value: Value, mutable: bool) -> Self {
+        self.module.add_global(Global::new(value, mutable));
+        self
+    }
+
+    pub fn export(mut self, name: &str, export: Export) -> Self {
+        self.exports.push((name.to_string(), export));
+        self
+    }
+
+    /// Finish the module, failing if an export name was used twice
+    pub fn build(mut self) -> Result<Module, Error> {
+        for (name, export) in self.exports {
+            self.module.add_export(name, export)?;
+        }
+        Ok(self.module)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::call;
+    use crate::wasm::inst::{IUnOp, IUnOpType, LocalGet};
+
+    #[test]
+    fn built_function_can_be_called() {
+        let signature = FunctionType::new(vec![PrimitiveType::I32], vec![PrimitiveType::I32]);
+        let body: Vec<Box<dyn Instruction>> = vec![
+            Box::new(LocalGet::new(0)),
+            Box::new(IUnOp::new(PrimitiveType::I32, IUnOpType::Popcnt)),
+        ];
+        let mut module = ModuleBuilder::new()
+            .function(signature, Vec::new(), body)
+            .export("popcnt", Export::Function(0))
+            .build()
+            .unwrap();
+        module.instantiate().unwrap();
+        let results = call(&mut module, "popcnt", &[Value::i32(0b1011)]).unwrap();
+        assert_eq!(results, [TypedValue::I32(3)]);
+    }
+
+    #[test]
+    fn duplicate_export_names_fail_the_build() {
+        let builder = ModuleBuilder::new()
+            .memory(1, 1)
+            .export("x", Export::Memory(0))
+            .export("x", Export::Memory(0));
+        assert!(builder.build().is_err());
+    }
+}
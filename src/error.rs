@@ -1,3 +1,10 @@
+use alloc::boxed::Box;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::wasm::{Exception, Trap, Value};
+
+#[derive(Debug)]
 pub enum Error {
     InvalidInput,
     BadVersion,
@@ -9,9 +16,33 @@ pub enum Error {
     FloatSizeViolation,
     StackViolation,
     UnexpectedData(&'static str),
+    Trap(Trap),
+    /// An exception was thrown and no `try` caught it before it left the called function
+    UncaughtException(Exception),
+    /// An error from inside a called function, named by the name section or as `func[index]`.
+    /// Only the innermost call is named.
+    InFunction(String, Box<Error>),
+    /// Every value on a function's stack, bottom first, when it returned with the wrong number
+    /// of values. Only given by `Module::call_debug`.
+    FinalStack(Vec<Value>),
+    /// An import nothing was provided for, by module name and field name
+    UnresolvedImport {
+        module: String,
+        field: String,
+    },
     Misc(&'static str), /* Just to facilitate development for now, or for one-off errors */
 }
 
+impl Error {
+    /// The error without the name of the function it came from
+    pub fn root(&self) -> &Error {
+        match self {
+            Error::InFunction(_, e) => e.root(),
+            e => e,
+        }
+    }
+}
+
 // impl Display for Error {
 //     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
 // }
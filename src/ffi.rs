@@ -0,0 +1,265 @@
+//! A C interface to parsing modules and calling their exported functions. Modules are
+//! handed out as opaque pointers, and values cross the boundary as `WasmValue`s.
+
+use alloc::boxed::Box;
+use core::ffi::{c_char, CStr};
+use core::ptr;
+use core::slice;
+
+use crate::error::Error;
+use crate::parser::parse_wasm_bytes;
+use crate::wasm::{Module, PrimitiveType, Value};
+
+pub const WASM_KIND_I32: u32 = 0;
+pub const WASM_KIND_I64: u32 = 1;
+pub const WASM_KIND_F32: u32 = 2;
+pub const WASM_KIND_F64: u32 = 3;
+pub const WASM_KIND_FUNCREF: u32 = 4;
+pub const WASM_KIND_EXTERNREF: u32 = 5;
+
+/// A pointer argument was null
+pub const WASM_ERR_NULL: i32 = -1;
+/// The function name isn't valid UTF-8
+pub const WASM_ERR_NAME: i32 = -2;
+/// An argument has an unknown kind
+pub const WASM_ERR_ARGS: i32 = -3;
+/// The call trapped
+pub const WASM_ERR_TRAP: i32 = -4;
+/// The call returned more results than the output buffer holds
+pub const WASM_ERR_RESULTS: i32 = -5;
+/// Any other error, e.g. no exported function with the given name
+pub const WASM_ERR_CALL: i32 = -6;
+
+/// A wasm value: one of the `WASM_KIND_*` constants and the value's bit pattern. 32-bit
+/// values are in the low bits, and null references have every bit set.
+#[repr(C)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct WasmValue {
+    pub kind: u32,
+    pub bits: u64,
+}
+
+impl From<Value> for WasmValue {
+    fn from(v: Value) -> Self {
+        let kind = match v.get_type() {
+            PrimitiveType::I32 => WASM_KIND_I32,
+            PrimitiveType::I64 => WASM_KIND_I64,
+            PrimitiveType::F32 => WASM_KIND_F32,
+            PrimitiveType::F64 => WASM_KIND_F64,
+            PrimitiveType::FuncRef => WASM_KIND_FUNCREF,
+            PrimitiveType::ExternRef => WASM_KIND_EXTERNREF,
+        };
+        Self {
+            kind,
+            bits: v.to_bits(),
+        }
+    }
+}
+
+impl WasmValue {
+    fn to_value(self) -> Option<Value> {
+        let t = match self.kind {
+            WASM_KIND_I32 => PrimitiveType::I32,
+            WASM_KIND_I64 => PrimitiveType::I64,
+            WASM_KIND_F32 => PrimitiveType::F32,
+            WASM_KIND_F64 => PrimitiveType::F64,
+            WASM_KIND_FUNCREF => PrimitiveType::FuncRef,
+            WASM_KIND_EXTERNREF => PrimitiveType::ExternRef,
+            _ => return None,
+        };
+        Some(Value::from_bits(t, self.bits))
+    }
+}
+
+/// Parse and instantiate the module in the `len` bytes at `bytes`. Gives null if the module
+/// is invalid or its instantiation fails. The caller owns the returned module and must
+/// release it with `wasm_module_free`, never with `free` or another allocator. The bytes
+/// are copied, so they may be freed as soon as this returns.
+///
+/// # Safety
+///
+/// `bytes` must point to `len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn wasm_module_parse(bytes: *const u8, len: usize) -> *mut Module {
+    if bytes.is_null() {
+        return ptr::null_mut();
+    }
+    let mut module = match parse_wasm_bytes(slice::from_raw_parts(bytes, len)) {
+        Ok(m) => m,
+        Err(_) => return ptr::null_mut(),
+    };
+    if module.instantiate().is_err() {
+        return ptr::null_mut();
+    }
+    Box::into_raw(Box::new(module))
+}
+
+/// Call the exported function `name` with `args_len` arguments from `args`, writing its
+/// results to `results`, which has room for `results_len` values. Gives the number of
+/// results written, or one of the negative `WASM_ERR_*` codes.
+///
+/// # Safety
+///
+/// `module` must come from `wasm_module_parse` and not be freed, `name` must be a
+/// NUL-terminated string, `args` must point to `args_len` values and `results` must have
+/// room for `results_len` values. `args` and `results` may be null when their length is 0.
+#[no_mangle]
+pub unsafe extern "C" fn wasm_module_call(
+    module: *mut Module,
+    name: *const c_char,
+    args: *const WasmValue,
+    args_len: usize,
+    results: *mut WasmValue,
+    results_len: usize,
+) -> i32 {
+    if module.is_null()
+        || name.is_null()
+        || (args.is_null() && args_len != 0)
+        || (results.is_null() && results_len != 0)
+    {
+        return WASM_ERR_NULL;
+    }
+    let name = match CStr::from_ptr(name).to_str() {
+        Ok(s) => s,
+        Err(_) => return WASM_ERR_NAME,
+    };
+    let args = if args_len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(args, args_len)
+    };
+    let args = match args.iter().map(|a| a.to_value()).collect() {
+        Some(a) => a,
+        None => return WASM_ERR_ARGS,
+    };
+    let values = match (*module).call(name, args) {
+        Ok(v) => v,
+        Err(e) => {
+            return match e.root() {
+                Error::Trap(_) => WASM_ERR_TRAP,
+                _ => WASM_ERR_CALL,
+            }
+        }
+    };
+    if values.len() > results_len {
+        return WASM_ERR_RESULTS;
+    }
+    for (i, v) in values.iter().enumerate() {
+        *results.add(i) = WasmValue::from(*v);
+    }
+    values.len() as i32
+}
+
+/// Free a module from `wasm_module_parse`. Does nothing given null.
+///
+/// # Safety
+///
+/// `module` must be null or come from `wasm_module_parse`, and not already be freed.
+#[no_mangle]
+pub unsafe extern "C" fn wasm_module_free(module: *mut Module) {
+    if !module.is_null() {
+        drop(Box::from_raw(module));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+
+    fn value(v: Value) -> WasmValue {
+        WasmValue::from(v)
+    }
+
+    fn name(bytes: &[u8]) -> &CStr {
+        CStr::from_bytes_with_nul(bytes).unwrap()
+    }
+
+    #[test]
+    fn parse_call_and_free() {
+        let bytes = wasm(
+            r#"(module
+                (func (export "add") (param i64 f64) (result i64 f64)
+                    (i64.add (local.get 0) (i64.const 1))
+                    (f64.mul (local.get 1) (f64.const 2)))
+                (func (export "trap") unreachable))"#,
+        );
+        unsafe {
+            let module = wasm_module_parse(bytes.as_ptr(), bytes.len());
+            assert!(!module.is_null());
+
+            let args = [value(Value::i64(41)), value(Value::f64(1.25))];
+            let mut results = [WasmValue { kind: 99, bits: 0 }; 3];
+            let written = wasm_module_call(
+                module,
+                name(b"add\0").as_ptr(),
+                args.as_ptr(),
+                args.len(),
+                results.as_mut_ptr(),
+                results.len(),
+            );
+            assert_eq!(written, 2);
+            assert_eq!(
+                results[..2],
+                [
+                    WasmValue {
+                        kind: WASM_KIND_I64,
+                        bits: 42
+                    },
+                    WasmValue {
+                        kind: WASM_KIND_F64,
+                        bits: 2.5_f64.to_bits()
+                    }
+                ]
+            );
+            // Slots past the results are left alone
+            assert_eq!(results[2].kind, 99);
+
+            let mut one = [WasmValue { kind: 0, bits: 0 }; 1];
+            let call = |function: &CStr, args: &[WasmValue], results: &mut [WasmValue]| {
+                wasm_module_call(
+                    module,
+                    function.as_ptr(),
+                    args.as_ptr(),
+                    args.len(),
+                    results.as_mut_ptr(),
+                    results.len(),
+                )
+            };
+            assert_eq!(call(name(b"add\0"), &args, &mut one), WASM_ERR_RESULTS);
+            assert_eq!(call(name(b"trap\0"), &[], &mut []), WASM_ERR_TRAP);
+            assert_eq!(call(name(b"missing\0"), &[], &mut []), WASM_ERR_CALL);
+            let bad_kind = [WasmValue { kind: 17, bits: 0 }, args[1]];
+            assert_eq!(call(name(b"add\0"), &bad_kind, &mut results), WASM_ERR_ARGS);
+            assert_eq!(call(name(b"\xff\0"), &[], &mut []), WASM_ERR_NAME);
+            assert_eq!(
+                wasm_module_call(
+                    ptr::null_mut(),
+                    name(b"add\0").as_ptr(),
+                    ptr::null(),
+                    0,
+                    ptr::null_mut(),
+                    0
+                ),
+                WASM_ERR_NULL
+            );
+
+            wasm_module_free(module);
+            wasm_module_free(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn invalid_modules_give_null() {
+        let garbage = [0, b'a', b's', b'm', 2, 0, 0, 0];
+        unsafe {
+            assert!(wasm_module_parse(garbage.as_ptr(), garbage.len()).is_null());
+            assert!(wasm_module_parse(ptr::null(), 0).is_null());
+        }
+        // A start function that traps fails instantiation
+        let bytes = wasm(r#"(module (func unreachable) (start 0))"#);
+        unsafe {
+            assert!(wasm_module_parse(bytes.as_ptr(), bytes.len()).is_null());
+        }
+    }
+}
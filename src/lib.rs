@@ -1,5 +1,35 @@
-#![feature(option_result_unwrap_unchecked)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
+extern crate alloc;
+
+/// `log::debug!` when the `log` feature is enabled, otherwise nothing
+macro_rules! debug {
+    ($($arg:tt)*) => {{
+        #[cfg(feature = "log")]
+        log::debug!($($arg)*);
+        #[cfg(not(feature = "log"))]
+        let _ = format_args!($($arg)*);
+    }};
+}
+
+/// Records a message in the execution context's trace when tracing is on, and passes it to
+/// `debug!` either way
+macro_rules! trace {
+    ($ctx:expr, $($arg:tt)*) => {{
+        if $ctx.config.verbosity == crate::wasm::Verbosity::Trace {
+            $ctx.trace.push(::alloc::format!($($arg)*));
+        }
+        debug!($($arg)*);
+    }};
+}
+
+pub mod builder;
 pub mod error;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod parser;
+#[cfg(test)]
+mod test_util;
 pub mod wasm;
+
+pub use parser::{supported_opcodes, supported_secondary_opcodes};
@@ -1,7 +1,7 @@
 use std::env;
 use wasm_interpreter::error::Error;
 use wasm_interpreter::parser::*;
-use wasm_interpreter::wasm::Value;
+use wasm_interpreter::wasm::{PrimitiveType, Value};
 
 fn handle_error<T>(x: Result<T, Error>) -> T {
     match x {
@@ -35,6 +35,21 @@ fn handle_error<T>(x: Result<T, Error>) -> T {
         Err(Error::UnexpectedData(s)) => {
             println!("{}", s);
         }
+        Err(Error::Trap(t)) => {
+            println!("Trap: {:?}", t)
+        }
+        Err(Error::UncaughtException(e)) => {
+            println!("Uncaught exception with tag {}", e.tag)
+        }
+        Err(Error::InFunction(name, e)) => {
+            println!("In {}: {:?}", name, e)
+        }
+        Err(Error::FinalStack(values)) => {
+            println!("Wrong number of values left on the stack: {:?}", values)
+        }
+        Err(Error::UnresolvedImport { module, field }) => {
+            println!("Unresolved import: {}.{}", module, field)
+        }
         Err(Error::Misc(s)) => {
             println!("{}", s);
         }
@@ -45,21 +60,118 @@ fn handle_error<T>(x: Result<T, Error>) -> T {
     std::process::exit(1);
 }
 
-fn main() {
+/// Runs `f`, returning its result and a description of how long it took
+#[cfg(target_arch = "x86_64")]
+fn timed<T>(f: impl FnOnce() -> T) -> (T, String) {
     use core::arch::x86_64::_rdtsc;
-    
+
+    let start_cycles = unsafe { _rdtsc() };
+    let ret = f();
+    let end_cycles = unsafe { _rdtsc() };
+    (ret, format!("{} cycles", end_cycles - start_cycles))
+}
+
+/// Fallback for targets without a cycle counter, using wall-clock time instead
+#[cfg(not(target_arch = "x86_64"))]
+fn timed<T>(f: impl FnOnce() -> T) -> (T, String) {
+    let start = std::time::Instant::now();
+    let ret = f();
+    (ret, format!("{:?}", start.elapsed()))
+}
+
+/// Parse an argument of the form `type:value`, e.g. `i32:-7` or `f64:2.5`. Integers may also be
+/// given in hex, and for floats hex gives the exact bit pattern, e.g. `f32:0x7FC00000`. Floats
+/// accept `inf`, `-inf` and `nan` too.
+fn parse_arg(arg: &str) -> Result<Value, String> {
+    let (t, literal) = match arg.split_once(':') {
+        Some(parts) => parts,
+        None => return Err(format!("Expected an argument like i32:5, got {}", arg)),
+    };
+    let hex = literal
+        .strip_prefix("0x")
+        .or_else(|| literal.strip_prefix("0X"));
+    let value = match (t, hex) {
+        ("i32", Some(h)) => u32::from_str_radix(h, 16)
+            .ok()
+            .map(|n| Value::from(n as i32)),
+        ("i32", None) => literal
+            .parse::<i32>()
+            .or_else(|_| literal.parse::<u32>().map(|n| n as i32))
+            .ok()
+            .map(Value::from),
+        ("i64", Some(h)) => u64::from_str_radix(h, 16)
+            .ok()
+            .map(|n| Value::from(n as i64)),
+        ("i64", None) => literal
+            .parse::<i64>()
+            .or_else(|_| literal.parse::<u64>().map(|n| n as i64))
+            .ok()
+            .map(Value::from),
+        ("f32", Some(h)) => u32::from_str_radix(h, 16)
+            .ok()
+            .map(|n| Value::from_bits(PrimitiveType::F32, n as u64)),
+        ("f32", None) => literal.parse::<f32>().ok().map(Value::from),
+        ("f64", Some(h)) => u64::from_str_radix(h, 16)
+            .ok()
+            .map(|n| Value::from_bits(PrimitiveType::F64, n)),
+        ("f64", None) => literal.parse::<f64>().ok().map(Value::from),
+        _ => return Err(format!("Unknown argument type {}", t)),
+    };
+    value.ok_or_else(|| format!("{} is not a valid {}", literal, t))
+}
+
+/// Types as a comma separated list, e.g. `i32, f64`
+fn type_list(types: &[PrimitiveType]) -> String {
+    types
+        .iter()
+        .map(|t| t.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn main() {
     env_logger::init();
 
     let args: Vec<String> = env::args().collect();
+    if args.len() < 3 {
+        println!("Usage: {} <file.wasm> <function> [type:value]...", args[0]);
+        std::process::exit(1);
+    }
     let filename = &args[1];
     let function_name = &args[2];
+    let mut call_args = Vec::new();
+    for arg in &args[3..] {
+        match parse_arg(arg) {
+            Ok(v) => call_args.push(v),
+            Err(e) => {
+                println!("{}", e);
+                std::process::exit(1);
+            }
+        }
+    }
 
     let mut module = handle_error(parse_wasm(filename));
-    let start_cycles = unsafe { _rdtsc() };
-    let ret_val = handle_error(module.call(function_name, vec![Value::from(100000_i64)]));
-    let end_cycles = unsafe { _rdtsc() };
+    handle_error(module.instantiate());
+    let params = &handle_error(module.function_signature(function_name)).params;
+    let given: Vec<PrimitiveType> = call_args.iter().map(Value::get_type).collect();
+    if *params != given {
+        println!(
+            "{} takes ({}), but was given ({})",
+            function_name,
+            type_list(params),
+            type_list(&given)
+        );
+        std::process::exit(1);
+    }
+    let (ret_val, elapsed) = timed(|| module.call(function_name, call_args));
+    let ret_val = handle_error(ret_val);
 
-    println!("Final value: {}", ret_val);
-    println!("In {} cycles", end_cycles - start_cycles);
+    if ret_val.is_empty() {
+        println!("No return value");
+    }
+    for v in ret_val {
+        println!("Final value: {}", v);
+    }
+    println!("In {}", elapsed);
     // return module.call_external("main");
 }
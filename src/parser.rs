@@ -1,48 +1,130 @@
-use std::convert::TryFrom;
-use std::convert::TryInto;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::rc::Rc;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::convert::TryFrom;
+use core::convert::TryInto;
+#[cfg(feature = "std")]
 use std::fs::File;
+#[cfg(feature = "std")]
 use std::io::BufReader;
+#[cfg(feature = "std")]
 use std::io::Read;
 
 use crate::error::Error;
 use crate::wasm::inst::*;
 use crate::wasm::*;
 
-/// Returns (value, length read)
-fn parse_unsigned_leb128(bytes: &[u8]) -> (u64, usize) {
-    let mut value = 0;
-    let mut offset = 0_usize;
-    while bytes[offset] & (1_u8 << 7) != 0 {
-        value += ((bytes[offset] & 0b01111111) as u64) << (7 * offset);
-        offset += 1;
-    }
-    value += ((bytes[offset] & 0b01111111) as u64) << (7 * offset);
-    offset += 1;
+/// Deepest nesting of blocks the parser accepts, so malformed input can't overflow the stack
+const MAX_BLOCK_DEPTH: usize = 1024;
+/// Most locals a single function may declare
+const MAX_LOCALS: usize = 50_000;
+/// Most elements a table may start with
+const MAX_TABLE_SIZE: u32 = 10_000_000;
+
+/// Caps on what a module may declare, so a small malicious file can't make the parser
+/// allocate or work through enormous amounts of data. `Default` gives the limits web
+/// browsers apply.
+#[derive(Copy, Clone, Debug)]
+pub struct ParseLimits {
+    pub max_types: usize,
+    pub max_imports: usize,
+    pub max_functions: usize,
+    /// Most instructions in the body of one function, counting those nested in blocks
+    pub max_function_instructions: usize,
+    /// Most memories, imported and defined
+    pub max_memories: usize,
+    /// Most pages all memories may start with together. Memories are allocated at their
+    /// initial size while the module is parsed.
+    pub max_initial_memory_pages: u64,
+    /// Most tables, imported and defined
+    pub max_tables: usize,
+    /// Most elements all tables may start with together. Tables are allocated at their
+    /// initial size while the module is parsed.
+    pub max_initial_table_elements: u64,
+}
 
-    (value, offset)
+impl Default for ParseLimits {
+    fn default() -> Self {
+        Self {
+            max_types: 1_000_000,
+            max_imports: 100_000,
+            max_functions: 1_000_000,
+            max_function_instructions: 7_654_321,
+            max_memories: 100,
+            max_initial_memory_pages: MAX_PAGES as u64,
+            max_tables: 100_000,
+            max_initial_table_elements: MAX_TABLE_SIZE as u64,
+        }
+    }
 }
 
-fn parse_signed_leb128(bytes: &[u8]) -> (i64, usize) {
+/// Reads the groups of a LEB128 number, returning (value, length read, last byte)
+fn read_leb128_groups(bytes: &[u8]) -> Result<(u64, usize, u8), Error> {
     let mut value = 0;
     let mut offset = 0_usize;
-    while bytes[offset] & (1_u8 << 7) != 0 {
-        value += ((bytes[offset] & 0b01111111) as u64) << (7 * offset);
+    loop {
+        let byte = match bytes.get(offset) {
+            Some(b) => *b,
+            None => return Err(Error::EndOfData),
+        };
+        if 7 * offset >= 64 {
+            return Err(Error::IntSizeViolation);
+        }
+        value |= ((byte & 0b01111111) as u64) << (7 * offset);
         offset += 1;
+        if byte & (1_u8 << 7) == 0 {
+            return Ok((value, offset, byte));
+        }
     }
-    value += ((bytes[offset] & 0b01111111) as u64) << (7 * offset);
-    offset += 1;
+}
+
+/// Returns (value, length read)
+fn parse_unsigned_leb128(bytes: &[u8]) -> Result<(u64, usize), Error> {
+    let (value, offset, _) = read_leb128_groups(bytes)?;
+    Ok((value, offset))
+}
+
+fn parse_signed_leb128(bytes: &[u8]) -> Result<(i64, usize), Error> {
+    let (mut value, offset, last_byte) = read_leb128_groups(bytes)?;
 
     // sign extension needed if the highest bit of the parsed number is 1
-    if (7 * offset) < 64 && bytes[offset - 1] & 1_u8 << 6 != 0 {
+    if (7 * offset) < 64 && last_byte & 1_u8 << 6 != 0 {
         value |= !0_u64 << (7 * offset);
     }
 
-    (value as i64, offset)
+    Ok((value as i64, offset))
 }
 
 struct ByteReader {
     content: Vec<u8>,
     offset: usize,
+    block_depth: usize,
+    /// The module's function types, which block types may refer to by index
+    function_types: Vec<FunctionType>,
+    /// The module's declared number of data segments, which data indices are checked against
+    data_count: Option<u32>,
+    /// Number of memories the module declares or imports, which memory indices are checked
+    /// against
+    num_memories: usize,
+    /// Element type of each table the module declares or imports, which table indices are
+    /// checked against
+    table_types: Vec<PrimitiveType>,
+    /// Reference type of each element segment, which element indices are checked against
+    element_types: Vec<PrimitiveType>,
+    /// Instructions the function body being read may still contain
+    instructions_left: usize,
+    /// Number of functions, imported and defined, which function indices are checked against
+    num_functions: usize,
+    /// Params plus declared locals of the function body being read, which local indices are
+    /// checked against
+    num_locals: usize,
+    /// Where `content` starts in the module binary
+    base_offset: usize,
+    /// Offset in the module binary and mnemonic of each instruction read so far
+    disassembly: Vec<(usize, String)>,
 }
 
 trait CheckedFromU64 {
@@ -104,9 +186,12 @@ impl CheckedFromI64 for i64 {
 
 impl CheckedFromI64 for i32 {
     fn from(u: i64) -> Result<Self, Error> {
-        match Self::try_from(u) {
-            Ok(n) => Ok(n),
-            Err(_) => Err(Error::IntSizeViolation),
+        // Some producers encode constants with the high bit set by their unsigned value,
+        // e.g. 0xFFFFFFFF for -1, so accept the whole 32-bit range of either signedness
+        if (i32::MIN as i64..=u32::MAX as i64).contains(&u) {
+            Ok(u as i32)
+        } else {
+            Err(Error::IntSizeViolation)
         }
     }
 }
@@ -117,11 +202,74 @@ macro_rules! inst {
     };
 }
 
+/// Builds `ByteReader::decode_inst` and the opcode lists behind `supported_opcodes` and
+/// `supported_secondary_opcodes` from a single table, so the two can't drift apart. Each entry is
+/// `opcode "name" => decoder`, where the decoder reads any immediates through `$r`. Entries under
+/// `unsupported` are decoded but not advertised.
+macro_rules! opcode_table {
+    (
+        reader: $r:ident;
+        $($op:literal $name:literal => $decode:expr,)*
+        $(prefix $prefix:literal {
+            $($sub_op:literal $sub_name:literal => $sub_decode:expr,)*
+        })*
+        unsupported {
+            $($unsupported_op:literal => $unsupported_decode:expr,)*
+        }
+    ) => {
+        /// Opcodes `read_inst` accepts, with their names in the text format
+        const SUPPORTED_OPCODES: &[(u8, &str)] = &[$(($op, $name),)*];
+
+        /// Opcodes accepted after a prefix byte, as (prefix, secondary opcode, name)
+        const SUPPORTED_SECONDARY_OPCODES: &[(u8, u32, &str)] =
+            &[$($(($prefix, $sub_op, $sub_name),)*)*];
+
+        impl ByteReader {
+            fn decode_inst(&mut self) -> Result<Option<Box<dyn Instruction>>, Error> {
+                let opcode = self.read_byte()?;
+                let $r = self;
+                match opcode {
+                    $($op => $decode,)*
+                    $($prefix => match $r.read_int::<u32>()? {
+                        $($sub_op => $sub_decode,)*
+                        x => Err(Error::UnknownSecondaryOpcode(x as u64)),
+                    },)*
+                    $($unsupported_op => $unsupported_decode,)*
+                    x => Err(Error::UnknownOpcode(x as u64)),
+                }
+            }
+        }
+    };
+}
+
 impl ByteReader {
     fn new(content: &[u8]) -> Self {
         Self {
             content: Vec::from(content),
             offset: 0,
+            block_depth: 0,
+            function_types: Vec::new(),
+            data_count: None,
+            num_memories: 0,
+            table_types: Vec::new(),
+            element_types: Vec::new(),
+            instructions_left: usize::MAX,
+            num_functions: 0,
+            num_locals: 0,
+            base_offset: 0,
+            disassembly: Vec::new(),
+        }
+    }
+
+    /// Offset of the next byte to be read, relative to the start of the module binary
+    fn binary_offset(&self) -> usize {
+        self.base_offset + self.offset
+    }
+
+    fn peek_byte(&self) -> Result<u8, Error> {
+        match self.content.get(self.offset) {
+            Some(n) => Ok(*n),
+            None => Err(Error::EndOfData),
         }
     }
 
@@ -145,444 +293,252 @@ impl ByteReader {
     }
 
     fn read_int<I: CheckedFromU64>(&mut self) -> Result<I, Error> {
-        let (value, read_bytes) = parse_unsigned_leb128(&self.content[self.offset..]);
+        let (value, read_bytes) = parse_unsigned_leb128(&self.content[self.offset..])?;
         self.offset += read_bytes;
-        Ok(I::from(value)?)
+        I::from(value)
     }
 
     // same as `read_int`, but uses signed leb128 decoding
     fn read_signed_int<I: CheckedFromI64>(&mut self) -> Result<I, Error> {
-        let (value, read_bytes) = parse_signed_leb128(&self.content[self.offset..]);
+        let (value, read_bytes) = parse_signed_leb128(&self.content[self.offset..])?;
         self.offset += read_bytes;
-        Ok(I::from(value)?)
+        I::from(value)
     }
 
-    fn read_f32(&mut self) -> Result<f32, Error> {
-        let value = f32::from_le_bytes(
-            (&self.content[self.offset..self.offset + 4])
-                .try_into()
-                .map_err(|_| Error::FloatSizeViolation)?,
+    /// Reads an f32 by its bit pattern, so NaN payloads (including signaling NaNs) are kept
+    fn read_f32(&mut self) -> Result<Value, Error> {
+        let bits = u32::from_le_bytes(
+            match self.content.get(self.offset..self.offset + 4) {
+                Some(b) => b,
+                None => return Err(Error::EndOfData),
+            }
+            .try_into()
+            .map_err(|_| Error::FloatSizeViolation)?,
         );
         self.offset += 4;
-        Ok(value)
+        Ok(Value::from_bits(PrimitiveType::F32, bits as u64))
     }
 
-    fn read_f64(&mut self) -> Result<f64, Error> {
-        let value = f64::from_le_bytes(
-            (&self.content[self.offset..self.offset + 8])
-                .try_into()
-                .map_err(|_| Error::FloatSizeViolation)?,
+    /// Reads an f64 by its bit pattern, so NaN payloads (including signaling NaNs) are kept
+    fn read_f64(&mut self) -> Result<Value, Error> {
+        let bits = u64::from_le_bytes(
+            match self.content.get(self.offset..self.offset + 8) {
+                Some(b) => b,
+                None => return Err(Error::EndOfData),
+            }
+            .try_into()
+            .map_err(|_| Error::FloatSizeViolation)?,
         );
         self.offset += 8;
-        Ok(value)
+        Ok(Value::from_bits(PrimitiveType::F64, bits))
     }
 
+    /// Reads an instruction, recording its offset and mnemonic for `Module::disassemble`.
+    /// Gives `None` at the `end` of a function body
     fn read_inst(&mut self) -> Result<Option<Box<dyn Instruction>>, Error> {
-        let opcode = self.read_byte()?;
-        match opcode {
-            0x02 => {
-                let _ = self.read_int::<u64>()?; // Block type, which we might need to implement later
-                let mut block_instructions: Vec<Box<dyn Instruction>> = Vec::new();
-                while let Some(inst) = self.read_inst()? {
-                    block_instructions.push(inst);
-                }
-                inst!(Block::new(BlockContinuation::Branch, block_instructions))
+        self.instructions_left = match self.instructions_left.checked_sub(1) {
+            Some(n) => n,
+            None => return Err(Error::Misc("Function has too many instructions")),
+        };
+        // Nested instructions are recorded while this one is decoded, so reserve its entry
+        let entry = self.disassembly.len();
+        self.disassembly.push((self.binary_offset(), String::new()));
+        let inst = match self.decode_inst() {
+            Ok(i) => i,
+            Err(e) => {
+                self.disassembly.truncate(entry);
+                return Err(e);
             }
-            0x03 => {
-                let _ = self.read_int::<u64>()?; // Block type, which we might need to implement later
-                let mut block_instructions: Vec<Box<dyn Instruction>> = Vec::new();
-                while let Some(inst) = self.read_inst()? {
-                    block_instructions.push(inst);
-                }
-                inst!(Block::new(BlockContinuation::Loop, block_instructions))
-            }
-            0x0B => Ok(None),
-            0x0C => inst!(Branch::new(self.read_int()?)),
-            0x0D => inst!(BranchIf::new(self.read_int()?)),
-            0x0F => inst!(Return::new()),
-            0x10 => inst!(Call::new(self.read_int()?)),
-            0x20 => inst!(LocalGet::new(self.read_int()?)),
-            0x21 => inst!(LocalSet::new(self.read_int()?)),
-            0x22 => inst!(LocalTee::new(self.read_int()?)),
-            0x28 => inst!(Load::new(
-                PrimitiveType::I32,
-                32,
-                self.read_int()?,
-                self.read_int()?
-            )),
-            0x29 => inst!(Load::new(
-                PrimitiveType::I64,
-                64,
-                self.read_int()?,
-                self.read_int()?
-            )),
-            0x2A => inst!(Load::new(
-                PrimitiveType::F32,
-                32,
-                self.read_int()?,
-                self.read_int()?
-            )),
-            0x2B => inst!(Load::new(
-                PrimitiveType::F64,
-                64,
-                self.read_int()?,
-                self.read_int()?
-            )),
-            0x36 => inst!(Store::new(32, self.read_int()?, self.read_int()?)),
-            0x37 => inst!(Store::new(64, self.read_int()?, self.read_int()?)),
-            0x38 => inst!(Store::new(32, self.read_int()?, self.read_int()?)),
-            0x39 => inst!(Store::new(64, self.read_int()?, self.read_int()?)),
-            0x41 => inst!(Const::new(Value::new(self.read_signed_int::<i32>()?))),
-            0x42 => inst!(Const::new(Value::new(self.read_signed_int::<i64>()?))),
-            0x43 => inst!(Const::new(Value::new(self.read_f32()?))),
-            0x44 => inst!(Const::new(Value::new(self.read_f64()?))),
-            0x45 => inst!(ITestOpEqz::new(PrimitiveType::I32)),
-            0x46 => inst!(RelOp::new(PrimitiveType::I32, RelOpType::Eq)),
-            0x47 => inst!(RelOp::new(PrimitiveType::I32, RelOpType::Neq)),
-            0x48 => inst!(RelOp::new(
-                PrimitiveType::I32,
-                RelOpType::Lt(Signedness::Signed)
-            )),
-            0x49 => inst!(RelOp::new(
-                PrimitiveType::I32,
-                RelOpType::Lt(Signedness::Unsigned)
-            )),
-            0x4A => inst!(RelOp::new(
-                PrimitiveType::I32,
-                RelOpType::Gt(Signedness::Signed)
-            )),
-            0x4B => inst!(RelOp::new(
-                PrimitiveType::I32,
-                RelOpType::Gt(Signedness::Unsigned)
-            )),
-            0x4C => inst!(RelOp::new(
-                PrimitiveType::I32,
-                RelOpType::Le(Signedness::Signed)
-            )),
-            0x4D => inst!(RelOp::new(
-                PrimitiveType::I32,
-                RelOpType::Le(Signedness::Unsigned)
-            )),
-            0x4E => inst!(RelOp::new(
-                PrimitiveType::I32,
-                RelOpType::Ge(Signedness::Signed)
-            )),
-            0x4F => inst!(RelOp::new(
-                PrimitiveType::I32,
-                RelOpType::Ge(Signedness::Unsigned)
-            )),
-            0x50 => inst!(ITestOpEqz::new(PrimitiveType::I64)),
-            0x51 => inst!(RelOp::new(PrimitiveType::I64, RelOpType::Eq)),
-            0x52 => inst!(RelOp::new(PrimitiveType::I64, RelOpType::Neq)),
-            0x53 => inst!(RelOp::new(
-                PrimitiveType::I64,
-                RelOpType::Lt(Signedness::Signed)
-            )),
-            0x54 => inst!(RelOp::new(
-                PrimitiveType::I64,
-                RelOpType::Lt(Signedness::Unsigned)
-            )),
-            0x55 => inst!(RelOp::new(
-                PrimitiveType::I64,
-                RelOpType::Gt(Signedness::Signed)
-            )),
-            0x56 => inst!(RelOp::new(
-                PrimitiveType::I64,
-                RelOpType::Gt(Signedness::Unsigned)
-            )),
-            0x57 => inst!(RelOp::new(
-                PrimitiveType::I64,
-                RelOpType::Le(Signedness::Signed)
-            )),
-            0x58 => inst!(RelOp::new(
-                PrimitiveType::I64,
-                RelOpType::Le(Signedness::Unsigned)
-            )),
-            0x59 => inst!(RelOp::new(
-                PrimitiveType::I64,
-                RelOpType::Ge(Signedness::Signed)
-            )),
-            0x5A => inst!(RelOp::new(
-                PrimitiveType::I64,
-                RelOpType::Ge(Signedness::Unsigned)
-            )),
-            0x5B => inst!(RelOp::new(PrimitiveType::F32, RelOpType::Eq)),
-            0x5C => inst!(RelOp::new(PrimitiveType::F32, RelOpType::Neq)),
-            0x5D => inst!(RelOp::new(
-                PrimitiveType::F32,
-                RelOpType::Lt(Signedness::Signed)
-            )),
-            0x5E => inst!(RelOp::new(
-                PrimitiveType::F32,
-                RelOpType::Gt(Signedness::Signed)
-            )),
-            0x5F => inst!(RelOp::new(
-                PrimitiveType::F32,
-                RelOpType::Le(Signedness::Signed)
-            )),
-            0x60 => inst!(RelOp::new(
-                PrimitiveType::F32,
-                RelOpType::Ge(Signedness::Signed)
-            )),
-            0x61 => inst!(RelOp::new(PrimitiveType::F64, RelOpType::Eq)),
-            0x62 => inst!(RelOp::new(PrimitiveType::F64, RelOpType::Neq)),
-            0x63 => inst!(RelOp::new(
-                PrimitiveType::F64,
-                RelOpType::Lt(Signedness::Signed)
-            )),
-            0x64 => inst!(RelOp::new(
-                PrimitiveType::F64,
-                RelOpType::Gt(Signedness::Signed)
-            )),
-            0x65 => inst!(RelOp::new(
-                PrimitiveType::F64,
-                RelOpType::Le(Signedness::Signed)
-            )),
-            0x66 => inst!(RelOp::new(
-                PrimitiveType::F64,
-                RelOpType::Ge(Signedness::Signed)
-            )),
-            0x67 => inst!(IUnOp::new(PrimitiveType::I32, IUnOpType::Clz)),
-            0x68 => inst!(IUnOp::new(PrimitiveType::I32, IUnOpType::Ctz)),
-            0x69 => inst!(IUnOp::new(PrimitiveType::I32, IUnOpType::Popcnt)),
-            0x6A => inst!(IBinOp::new(PrimitiveType::I32, IBinOpType::Add)),
-            0x6B => inst!(IBinOp::new(PrimitiveType::I32, IBinOpType::Sub)),
-            0x6C => inst!(IBinOp::new(PrimitiveType::I32, IBinOpType::Mul)),
-            0x6D => inst!(IBinOp::new(
-                PrimitiveType::I32,
-                IBinOpType::Div(Signedness::Signed)
-            )),
-            0x6E => inst!(IBinOp::new(
-                PrimitiveType::I32,
-                IBinOpType::Div(Signedness::Unsigned)
-            )),
-            0x6F => inst!(IBinOp::new(
-                PrimitiveType::I32,
-                IBinOpType::Rem(Signedness::Signed)
-            )),
-            0x70 => inst!(IBinOp::new(
-                PrimitiveType::I32,
-                IBinOpType::Rem(Signedness::Unsigned)
-            )),
-            0x71 => inst!(IBinOp::new(PrimitiveType::I32, IBinOpType::And)),
-            0x72 => inst!(IBinOp::new(PrimitiveType::I32, IBinOpType::Or)),
-            0x73 => inst!(IBinOp::new(PrimitiveType::I32, IBinOpType::Xor)),
-            0x74 => inst!(IBinOp::new(PrimitiveType::I32, IBinOpType::Shl)),
-            0x75 => inst!(IBinOp::new(
-                PrimitiveType::I32,
-                IBinOpType::Shr(Signedness::Signed)
-            )),
-            0x76 => inst!(IBinOp::new(
-                PrimitiveType::I32,
-                IBinOpType::Shr(Signedness::Unsigned)
-            )),
-            0x77 => inst!(IBinOp::new(PrimitiveType::I32, IBinOpType::Rotl)),
-            0x78 => inst!(IBinOp::new(PrimitiveType::I32, IBinOpType::Rotr)),
-
-            0x79 => inst!(IUnOp::new(PrimitiveType::I64, IUnOpType::Clz)),
-            0x7A => inst!(IUnOp::new(PrimitiveType::I64, IUnOpType::Ctz)),
-            0x7B => inst!(IUnOp::new(PrimitiveType::I64, IUnOpType::Popcnt)),
-            0x7C => inst!(IBinOp::new(PrimitiveType::I64, IBinOpType::Add)),
-            0x7D => inst!(IBinOp::new(PrimitiveType::I64, IBinOpType::Sub)),
-            0x7E => inst!(IBinOp::new(PrimitiveType::I64, IBinOpType::Mul)),
-            0x7F => inst!(IBinOp::new(
-                PrimitiveType::I64,
-                IBinOpType::Div(Signedness::Signed)
-            )),
-            0x80 => inst!(IBinOp::new(
-                PrimitiveType::I64,
-                IBinOpType::Div(Signedness::Unsigned)
-            )),
-            0x81 => inst!(IBinOp::new(
-                PrimitiveType::I64,
-                IBinOpType::Rem(Signedness::Signed)
-            )),
-            0x82 => inst!(IBinOp::new(
-                PrimitiveType::I64,
-                IBinOpType::Rem(Signedness::Unsigned)
-            )),
-            0x83 => inst!(IBinOp::new(PrimitiveType::I64, IBinOpType::And)),
-            0x84 => inst!(IBinOp::new(PrimitiveType::I64, IBinOpType::Or)),
-            0x85 => inst!(IBinOp::new(PrimitiveType::I64, IBinOpType::Xor)),
-            0x86 => inst!(IBinOp::new(PrimitiveType::I64, IBinOpType::Shl)),
-            0x87 => inst!(IBinOp::new(
-                PrimitiveType::I64,
-                IBinOpType::Shr(Signedness::Signed)
-            )),
-            0x88 => inst!(IBinOp::new(
-                PrimitiveType::I64,
-                IBinOpType::Shr(Signedness::Unsigned)
+        };
+        self.disassembly[entry].1 = match &inst {
+            Some(i) => i.mnemonic(),
+            None => String::from("end"),
+        };
+        Ok(inst)
+    }
+
+    /// Reads the alignment, memory index and offset of a load or store. The memory index is
+    /// only encoded, after the alignment, when bit 6 of the alignment is set.
+    fn read_memarg(&mut self) -> Result<MemArg, Error> {
+        let flags = self.read_int::<u32>()?;
+        let memory_index = if flags & 0x40 != 0 {
+            self.read_int()?
+        } else {
+            0
+        };
+        Ok(MemArg {
+            align: flags & !0x40,
+            memory_index: self.check_memory_index(memory_index)?,
+            offset: self.read_int()?,
+        })
+    }
+
+    /// Same as `read_memarg`, but atomic accesses must declare their natural alignment
+    fn read_atomic_memarg(&mut self, bitwidth: u8) -> Result<MemArg, Error> {
+        let memarg = self.read_memarg()?;
+        if 1_u32.checked_shl(memarg.align) != Some((bitwidth / 8) as u32) {
+            return Err(Error::UnexpectedData(
+                "Expected atomic alignment to equal the access width",
+            ));
+        }
+        Ok(memarg)
+    }
+
+    /// Reads the memory index of `memory.size`, `memory.grow` or `memory.init`
+    fn read_memory_index(&mut self) -> Result<usize, Error> {
+        let index = self.read_int()?;
+        self.check_memory_index(index)
+    }
+
+    /// Gives back `index` if it names one of the module's memories
+    fn check_memory_index(&self, index: usize) -> Result<usize, Error> {
+        if index >= self.num_memories {
+            return Err(Error::UnexpectedData("Expected a valid memory index"));
+        }
+        Ok(index)
+    }
+
+    /// Reads the data segment index of `memory.init` or `data.drop`, which the data count
+    /// section must have declared
+    fn read_data_index(&mut self) -> Result<usize, Error> {
+        let data_index = self.read_int::<u32>()?;
+        match self.data_count {
+            Some(count) if data_index < count => Ok(data_index as usize),
+            Some(_) => Err(Error::UnexpectedData("Expected a valid data segment index")),
+            None => Err(Error::UnexpectedData(
+                "Expected a data count section before memory.init or data.drop",
             )),
-            0x89 => inst!(IBinOp::new(PrimitiveType::I64, IBinOpType::Rotl)),
-            0x8A => inst!(IBinOp::new(PrimitiveType::I64, IBinOpType::Rotr)),
-
-            0x8B => inst!(FUnOp::new(PrimitiveType::F32, FUnOpType::Abs)),
-            0x8C => inst!(FUnOp::new(PrimitiveType::F32, FUnOpType::Neg)),
-            0x8D => inst!(FUnOp::new(PrimitiveType::F32, FUnOpType::Ceil)),
-            0x8E => inst!(FUnOp::new(PrimitiveType::F32, FUnOpType::Floor)),
-            0x8F => inst!(FUnOp::new(PrimitiveType::F32, FUnOpType::Trunc)),
-            0x90 => inst!(FUnOp::new(PrimitiveType::F32, FUnOpType::Nearest)),
-            0x91 => inst!(FUnOp::new(PrimitiveType::F32, FUnOpType::Sqrt)),
-            0x92 => inst!(FBinOp::new(PrimitiveType::F32, FBinOpType::Add)),
-            0x93 => inst!(FBinOp::new(PrimitiveType::F32, FBinOpType::Sub)),
-            0x94 => inst!(FBinOp::new(PrimitiveType::F32, FBinOpType::Mul)),
-            0x95 => inst!(FBinOp::new(PrimitiveType::F32, FBinOpType::Div)),
-            0x96 => inst!(FBinOp::new(PrimitiveType::F32, FBinOpType::Min)),
-            0x97 => inst!(FBinOp::new(PrimitiveType::F32, FBinOpType::Max)),
-            0x98 => inst!(FBinOp::new(PrimitiveType::F32, FBinOpType::CopySign)),
-
-            0x99 => inst!(FUnOp::new(PrimitiveType::F64, FUnOpType::Abs)),
-            0x9A => inst!(FUnOp::new(PrimitiveType::F64, FUnOpType::Neg)),
-            0x9B => inst!(FUnOp::new(PrimitiveType::F64, FUnOpType::Ceil)),
-            0x9C => inst!(FUnOp::new(PrimitiveType::F64, FUnOpType::Floor)),
-            0x9D => inst!(FUnOp::new(PrimitiveType::F64, FUnOpType::Trunc)),
-            0x9E => inst!(FUnOp::new(PrimitiveType::F64, FUnOpType::Nearest)),
-            0x9F => inst!(FUnOp::new(PrimitiveType::F64, FUnOpType::Sqrt)),
-            0xA0 => inst!(FBinOp::new(PrimitiveType::F64, FBinOpType::Add)),
-            0xA1 => inst!(FBinOp::new(PrimitiveType::F64, FBinOpType::Sub)),
-            0xA2 => inst!(FBinOp::new(PrimitiveType::F64, FBinOpType::Mul)),
-            0xA3 => inst!(FBinOp::new(PrimitiveType::F64, FBinOpType::Div)),
-            0xA4 => inst!(FBinOp::new(PrimitiveType::F64, FBinOpType::Min)),
-            0xA5 => inst!(FBinOp::new(PrimitiveType::F64, FBinOpType::Max)),
-            0xA6 => inst!(FBinOp::new(PrimitiveType::F64, FBinOpType::CopySign)),
-
-            0xA7 => inst!(CvtOp::new(CvtOpType::Wrap)),
-            0xA8 => inst!(CvtOp::new(CvtOpType::Trunc(
-                Signedness::Signed,
-                PrimitiveType::F32,
-                PrimitiveType::I32
-            ))),
-            0xA9 => inst!(CvtOp::new(CvtOpType::Trunc(
-                Signedness::Unsigned,
-                PrimitiveType::F32,
-                PrimitiveType::I32,
-            ))),
-            0xAA => inst!(CvtOp::new(CvtOpType::Trunc(
-                Signedness::Signed,
-                PrimitiveType::F64,
-                PrimitiveType::I32,
-            ))),
-            0xAB => inst!(CvtOp::new(CvtOpType::Trunc(
-                Signedness::Unsigned,
-                PrimitiveType::F64,
-                PrimitiveType::I32,
-            ))),
-            0xAC => inst!(CvtOp::new(CvtOpType::Extend(Signedness::Signed))),
-            0xAD => inst!(CvtOp::new(CvtOpType::Extend(Signedness::Unsigned))),
-            0xAE => inst!(CvtOp::new(CvtOpType::Trunc(
-                Signedness::Signed,
-                PrimitiveType::F32,
-                PrimitiveType::I64,
-            ))),
-            0xAF => inst!(CvtOp::new(CvtOpType::Trunc(
-                Signedness::Unsigned,
-                PrimitiveType::F32,
-                PrimitiveType::I64,
-            ))),
-            0xB0 => inst!(CvtOp::new(CvtOpType::Trunc(
-                Signedness::Signed,
-                PrimitiveType::F64,
-                PrimitiveType::I64,
-            ))),
-            0xB1 => inst!(CvtOp::new(CvtOpType::Trunc(
-                Signedness::Unsigned,
-                PrimitiveType::F64,
-                PrimitiveType::I64,
-            ))),
-            0xB2 => inst!(CvtOp::new(CvtOpType::Convert(
-                Signedness::Signed,
-                PrimitiveType::I32,
-                PrimitiveType::F32,
-            ))),
-            0xB3 => inst!(CvtOp::new(CvtOpType::Convert(
-                Signedness::Unsigned,
-                PrimitiveType::I32,
-                PrimitiveType::F32,
-            ))),
-            0xB4 => inst!(CvtOp::new(CvtOpType::Convert(
-                Signedness::Signed,
-                PrimitiveType::I64,
-                PrimitiveType::F32,
-            ))),
-            0xB5 => inst!(CvtOp::new(CvtOpType::Convert(
-                Signedness::Unsigned,
-                PrimitiveType::I64,
-                PrimitiveType::F32,
-            ))),
-
-            0xB6 => inst!(CvtOp::new(CvtOpType::Demote)),
-            0xB7 => inst!(CvtOp::new(CvtOpType::Convert(
-                Signedness::Signed,
-                PrimitiveType::I32,
-                PrimitiveType::F64,
-            ))),
-            0xB8 => inst!(CvtOp::new(CvtOpType::Convert(
-                Signedness::Unsigned,
-                PrimitiveType::I32,
-                PrimitiveType::F64,
-            ))),
-            0xB9 => inst!(CvtOp::new(CvtOpType::Convert(
-                Signedness::Signed,
-                PrimitiveType::I64,
-                PrimitiveType::F64,
-            ))),
-            0xBA => inst!(CvtOp::new(CvtOpType::Convert(
-                Signedness::Unsigned,
-                PrimitiveType::I64,
-                PrimitiveType::F64,
-            ))),
-            0xBB => inst!(CvtOp::new(CvtOpType::Promote)),
-
-            0xBC => inst!(CvtOp::new(CvtOpType::Reinterpret(PrimitiveType::F32))),
-            0xBD => inst!(CvtOp::new(CvtOpType::Reinterpret(PrimitiveType::F64))),
-            0xBE => inst!(CvtOp::new(CvtOpType::Reinterpret(PrimitiveType::I32))),
-            0xBF => inst!(CvtOp::new(CvtOpType::Reinterpret(PrimitiveType::I64))),
-
-            0xFC => match self.read_byte()? {
-                0x0 => inst!(CvtOp::new(CvtOpType::TruncSat(
-                    Signedness::Signed,
-                    PrimitiveType::F32,
-                    PrimitiveType::I32,
-                ))),
-                0x1 => inst!(CvtOp::new(CvtOpType::TruncSat(
-                    Signedness::Unsigned,
-                    PrimitiveType::F32,
-                    PrimitiveType::I32,
-                ))),
-                0x2 => inst!(CvtOp::new(CvtOpType::TruncSat(
-                    Signedness::Signed,
-                    PrimitiveType::F64,
-                    PrimitiveType::I32,
-                ))),
-                0x3 => inst!(CvtOp::new(CvtOpType::TruncSat(
-                    Signedness::Unsigned,
-                    PrimitiveType::F64,
-                    PrimitiveType::I32,
-                ))),
-                0x4 => inst!(CvtOp::new(CvtOpType::TruncSat(
-                    Signedness::Signed,
-                    PrimitiveType::F32,
-                    PrimitiveType::I64,
-                ))),
-                0x5 => inst!(CvtOp::new(CvtOpType::TruncSat(
-                    Signedness::Unsigned,
-                    PrimitiveType::F32,
-                    PrimitiveType::I64,
-                ))),
-                0x6 => inst!(CvtOp::new(CvtOpType::TruncSat(
-                    Signedness::Signed,
-                    PrimitiveType::F64,
-                    PrimitiveType::I64,
-                ))),
-                0x7 => inst!(CvtOp::new(CvtOpType::TruncSat(
-                    Signedness::Unsigned,
-                    PrimitiveType::F64,
-                    PrimitiveType::I64,
-                ))),
-                x => Err(Error::UnknownSecondaryOpcode(x as u64)),
-            },
+        }
+    }
+
+    /// Reads the index of `local.get`, `local.set` or `local.tee`, which must name one of the
+    /// function's params or locals
+    fn read_local_index(&mut self) -> Result<usize, Error> {
+        let index = self.read_int()?;
+        if index >= self.num_locals {
+            return Err(Error::UnexpectedData("Expected a valid local index"));
+        }
+        Ok(index)
+    }
+
+    /// Reads the index of the function a `call` names, which may be imported or defined
+    fn read_function_index(&mut self) -> Result<usize, Error> {
+        let index = self.read_int()?;
+        if index >= self.num_functions {
+            return Err(Error::UnexpectedData("Expected a valid function index"));
+        }
+        Ok(index)
+    }
+
+    /// Reads the table index immediate of a table instruction, giving it along with the
+    /// table's element type
+    fn read_table_index(&mut self) -> Result<(usize, PrimitiveType), Error> {
+        let index = self.read_int()?;
+        match self.table_types.get(index) {
+            Some(t) => Ok((index, *t)),
+            None => Err(Error::UnexpectedData("Expected a valid table index")),
+        }
+    }
+
+    /// Reads an element segment index, giving it along with the segment's reference type
+    fn read_element_index(&mut self) -> Result<(usize, PrimitiveType), Error> {
+        let index = self.read_int()?;
+        match self.element_types.get(index) {
+            Some(t) => Ok((index, *t)),
+            None => Err(Error::UnexpectedData("Expected a valid element index")),
+        }
+    }
+
+    /// Reads the index of a table that must hold function references
+    fn read_funcref_table_index(&mut self) -> Result<usize, Error> {
+        match self.read_table_index()? {
+            (index, PrimitiveType::FuncRef) => Ok(index),
+            _ => Err(Error::UnexpectedData("Expected a funcref table")),
+        }
+    }
+
+    /// Reads instructions up to the `end` of a block, keeping track of how deeply blocks nest
+    fn read_block_body(&mut self) -> Result<Vec<Box<dyn Instruction>>, Error> {
+        let (block_instructions, _) = self.read_block_body_until(&[0x0B])?;
+        Ok(block_instructions)
+    }
+
+    /// Reads instructions up to one of the `terminators` opcodes, e.g. `catch` in a `try`
+    /// block, returning them along with the terminator that was found
+    fn read_block_body_until(
+        &mut self,
+        terminators: &[u8],
+    ) -> Result<(Vec<Box<dyn Instruction>>, u8), Error> {
+        if self.block_depth >= MAX_BLOCK_DEPTH {
+            return Err(Error::Misc("Blocks are nested too deeply"));
+        }
+        self.block_depth += 1;
+        let mut block_instructions: Vec<Box<dyn Instruction>> = Vec::new();
+        let terminator = loop {
+            let opcode = self.peek_byte()?;
+            if terminators.contains(&opcode) {
+                let mnemonic = match opcode {
+                    0x05 => "else",
+                    0x07 => "catch",
+                    0x19 => "catch_all",
+                    _ => "end",
+                };
+                self.disassembly
+                    .push((self.binary_offset(), String::from(mnemonic)));
+                self.offset += 1;
+                break opcode;
+            }
+            match self.read_inst()? {
+                Some(inst) => block_instructions.push(inst),
+                None => return Err(Error::UnexpectedData("Expected a block terminator")),
+            }
+        };
+        self.block_depth -= 1;
+        Ok((block_instructions, terminator))
+    }
+
+    /// Reads the locals and instructions of a function body into `function`
+    fn read_function_body(
+        &mut self,
+        function: &mut Function,
+        limits: &ParseLimits,
+    ) -> Result<(), Error> {
+        // length of the implicit vector containing one tuple (count, type) for each type of local
+        let locals_types = self.read_int()?;
+
+        for _ in 0..locals_types {
+            let num_locals: usize = self.read_int()?; // number of locals of type `typ`
+            let typ = self.read_primitive_type()?;
+            if num_locals > MAX_LOCALS - function.num_locals().min(MAX_LOCALS) {
+                return Err(Error::Misc("Function declares too many locals"));
+            }
+            function.new_locals(num_locals, typ);
+        }
+
+        self.instructions_left = limits.max_function_instructions;
+        self.num_locals = function.num_params() + function.num_locals();
+        while let Some(i) = self.read_inst()? {
+            function.push_inst(i);
+        }
+        Ok(())
+    }
 
-            x => Err(Error::UnknownOpcode(x as u64)),
+    /// Reads the `catch` and `catch_all` clauses of a `try`, after its body
+    fn read_catches(&mut self, arity: usize, mut terminator: u8) -> Result<Vec<Catch>, Error> {
+        let mut catches = Vec::new();
+        loop {
+            let tag = match terminator {
+                0x07 => Some(self.read_int()?),
+                0x19 => None,
+                0x0B => return Ok(catches),
+                _ => return Err(Error::Misc("delegate is unimplemented")),
+            };
+            let (instructions, next) = self.read_block_body_until(&[0x07, 0x19, 0x0B])?;
+            catches.push(Catch::new(
+                tag,
+                Block::new(BlockContinuation::Branch, arity, instructions),
+            ));
+            terminator = next;
         }
     }
 
@@ -592,7 +548,36 @@ impl ByteReader {
             0x7E => Ok(PrimitiveType::I64),
             0x7D => Ok(PrimitiveType::F32),
             0x7C => Ok(PrimitiveType::F64),
-            _ => Err(Error::UnexpectedData("Expected a number type")),
+            0x70 => Ok(PrimitiveType::FuncRef),
+            0x6F => Ok(PrimitiveType::ExternRef),
+            _ => Err(Error::UnexpectedData("Expected a value type")),
+        }
+    }
+
+    /// Reads a block type: empty, a single result type, or the index of a function type
+    /// giving the block's params and results
+    fn read_block_type(&mut self) -> Result<FunctionType, Error> {
+        match self.peek_byte()? {
+            0x40 => {
+                self.offset += 1;
+                Ok(FunctionType::default())
+            }
+            0x6F | 0x70 | 0x7C..=0x7F => {
+                let t = self.read_primitive_type()?;
+                Ok(FunctionType::new(Vec::new(), vec![t]))
+            }
+            _ => {
+                // The index is a signed 33-bit LEB128, so single-byte value types read as
+                // negative and can't be confused with it
+                let index = self.read_signed_int::<i64>()?;
+                match usize::try_from(index)
+                    .ok()
+                    .and_then(|i| self.function_types.get(i))
+                {
+                    Some(t) => Ok(t.clone()),
+                    None => Err(Error::UnexpectedData("Expected a valid block type")),
+                }
+            }
         }
     }
 
@@ -617,6 +602,201 @@ impl ByteReader {
         Ok(FunctionType::new(param_types, result_types))
     }
 
+    /// Reads the (min, max) page or element counts of a memory or table type
+    fn read_limits(&mut self) -> Result<(u32, u32), Error> {
+        let (min, max) = match self.read_byte()? {
+            0x00 => (self.read_int::<u32>()?, u32::MAX),
+            0x01 => (self.read_int::<u32>()?, self.read_int::<u32>()?),
+            _ => return Err(Error::UnexpectedData("Expected a valid limit type")),
+        };
+        if min > max {
+            return Err(Error::UnexpectedData("Expected limits with min <= max"));
+        }
+        Ok((min, max))
+    }
+
+    /// Reads a memory's limits and builds it, failing if it would take `module` past `limits`
+    fn read_memory_type(&mut self, module: &Module, limits: &ParseLimits) -> Result<Memory, Error> {
+        let (min, max, shared) = self.read_memory_limits()?;
+        if module.num_memories() >= limits.max_memories {
+            return Err(Error::Misc("Module declares too many memories"));
+        }
+        let initial_pages: u64 = (0..module.num_memories())
+            .filter_map(|i| module.memory(i))
+            .map(|m| m.size_pages() as u64)
+            .sum();
+        if initial_pages + min as u64 > limits.max_initial_memory_pages {
+            return Err(Error::Misc("Memories start with too many pages"));
+        }
+        let mut memory = Memory::new(min, max);
+        memory.set_shared(shared);
+        Ok(memory)
+    }
+
+    /// Reads a memory's (min, max) pages and the threads proposal's shared flag
+    fn read_memory_limits(&mut self) -> Result<(u32, u32, bool), Error> {
+        let (min, max, shared) = match self.peek_byte()? {
+            // Shared memories must declare a maximum
+            0x03 => {
+                self.offset += 1;
+                let (min, max) = (self.read_int::<u32>()?, self.read_int::<u32>()?);
+                if min > max {
+                    return Err(Error::UnexpectedData("Expected limits with min <= max"));
+                }
+                (min, max, true)
+            }
+            0x02 => {
+                return Err(Error::UnexpectedData(
+                    "Expected a maximum for a shared memory",
+                ))
+            }
+            _ => {
+                let (min, max) = self.read_limits()?;
+                (min, max, false)
+            }
+        };
+        if min > MAX_PAGES {
+            return Err(Error::UnexpectedData(
+                "Expected memory size within 65536 pages",
+            ));
+        }
+        Ok((min, max, shared))
+    }
+
+    fn read_table_limits(&mut self) -> Result<(u32, u32), Error> {
+        let (min, max) = self.read_limits()?;
+        if min > MAX_TABLE_SIZE {
+            return Err(Error::Misc("Table is too large"));
+        }
+        Ok((min, max))
+    }
+
+    /// Reads `funcref` or `externref`
+    fn read_ref_type(&mut self) -> Result<PrimitiveType, Error> {
+        match self.read_byte()? {
+            0x70 => Ok(PrimitiveType::FuncRef),
+            0x6F => Ok(PrimitiveType::ExternRef),
+            _ => Err(Error::UnexpectedData("Expected a reference type")),
+        }
+    }
+
+    /// Reads a table's element type and limits and builds it, failing if it would take
+    /// `module` past `limits`
+    fn read_table_type(&mut self, module: &Module, limits: &ParseLimits) -> Result<Table, Error> {
+        let element_type = self.read_ref_type()?;
+        let (min, max) = self.read_table_limits()?;
+        if module.num_tables() >= limits.max_tables {
+            return Err(Error::Misc("Module declares too many tables"));
+        }
+        let initial_elements: u64 = (0..module.num_tables())
+            .filter_map(|i| module.table(i))
+            .map(|t| t.size() as u64)
+            .sum();
+        if initial_elements + min as u64 > limits.max_initial_table_elements {
+            return Err(Error::Misc("Tables start with too many elements"));
+        }
+        Ok(Table::with_type(element_type, min, max))
+    }
+
+    /// Evaluates a constant expression, such as a global initializer or a segment offset
+    fn eval_const_expr(&mut self, globals: &[Global]) -> Result<Value, Error> {
+        let value = match self.read_byte()? {
+            0x23 => match globals.get(self.read_int::<usize>()?) {
+                Some(g) if g.mutable => {
+                    return Err(Error::UnexpectedData(
+                        "Expected an immutable global in constant expression",
+                    ))
+                }
+                Some(g) => g.value,
+                None => {
+                    return Err(Error::UnexpectedData(
+                        "Expected a defined global in constant expression",
+                    ))
+                }
+            },
+            0x41 => Value::new(self.read_signed_int::<i32>()?),
+            0x42 => Value::new(self.read_signed_int::<i64>()?),
+            0x43 => self.read_f32()?,
+            0x44 => self.read_f64()?,
+            0xD0 => Value::null(self.read_ref_type()?),
+            0xD2 => Value::func_ref(self.read_int()?),
+            _ => {
+                return Err(Error::UnexpectedData(
+                    "Expected a constant instruction in constant expression",
+                ))
+            }
+        };
+        if self.read_byte()? != 0x0B {
+            return Err(Error::UnexpectedData("Expected end of constant expression"));
+        }
+        Ok(value)
+    }
+
+    /// Evaluates an element expression of a segment holding references of `element_type`
+    fn read_element_expr(&mut self, element_type: PrimitiveType) -> Result<Value, Error> {
+        let element = match self.read_byte()? {
+            0xD0 => {
+                if self.read_ref_type()? != element_type {
+                    return Err(Error::UnexpectedData(
+                        "Expected a null reference of the segment's type",
+                    ));
+                }
+                Value::null(element_type)
+            }
+            0xD2 if element_type == PrimitiveType::FuncRef => Value::func_ref(self.read_int()?),
+            _ => {
+                return Err(Error::UnexpectedData(
+                    "Expected a reference in element expression",
+                ))
+            }
+        };
+        if self.read_byte()? != 0x0B {
+            return Err(Error::UnexpectedData("Expected end of element expression"));
+        }
+        Ok(element)
+    }
+
+    /// Evaluates the offset expression of an active data or element segment
+    fn read_segment_offset(&mut self, globals: &[Global]) -> Result<u64, Error> {
+        Ok(u32::try_from(self.eval_const_expr(globals)?)? as u64)
+    }
+
+    /// Reads a vector of (index, name) pairs
+    fn read_name_map(&mut self) -> Result<BTreeMap<usize, String>, Error> {
+        let mut map = BTreeMap::new();
+        let map_len = self.read_int()?;
+        for _ in 0..map_len {
+            let index = self.read_int()?;
+            map.insert(index, self.read_name()?);
+        }
+        Ok(map)
+    }
+
+    /// Reads the body of the "name" custom section, following its name
+    fn read_names(&mut self) -> Result<Names, Error> {
+        let mut names = Names::default();
+        while self.offset < self.content.len() {
+            let subsection_id = self.read_byte()?;
+            let subsection_len = self.read_int::<usize>()?;
+            match subsection_id {
+                0 => names.module = Some(self.read_name()?),
+                1 => names.functions = self.read_name_map()?,
+                2 => {
+                    let function_vec_len = self.read_int()?;
+                    for _ in 0..function_vec_len {
+                        let function_index = self.read_int()?;
+                        names.locals.insert(function_index, self.read_name_map()?);
+                    }
+                }
+                _ => {
+                    // Subsections from extensions of the name section aren't needed
+                    self.read_bytes(subsection_len)?;
+                }
+            }
+        }
+        Ok(names)
+    }
+
     fn read_name(&mut self) -> Result<String, Error> {
         let name_len = self.read_int()?;
         let name = match String::from_utf8(self.read_bytes(name_len)?) {
@@ -630,56 +810,207 @@ impl ByteReader {
 struct ModuleSection {
     section_type: u8,
     content: ByteReader,
+    /// Errors the section recovered from, when parsing leniently
+    diagnostics: Option<Vec<Error>>,
 }
 
 impl ModuleSection {
-    fn new(section_type: u8, content: &[u8]) -> Self {
+    /// `offset` is where `content` starts in the module binary
+    fn new(section_type: u8, content: &[u8], offset: usize) -> Self {
+        let mut content = ByteReader::new(content);
+        content.base_offset = offset;
         ModuleSection {
             section_type,
-            content: ByteReader::new(content),
+            content,
+            diagnostics: None,
         }
     }
 
-    fn update_module(&mut self, module: &mut Module) -> Result<(), Error> {
+    fn update_module(
+        &mut self,
+        module: &mut Module,
+        imported_globals: &ImportedGlobals,
+        limits: &ParseLimits,
+    ) -> Result<(), Error> {
         match self.section_type {
+            0 => {
+                // Custom section
+                // The body starts with the section's name, which says how to read the rest
+                let name = self.content.read_name()?;
+                if name == "name" {
+                    // Malformed custom sections must not invalidate the module, so just drop them
+                    match self.content.read_names() {
+                        Ok(names) => module.set_names(names),
+                        Err(_) => debug!("Ignoring malformed name section"),
+                    }
+                } else {
+                    debug!("Skipping custom section {}", name);
+                    let rest = self.content.content[self.content.offset..].to_vec();
+                    module.add_custom_section(name, rest);
+                }
+            }
             1 => {
                 // Type section
-                let type_vec_len = self.content.read_int()?;
+                let type_vec_len: usize = self.content.read_int()?;
+                if type_vec_len > limits.max_types {
+                    return Err(Error::Misc("Module declares too many types"));
+                }
                 for _i in 0..type_vec_len {
                     module.add_function_type(self.content.read_function_type()?);
                 }
             }
+            2 => {
+                // Import section
+                let import_vec_len: usize = self.content.read_int()?;
+                if import_vec_len > limits.max_imports {
+                    return Err(Error::Misc("Module declares too many imports"));
+                }
+                for _ in 0..import_vec_len {
+                    let module_name = self.content.read_name()?;
+                    let name = self.content.read_name()?;
+                    let descriptor = match self.content.read_byte()? {
+                        0x00 => {
+                            let type_index = self.content.read_int()?;
+                            let function_type = match module.get_function_type(type_index) {
+                                Some(t) => t,
+                                None => {
+                                    return Err(Error::UnexpectedData(
+                                        "Expected a valid type index",
+                                    ))
+                                }
+                            };
+                            module.add_function(Function::imported(
+                                function_type,
+                                module_name.clone(),
+                                name.clone(),
+                            ));
+                            ImportDescriptor::Function(type_index)
+                        }
+                        0x01 => {
+                            module.add_table(self.content.read_table_type(module, limits)?);
+                            ImportDescriptor::Table
+                        }
+                        0x02 => {
+                            let memory = self.content.read_memory_type(module, limits)?;
+                            module.add_memory(memory);
+                            ImportDescriptor::Memory
+                        }
+                        0x03 => {
+                            let global_type = self.content.read_primitive_type()?;
+                            let mutable = match self.content.read_byte()? {
+                                0x00 => false,
+                                0x01 => true,
+                                _ => {
+                                    return Err(Error::UnexpectedData(
+                                        "Expected a valid mutability",
+                                    ))
+                                }
+                            };
+                            // Imported globals come first in the global index space
+                            let key = (module_name.clone(), name.clone());
+                            let value = match imported_globals.get(&key) {
+                                Some(v) if v.get_type() == global_type => *v,
+                                Some(_) => {
+                                    return Err(Error::Misc(
+                                        "Imported global value has the wrong type",
+                                    ))
+                                }
+                                None => {
+                                    return Err(Error::UnresolvedImport {
+                                        module: module_name,
+                                        field: name,
+                                    })
+                                }
+                            };
+                            module.add_global(Global::new(value, mutable));
+                            ImportDescriptor::Global(global_type, mutable)
+                        }
+                        _ => {
+                            return Err(Error::UnexpectedData(
+                                "Expected a valid import descriptor type",
+                            ))
+                        }
+                    };
+                    module.add_import(Import {
+                        module: module_name,
+                        name,
+                        descriptor,
+                    });
+                }
+            }
+            4 => {
+                // Table section
+                let table_vec_len = self.content.read_int()?;
+                for _ in 0..table_vec_len {
+                    module.add_table(self.content.read_table_type(module, limits)?);
+                }
+            }
             3 => {
                 // Function section
-                let type_index_vec_len = self.content.read_int()?;
+                let type_index_vec_len: usize = self.content.read_int()?;
+                if type_index_vec_len > limits.max_functions {
+                    return Err(Error::Misc("Module declares too many functions"));
+                }
+                // Functions of the same type share one copy of it
+                let mut shared_types: Vec<Option<Rc<FunctionType>>> =
+                    vec![None; module.function_types().len()];
                 for _ in 0..type_index_vec_len {
-                    let type_index = self.content.read_int()?;
-                    let function_type = module.get_function_type(type_index);
+                    let type_index: usize = self.content.read_int()?;
+                    let function_type = match shared_types.get_mut(type_index) {
+                        Some(t) => t
+                            .get_or_insert_with(|| {
+                                Rc::new(module.function_types()[type_index].clone())
+                            })
+                            .clone(),
+                        None => return Err(Error::UnexpectedData("Expected a valid type index")),
+                    };
                     module.add_function(Function::new(function_type))
                 }
             }
             5 => {
                 // Memory section
                 let memory_vec_len = self.content.read_int()?;
-                if memory_vec_len > 1 {
-                    return Err(Error::Misc(
-                        "Multiple memories are unimplemented per WASM spec restrictions.",
-                    ));
-                }
                 for _ in 0..memory_vec_len {
-                    // These are called limits in the spec, could abstract if it's ever used somewhere else
-                    let (mem_min, mem_max) = match self.content.read_byte()? {
-                        0x00 => (self.content.read_int::<u32>()?, u32::MAX),
-                        0x01 => (
-                            self.content.read_int::<u32>()?,
-                            self.content.read_int::<u32>()?,
-                        ),
-                        _ => return Err(Error::UnexpectedData("Expected a valid limit type")),
-                    };
-                    let memory = Memory::new(mem_min, mem_max);
+                    let memory = self.content.read_memory_type(module, limits)?;
                     module.add_memory(memory);
                 }
             }
+            6 => {
+                // Global section
+                let global_vec_len = self.content.read_int()?;
+                for _ in 0..global_vec_len {
+                    let global_type = self.content.read_primitive_type()?;
+                    let mutable = match self.content.read_byte()? {
+                        0x00 => false,
+                        0x01 => true,
+                        _ => return Err(Error::UnexpectedData("Expected a valid mutability")),
+                    };
+                    let value = self.content.eval_const_expr(module.globals())?;
+                    if value.get_type() != global_type {
+                        return Err(Error::UnexpectedData(
+                            "Expected global initializer to match the global's type",
+                        ));
+                    }
+                    module.add_global(Global::new(value, mutable));
+                }
+            }
+            13 => {
+                // Tag section
+                let tag_vec_len = self.content.read_int()?;
+                for _ in 0..tag_vec_len {
+                    if self.content.read_byte()? != 0x00 {
+                        return Err(Error::UnexpectedData("Expected an exception tag attribute"));
+                    }
+                    let tag_type = match module.get_function_type(self.content.read_int()?) {
+                        Some(t) => t,
+                        None => return Err(Error::UnexpectedData("Expected a valid type index")),
+                    };
+                    if !tag_type.returns.is_empty() {
+                        return Err(Error::UnexpectedData("Expected a tag type without results"));
+                    }
+                    module.add_tag(tag_type);
+                }
+            }
             7 => {
                 // Export section
                 let export_vec_len = self.content.read_int()?;
@@ -704,59 +1035,990 @@ impl ModuleSection {
                     }
                 }
             }
+            8 => {
+                // Start section
+                module.set_start(self.content.read_int()?);
+            }
+            9 => {
+                // Element section
+                let element_vec_len = self.content.read_int()?;
+                for _ in 0..element_vec_len {
+                    // Bit 0 marks passive or declarative segments, bit 1 an explicit table index
+                    // (or declarative, if not active), and bit 2 elements given as expressions
+                    let flags = self.content.read_int::<u32>()?;
+                    if flags > 7 {
+                        return Err(Error::UnexpectedData(
+                            "Expected a valid element segment kind",
+                        ));
+                    }
+                    let active = flags & 0b001 == 0;
+                    let declarative = !active && flags & 0b010 != 0;
+                    let (table_index, offset) = if active {
+                        let table_index = if flags & 0b010 != 0 {
+                            self.content.read_int()?
+                        } else {
+                            0
+                        };
+                        if module.table(table_index).is_none() {
+                            return Err(Error::UnexpectedData("Expected a valid table index"));
+                        }
+                        let offset = self.content.read_segment_offset(module.globals())?;
+                        (table_index, Some(offset))
+                    } else {
+                        (0, None)
+                    };
+
+                    // Segments for table 0 leave out the element type, which is then funcref
+                    let element_type = if flags & 0b011 == 0 {
+                        PrimitiveType::FuncRef
+                    } else if flags & 0b100 == 0 {
+                        match self.content.read_byte()? {
+                            0x00 => PrimitiveType::FuncRef,
+                            _ => {
+                                return Err(Error::UnexpectedData("Expected a valid element kind"))
+                            }
+                        }
+                    } else {
+                        self.content.read_ref_type()?
+                    };
+                    if active
+                        && module.table(table_index).map(|t| t.element_type()) != Some(element_type)
+                    {
+                        return Err(Error::UnexpectedData(
+                            "Expected element type to match the table",
+                        ));
+                    }
+
+                    let elements_len = self.content.read_int()?;
+                    let mut elements = Vec::new();
+                    for _ in 0..elements_len {
+                        if flags & 0b100 == 0 {
+                            elements.push(Value::func_ref(self.content.read_int()?));
+                        } else {
+                            elements.push(self.content.read_element_expr(element_type)?);
+                        }
+                    }
+
+                    if let Some(offset) = offset {
+                        module.add_active_element_segment(ActiveElementSegment {
+                            table_index,
+                            offset,
+                            elements,
+                        });
+                        module.add_element_segment(element_type, Vec::new());
+                    } else if declarative {
+                        module.add_element_segment(element_type, Vec::new());
+                    } else {
+                        module.add_element_segment(element_type, elements);
+                    }
+                }
+            }
             10 => {
                 // Code section
-                let functions_vec_len = self.content.read_int()?;
-                for function_index in 0..functions_vec_len {
-                    let _function_len_bytes = self.content.read_int::<usize>()?; /* Needs to be read, but we don't use it */
-                    let function = module.get_mut_function(function_index);
-
-                    // length of the implicit vector containing one tuple (count, type) for each type of local
-                    let locals_types = self.content.read_int()?;
-
-                    for _ in 0..locals_types {
-                        let num_locals: usize = self.content.read_int()?; // number of locals of type `typ`
-                        let typ = self.content.read_primitive_type()?;
-                        function.new_locals(num_locals, typ);
+                self.content.function_types = module.function_types().to_vec();
+                self.content.data_count = module.data_count();
+                self.content.num_memories = module.num_memories();
+                self.content.table_types = (0..module.num_tables())
+                    .filter_map(|i| module.table(i))
+                    .map(|t| t.element_type())
+                    .collect();
+                self.content.element_types = module.element_types().to_vec();
+                self.content.num_functions = module.num_functions();
+                let functions_vec_len: usize = self.content.read_int()?;
+                // Bodies are only given for functions that aren't imported
+                let first_body = module.num_imported_functions();
+                if functions_vec_len != module.num_functions() - first_body {
+                    return Err(Error::UnexpectedData(
+                        "Expected one function body for each declared function",
+                    ));
+                }
+                for function_index in first_body..module.num_functions() {
+                    let function_len_bytes = self.content.read_int::<usize>()?;
+                    let function_end = match self.content.offset.checked_add(function_len_bytes) {
+                        Some(n) if n <= self.content.content.len() => n,
+                        _ => return Err(Error::EndOfData),
+                    };
+                    let function = match module.get_mut_function(function_index) {
+                        Some(f) => f,
+                        None => return Err(Error::Misc("Function index is not valid")),
+                    };
+                    let mut result = self.content.read_function_body(function, limits);
+                    if result.is_ok() && self.content.offset != function_end {
+                        result = Err(Error::UnexpectedData(
+                            "Expected the function body to end where its length says",
+                        ));
                     }
-
-                    loop {
-                        match self.content.read_inst() {
-                            Ok(Some(i)) => function.push_inst(i),
-                            Ok(None) => {
-                                break;
+                    if let Err(e) = result {
+                        match &mut self.diagnostics {
+                            // Bodies are length-prefixed, so the next one can still be found.
+                            // What was read of this one is kept, but ends in a trap. Only the
+                            // first error in a body is reported, as the rest of it is skipped.
+                            Some(diagnostics) => {
+                                diagnostics.push(e);
+                                function.push_inst(Box::new(Unreachable::new()));
+                                self.content.offset = function_end;
+                                self.content.block_depth = 0;
                             }
-                            Err(e) => return Err(e),
+                            None => return Err(e),
                         }
                     }
                 }
+                module.add_disassembly(core::mem::take(&mut self.content.disassembly));
             }
-            x => {
-                eprintln!("Unimplemented section: {:X}", x)
-                // return Err(Error::UnknownSection);
+            11 => {
+                // Data section
+                let data_vec_len = self.content.read_int()?;
+                if module.data_count().is_some_and(|n| n != data_vec_len) {
+                    return Err(Error::UnexpectedData(
+                        "Expected as many data segments as the data count section declared",
+                    ));
+                }
+                for _ in 0..data_vec_len {
+                    let memory_index = match self.content.read_int::<u32>()? {
+                        0x00 => 0,
+                        0x01 => {
+                            // Passive segments are only copied in by `memory.init`
+                            let data_len = self.content.read_int()?;
+                            module.add_data_segment(self.content.read_bytes(data_len)?);
+                            continue;
+                        }
+                        0x02 => self.content.read_int()?,
+                        _ => {
+                            return Err(Error::UnexpectedData("Expected a valid data segment kind"))
+                        }
+                    };
+                    let offset = self.content.read_segment_offset(module.globals())?;
+                    let data_len = self.content.read_int()?;
+                    let bytes = self.content.read_bytes(data_len)?;
+                    if module.memory(memory_index).is_none() {
+                        return Err(Error::UnexpectedData("Expected a valid memory index"));
+                    }
+                    module.add_active_data_segment(ActiveDataSegment {
+                        memory_index,
+                        offset,
+                        bytes,
+                    });
+                    module.add_data_segment(Vec::new());
+                }
+            }
+            12 => {
+                // Data count section
+                module.set_data_count(self.content.read_int()?);
+            }
+            x => {
+                // Keep the raw bytes around so they can still be inspected
+                debug!("Skipping unimplemented section: {:X}", x);
+                module.add_skipped_section(x, self.content.content.clone());
             }
         }
         Ok(())
     }
+
+    /// Adds what the section declares to `summary`. Only the sections with something to count
+    /// are read, so function bodies are never decoded.
+    fn update_summary(&mut self, summary: &mut ModuleSummary) -> Result<(), Error> {
+        match self.section_type {
+            1 => summary.num_types = self.content.read_int()?,
+            2 => {
+                summary.num_imports = self.content.read_int()?;
+                for _ in 0..summary.num_imports {
+                    self.content.read_name()?;
+                    self.content.read_name()?;
+                    match self.content.read_byte()? {
+                        0x00 => {
+                            self.content.read_int::<u32>()?;
+                            summary.num_functions += 1;
+                        }
+                        0x01 => {
+                            self.content.read_byte()?;
+                            self.content.read_table_limits()?;
+                        }
+                        0x02 => {
+                            let (min, max, _) = self.content.read_memory_limits()?;
+                            summary.memory_limits.push((min, max));
+                        }
+                        0x03 => {
+                            self.content.read_primitive_type()?;
+                            self.content.read_byte()?;
+                        }
+                        _ => {
+                            return Err(Error::UnexpectedData(
+                                "Expected a valid import descriptor type",
+                            ))
+                        }
+                    }
+                }
+            }
+            3 => summary.num_functions += self.content.read_int::<usize>()?,
+            5 => {
+                let memory_vec_len = self.content.read_int()?;
+                for _ in 0..memory_vec_len {
+                    let (min, max, _) = self.content.read_memory_limits()?;
+                    summary.memory_limits.push((min, max));
+                }
+            }
+            7 => summary.num_exports = self.content.read_int()?,
+            _ => (),
+        }
+        Ok(())
+    }
+}
+
+// Supporting a new instruction only takes an entry here
+opcode_table! {
+    reader: r;
+    0x00 "unreachable" => inst!(Unreachable::new()),
+    0x02 "block" => {
+        let block_type = r.read_block_type()?;
+        let block_instructions = r.read_block_body()?;
+        inst!(Block::with_type(
+            BlockContinuation::Branch,
+            &block_type,
+            block_instructions
+        ))
+    },
+    0x03 "loop" => {
+        let block_type = r.read_block_type()?;
+        let block_instructions = r.read_block_body()?;
+        inst!(Block::with_type(
+            BlockContinuation::Loop,
+            &block_type,
+            block_instructions
+        ))
+    },
+    0x04 "if" => {
+        let block_type = r.read_block_type()?;
+        let (then_body, terminator) = r.read_block_body_until(&[0x05, 0x0B])?;
+        let else_block = match terminator {
+            0x05 => Some(Block::with_type(
+                BlockContinuation::Branch,
+                &block_type,
+                r.read_block_body()?,
+            )),
+            _ => {
+                // The implicit else leaves the params on the stack as the results
+                if block_type.params != block_type.returns {
+                    return Err(Error::UnexpectedData(
+                        "Expected an if without an else to have matching params and results",
+                    ));
+                }
+                None
+            }
+        };
+        inst!(If::new(
+            Block::with_type(BlockContinuation::Branch, &block_type, then_body),
+            else_block
+        ))
+    },
+    0x06 "try" => {
+        let block_type = r.read_block_type()?;
+        let (body, terminator) = r.read_block_body_until(&[0x07, 0x19, 0x18, 0x0B])?;
+        let catches = r.read_catches(block_type.returns.len(), terminator)?;
+        inst!(Try::new(
+            Block::with_type(BlockContinuation::Branch, &block_type, body),
+            catches
+        ))
+    },
+    0x08 "throw" => inst!(Throw::new(r.read_int()?)),
+    0x0B "end" => Ok(None),
+    0x0C "br" => inst!(Branch::new(r.read_int()?)),
+    0x0D "br_if" => inst!(BranchIf::new(r.read_int()?)),
+    0x0F "return" => inst!(Return::new()),
+    0x10 "call" => inst!(Call::new(r.read_function_index()?)),
+    0x11 "call_indirect" => {
+        let type_index = r.read_int()?;
+        inst!(CallIndirect::new(type_index, r.read_funcref_table_index()?))
+    },
+    0x20 "local.get" => inst!(LocalGet::new(r.read_local_index()?)),
+    0x21 "local.set" => inst!(LocalSet::new(r.read_local_index()?)),
+    0x22 "local.tee" => inst!(LocalTee::new(r.read_local_index()?)),
+    0x23 "global.get" => inst!(GlobalGet::new(r.read_int()?)),
+    0x24 "global.set" => inst!(GlobalSet::new(r.read_int()?)),
+    0x25 "table.get" => inst!(TableGet::new(r.read_table_index()?.0)),
+    0x26 "table.set" => inst!(TableSet::new(r.read_table_index()?.0)),
+    0x28 "i32.load" => inst!(Load::new(PrimitiveType::I32, 32, r.read_memarg()?)),
+    0x29 "i64.load" => inst!(Load::new(PrimitiveType::I64, 64, r.read_memarg()?)),
+    0x2A "f32.load" => inst!(Load::new(PrimitiveType::F32, 32, r.read_memarg()?)),
+    0x2B "f64.load" => inst!(Load::new(PrimitiveType::F64, 64, r.read_memarg()?)),
+    0x36 "i32.store" => inst!(Store::new(PrimitiveType::I32, 32, r.read_memarg()?)),
+    0x37 "i64.store" => inst!(Store::new(PrimitiveType::I64, 64, r.read_memarg()?)),
+    0x38 "f32.store" => inst!(Store::new(PrimitiveType::F32, 32, r.read_memarg()?)),
+    0x39 "f64.store" => inst!(Store::new(PrimitiveType::F64, 64, r.read_memarg()?)),
+    0x3A "i32.store8" => inst!(Store::new(PrimitiveType::I32, 8, r.read_memarg()?)),
+    0x3B "i32.store16" => inst!(Store::new(PrimitiveType::I32, 16, r.read_memarg()?)),
+    0x3C "i64.store8" => inst!(Store::new(PrimitiveType::I64, 8, r.read_memarg()?)),
+    0x3D "i64.store16" => inst!(Store::new(PrimitiveType::I64, 16, r.read_memarg()?)),
+    0x3E "i64.store32" => inst!(Store::new(PrimitiveType::I64, 32, r.read_memarg()?)),
+    0x3F "memory.size" => inst!(MemorySize::new(r.read_memory_index()?)),
+    0x40 "memory.grow" => inst!(MemoryGrow::new(r.read_memory_index()?)),
+    0x41 "i32.const" => inst!(Const::new(Value::new(r.read_signed_int::<i32>()?))),
+    0x42 "i64.const" => inst!(Const::new(Value::new(r.read_signed_int::<i64>()?))),
+    0x43 "f32.const" => inst!(Const::new(r.read_f32()?)),
+    0x44 "f64.const" => inst!(Const::new(r.read_f64()?)),
+    0x45 "i32.eqz" => inst!(ITestOpEqz::new(PrimitiveType::I32)),
+    0x46 "i32.eq" => inst!(RelOp::new(PrimitiveType::I32, RelOpType::Eq)),
+    0x47 "i32.ne" => inst!(RelOp::new(PrimitiveType::I32, RelOpType::Neq)),
+    0x48 "i32.lt_s" => inst!(RelOp::new(
+        PrimitiveType::I32,
+        RelOpType::Lt(Signedness::Signed)
+    )),
+    0x49 "i32.lt_u" => inst!(RelOp::new(
+        PrimitiveType::I32,
+        RelOpType::Lt(Signedness::Unsigned)
+    )),
+    0x4A "i32.gt_s" => inst!(RelOp::new(
+        PrimitiveType::I32,
+        RelOpType::Gt(Signedness::Signed)
+    )),
+    0x4B "i32.gt_u" => inst!(RelOp::new(
+        PrimitiveType::I32,
+        RelOpType::Gt(Signedness::Unsigned)
+    )),
+    0x4C "i32.le_s" => inst!(RelOp::new(
+        PrimitiveType::I32,
+        RelOpType::Le(Signedness::Signed)
+    )),
+    0x4D "i32.le_u" => inst!(RelOp::new(
+        PrimitiveType::I32,
+        RelOpType::Le(Signedness::Unsigned)
+    )),
+    0x4E "i32.ge_s" => inst!(RelOp::new(
+        PrimitiveType::I32,
+        RelOpType::Ge(Signedness::Signed)
+    )),
+    0x4F "i32.ge_u" => inst!(RelOp::new(
+        PrimitiveType::I32,
+        RelOpType::Ge(Signedness::Unsigned)
+    )),
+    0x50 "i64.eqz" => inst!(ITestOpEqz::new(PrimitiveType::I64)),
+    0x51 "i64.eq" => inst!(RelOp::new(PrimitiveType::I64, RelOpType::Eq)),
+    0x52 "i64.ne" => inst!(RelOp::new(PrimitiveType::I64, RelOpType::Neq)),
+    0x53 "i64.lt_s" => inst!(RelOp::new(
+        PrimitiveType::I64,
+        RelOpType::Lt(Signedness::Signed)
+    )),
+    0x54 "i64.lt_u" => inst!(RelOp::new(
+        PrimitiveType::I64,
+        RelOpType::Lt(Signedness::Unsigned)
+    )),
+    0x55 "i64.gt_s" => inst!(RelOp::new(
+        PrimitiveType::I64,
+        RelOpType::Gt(Signedness::Signed)
+    )),
+    0x56 "i64.gt_u" => inst!(RelOp::new(
+        PrimitiveType::I64,
+        RelOpType::Gt(Signedness::Unsigned)
+    )),
+    0x57 "i64.le_s" => inst!(RelOp::new(
+        PrimitiveType::I64,
+        RelOpType::Le(Signedness::Signed)
+    )),
+    0x58 "i64.le_u" => inst!(RelOp::new(
+        PrimitiveType::I64,
+        RelOpType::Le(Signedness::Unsigned)
+    )),
+    0x59 "i64.ge_s" => inst!(RelOp::new(
+        PrimitiveType::I64,
+        RelOpType::Ge(Signedness::Signed)
+    )),
+    0x5A "i64.ge_u" => inst!(RelOp::new(
+        PrimitiveType::I64,
+        RelOpType::Ge(Signedness::Unsigned)
+    )),
+    0x5B "f32.eq" => inst!(RelOp::new(PrimitiveType::F32, RelOpType::Eq)),
+    0x5C "f32.ne" => inst!(RelOp::new(PrimitiveType::F32, RelOpType::Neq)),
+    0x5D "f32.lt" => inst!(RelOp::new(
+        PrimitiveType::F32,
+        RelOpType::Lt(Signedness::Signed)
+    )),
+    0x5E "f32.gt" => inst!(RelOp::new(
+        PrimitiveType::F32,
+        RelOpType::Gt(Signedness::Signed)
+    )),
+    0x5F "f32.le" => inst!(RelOp::new(
+        PrimitiveType::F32,
+        RelOpType::Le(Signedness::Signed)
+    )),
+    0x60 "f32.ge" => inst!(RelOp::new(
+        PrimitiveType::F32,
+        RelOpType::Ge(Signedness::Signed)
+    )),
+    0x61 "f64.eq" => inst!(RelOp::new(PrimitiveType::F64, RelOpType::Eq)),
+    0x62 "f64.ne" => inst!(RelOp::new(PrimitiveType::F64, RelOpType::Neq)),
+    0x63 "f64.lt" => inst!(RelOp::new(
+        PrimitiveType::F64,
+        RelOpType::Lt(Signedness::Signed)
+    )),
+    0x64 "f64.gt" => inst!(RelOp::new(
+        PrimitiveType::F64,
+        RelOpType::Gt(Signedness::Signed)
+    )),
+    0x65 "f64.le" => inst!(RelOp::new(
+        PrimitiveType::F64,
+        RelOpType::Le(Signedness::Signed)
+    )),
+    0x66 "f64.ge" => inst!(RelOp::new(
+        PrimitiveType::F64,
+        RelOpType::Ge(Signedness::Signed)
+    )),
+    0x67 "i32.clz" => inst!(IUnOp::new(PrimitiveType::I32, IUnOpType::Clz)),
+    0x68 "i32.ctz" => inst!(IUnOp::new(PrimitiveType::I32, IUnOpType::Ctz)),
+    0x69 "i32.popcnt" => inst!(IUnOp::new(PrimitiveType::I32, IUnOpType::Popcnt)),
+    0x6A "i32.add" => inst!(IBinOp::new(PrimitiveType::I32, IBinOpType::Add)),
+    0x6B "i32.sub" => inst!(IBinOp::new(PrimitiveType::I32, IBinOpType::Sub)),
+    0x6C "i32.mul" => inst!(IBinOp::new(PrimitiveType::I32, IBinOpType::Mul)),
+    0x6D "i32.div_s" => inst!(IBinOp::new(
+        PrimitiveType::I32,
+        IBinOpType::Div(Signedness::Signed)
+    )),
+    0x6E "i32.div_u" => inst!(IBinOp::new(
+        PrimitiveType::I32,
+        IBinOpType::Div(Signedness::Unsigned)
+    )),
+    0x6F "i32.rem_s" => inst!(IBinOp::new(
+        PrimitiveType::I32,
+        IBinOpType::Rem(Signedness::Signed)
+    )),
+    0x70 "i32.rem_u" => inst!(IBinOp::new(
+        PrimitiveType::I32,
+        IBinOpType::Rem(Signedness::Unsigned)
+    )),
+    0x71 "i32.and" => inst!(IBinOp::new(PrimitiveType::I32, IBinOpType::And)),
+    0x72 "i32.or" => inst!(IBinOp::new(PrimitiveType::I32, IBinOpType::Or)),
+    0x73 "i32.xor" => inst!(IBinOp::new(PrimitiveType::I32, IBinOpType::Xor)),
+    0x74 "i32.shl" => inst!(IBinOp::new(PrimitiveType::I32, IBinOpType::Shl)),
+    0x75 "i32.shr_s" => inst!(IBinOp::new(
+        PrimitiveType::I32,
+        IBinOpType::Shr(Signedness::Signed)
+    )),
+    0x76 "i32.shr_u" => inst!(IBinOp::new(
+        PrimitiveType::I32,
+        IBinOpType::Shr(Signedness::Unsigned)
+    )),
+    0x77 "i32.rotl" => inst!(IBinOp::new(PrimitiveType::I32, IBinOpType::Rotl)),
+    0x78 "i32.rotr" => inst!(IBinOp::new(PrimitiveType::I32, IBinOpType::Rotr)),
+    0x79 "i64.clz" => inst!(IUnOp::new(PrimitiveType::I64, IUnOpType::Clz)),
+    0x7A "i64.ctz" => inst!(IUnOp::new(PrimitiveType::I64, IUnOpType::Ctz)),
+    0x7B "i64.popcnt" => inst!(IUnOp::new(PrimitiveType::I64, IUnOpType::Popcnt)),
+    0x7C "i64.add" => inst!(IBinOp::new(PrimitiveType::I64, IBinOpType::Add)),
+    0x7D "i64.sub" => inst!(IBinOp::new(PrimitiveType::I64, IBinOpType::Sub)),
+    0x7E "i64.mul" => inst!(IBinOp::new(PrimitiveType::I64, IBinOpType::Mul)),
+    0x7F "i64.div_s" => inst!(IBinOp::new(
+        PrimitiveType::I64,
+        IBinOpType::Div(Signedness::Signed)
+    )),
+    0x80 "i64.div_u" => inst!(IBinOp::new(
+        PrimitiveType::I64,
+        IBinOpType::Div(Signedness::Unsigned)
+    )),
+    0x81 "i64.rem_s" => inst!(IBinOp::new(
+        PrimitiveType::I64,
+        IBinOpType::Rem(Signedness::Signed)
+    )),
+    0x82 "i64.rem_u" => inst!(IBinOp::new(
+        PrimitiveType::I64,
+        IBinOpType::Rem(Signedness::Unsigned)
+    )),
+    0x83 "i64.and" => inst!(IBinOp::new(PrimitiveType::I64, IBinOpType::And)),
+    0x84 "i64.or" => inst!(IBinOp::new(PrimitiveType::I64, IBinOpType::Or)),
+    0x85 "i64.xor" => inst!(IBinOp::new(PrimitiveType::I64, IBinOpType::Xor)),
+    0x86 "i64.shl" => inst!(IBinOp::new(PrimitiveType::I64, IBinOpType::Shl)),
+    0x87 "i64.shr_s" => inst!(IBinOp::new(
+        PrimitiveType::I64,
+        IBinOpType::Shr(Signedness::Signed)
+    )),
+    0x88 "i64.shr_u" => inst!(IBinOp::new(
+        PrimitiveType::I64,
+        IBinOpType::Shr(Signedness::Unsigned)
+    )),
+    0x89 "i64.rotl" => inst!(IBinOp::new(PrimitiveType::I64, IBinOpType::Rotl)),
+    0x8A "i64.rotr" => inst!(IBinOp::new(PrimitiveType::I64, IBinOpType::Rotr)),
+    0x8B "f32.abs" => inst!(FUnOp::new(PrimitiveType::F32, FUnOpType::Abs)),
+    0x8C "f32.neg" => inst!(FUnOp::new(PrimitiveType::F32, FUnOpType::Neg)),
+    0x8D "f32.ceil" => inst!(FUnOp::new(PrimitiveType::F32, FUnOpType::Ceil)),
+    0x8E "f32.floor" => inst!(FUnOp::new(PrimitiveType::F32, FUnOpType::Floor)),
+    0x8F "f32.trunc" => inst!(FUnOp::new(PrimitiveType::F32, FUnOpType::Trunc)),
+    0x90 "f32.nearest" => inst!(FUnOp::new(PrimitiveType::F32, FUnOpType::Nearest)),
+    0x91 "f32.sqrt" => inst!(FUnOp::new(PrimitiveType::F32, FUnOpType::Sqrt)),
+    0x92 "f32.add" => inst!(FBinOp::new(PrimitiveType::F32, FBinOpType::Add)),
+    0x93 "f32.sub" => inst!(FBinOp::new(PrimitiveType::F32, FBinOpType::Sub)),
+    0x94 "f32.mul" => inst!(FBinOp::new(PrimitiveType::F32, FBinOpType::Mul)),
+    0x95 "f32.div" => inst!(FBinOp::new(PrimitiveType::F32, FBinOpType::Div)),
+    0x96 "f32.min" => inst!(FBinOp::new(PrimitiveType::F32, FBinOpType::Min)),
+    0x97 "f32.max" => inst!(FBinOp::new(PrimitiveType::F32, FBinOpType::Max)),
+    0x98 "f32.copysign" => inst!(FBinOp::new(PrimitiveType::F32, FBinOpType::CopySign)),
+    0x99 "f64.abs" => inst!(FUnOp::new(PrimitiveType::F64, FUnOpType::Abs)),
+    0x9A "f64.neg" => inst!(FUnOp::new(PrimitiveType::F64, FUnOpType::Neg)),
+    0x9B "f64.ceil" => inst!(FUnOp::new(PrimitiveType::F64, FUnOpType::Ceil)),
+    0x9C "f64.floor" => inst!(FUnOp::new(PrimitiveType::F64, FUnOpType::Floor)),
+    0x9D "f64.trunc" => inst!(FUnOp::new(PrimitiveType::F64, FUnOpType::Trunc)),
+    0x9E "f64.nearest" => inst!(FUnOp::new(PrimitiveType::F64, FUnOpType::Nearest)),
+    0x9F "f64.sqrt" => inst!(FUnOp::new(PrimitiveType::F64, FUnOpType::Sqrt)),
+    0xA0 "f64.add" => inst!(FBinOp::new(PrimitiveType::F64, FBinOpType::Add)),
+    0xA1 "f64.sub" => inst!(FBinOp::new(PrimitiveType::F64, FBinOpType::Sub)),
+    0xA2 "f64.mul" => inst!(FBinOp::new(PrimitiveType::F64, FBinOpType::Mul)),
+    0xA3 "f64.div" => inst!(FBinOp::new(PrimitiveType::F64, FBinOpType::Div)),
+    0xA4 "f64.min" => inst!(FBinOp::new(PrimitiveType::F64, FBinOpType::Min)),
+    0xA5 "f64.max" => inst!(FBinOp::new(PrimitiveType::F64, FBinOpType::Max)),
+    0xA6 "f64.copysign" => inst!(FBinOp::new(PrimitiveType::F64, FBinOpType::CopySign)),
+    0xA7 "i32.wrap_i64" => inst!(CvtOp::new(CvtOpType::Wrap)),
+    0xA8 "i32.trunc_f32_s" => inst!(CvtOp::new(CvtOpType::Trunc(
+        Signedness::Signed,
+        PrimitiveType::F32,
+        PrimitiveType::I32
+    ))),
+    0xA9 "i32.trunc_f32_u" => inst!(CvtOp::new(CvtOpType::Trunc(
+        Signedness::Unsigned,
+        PrimitiveType::F32,
+        PrimitiveType::I32,
+    ))),
+    0xAA "i32.trunc_f64_s" => inst!(CvtOp::new(CvtOpType::Trunc(
+        Signedness::Signed,
+        PrimitiveType::F64,
+        PrimitiveType::I32,
+    ))),
+    0xAB "i32.trunc_f64_u" => inst!(CvtOp::new(CvtOpType::Trunc(
+        Signedness::Unsigned,
+        PrimitiveType::F64,
+        PrimitiveType::I32,
+    ))),
+    0xAC "i64.extend_i32_s" => inst!(CvtOp::new(CvtOpType::Extend(Signedness::Signed))),
+    0xAD "i64.extend_i32_u" => inst!(CvtOp::new(CvtOpType::Extend(Signedness::Unsigned))),
+    0xAE "i64.trunc_f32_s" => inst!(CvtOp::new(CvtOpType::Trunc(
+        Signedness::Signed,
+        PrimitiveType::F32,
+        PrimitiveType::I64,
+    ))),
+    0xAF "i64.trunc_f32_u" => inst!(CvtOp::new(CvtOpType::Trunc(
+        Signedness::Unsigned,
+        PrimitiveType::F32,
+        PrimitiveType::I64,
+    ))),
+    0xB0 "i64.trunc_f64_s" => inst!(CvtOp::new(CvtOpType::Trunc(
+        Signedness::Signed,
+        PrimitiveType::F64,
+        PrimitiveType::I64,
+    ))),
+    0xB1 "i64.trunc_f64_u" => inst!(CvtOp::new(CvtOpType::Trunc(
+        Signedness::Unsigned,
+        PrimitiveType::F64,
+        PrimitiveType::I64,
+    ))),
+    0xB2 "f32.convert_i32_s" => inst!(CvtOp::new(CvtOpType::Convert(
+        Signedness::Signed,
+        PrimitiveType::I32,
+        PrimitiveType::F32,
+    ))),
+    0xB3 "f32.convert_i32_u" => inst!(CvtOp::new(CvtOpType::Convert(
+        Signedness::Unsigned,
+        PrimitiveType::I32,
+        PrimitiveType::F32,
+    ))),
+    0xB4 "f32.convert_i64_s" => inst!(CvtOp::new(CvtOpType::Convert(
+        Signedness::Signed,
+        PrimitiveType::I64,
+        PrimitiveType::F32,
+    ))),
+    0xB5 "f32.convert_i64_u" => inst!(CvtOp::new(CvtOpType::Convert(
+        Signedness::Unsigned,
+        PrimitiveType::I64,
+        PrimitiveType::F32,
+    ))),
+    0xB6 "f32.demote_f64" => inst!(CvtOp::new(CvtOpType::Demote)),
+    0xB7 "f64.convert_i32_s" => inst!(CvtOp::new(CvtOpType::Convert(
+        Signedness::Signed,
+        PrimitiveType::I32,
+        PrimitiveType::F64,
+    ))),
+    0xB8 "f64.convert_i32_u" => inst!(CvtOp::new(CvtOpType::Convert(
+        Signedness::Unsigned,
+        PrimitiveType::I32,
+        PrimitiveType::F64,
+    ))),
+    0xB9 "f64.convert_i64_s" => inst!(CvtOp::new(CvtOpType::Convert(
+        Signedness::Signed,
+        PrimitiveType::I64,
+        PrimitiveType::F64,
+    ))),
+    0xBA "f64.convert_i64_u" => inst!(CvtOp::new(CvtOpType::Convert(
+        Signedness::Unsigned,
+        PrimitiveType::I64,
+        PrimitiveType::F64,
+    ))),
+    0xBB "f64.promote_f32" => inst!(CvtOp::new(CvtOpType::Promote)),
+    0xBC "i32.reinterpret_f32" => inst!(CvtOp::new(CvtOpType::Reinterpret(PrimitiveType::F32))),
+    0xBD "i64.reinterpret_f64" => inst!(CvtOp::new(CvtOpType::Reinterpret(PrimitiveType::F64))),
+    0xBE "f32.reinterpret_i32" => inst!(CvtOp::new(CvtOpType::Reinterpret(PrimitiveType::I32))),
+    0xBF "f64.reinterpret_i64" => inst!(CvtOp::new(CvtOpType::Reinterpret(PrimitiveType::I64))),
+    0xC0 "i32.extend8_s" => inst!(IUnOp::new(PrimitiveType::I32, IUnOpType::ExtendS(8))),
+    0xC1 "i32.extend16_s" => inst!(IUnOp::new(PrimitiveType::I32, IUnOpType::ExtendS(16))),
+    0xC2 "i64.extend8_s" => inst!(IUnOp::new(PrimitiveType::I64, IUnOpType::ExtendS(8))),
+    0xC3 "i64.extend16_s" => inst!(IUnOp::new(PrimitiveType::I64, IUnOpType::ExtendS(16))),
+    0xC4 "i64.extend32_s" => inst!(IUnOp::new(PrimitiveType::I64, IUnOpType::ExtendS(32))),
+    0xD0 "ref.null" => match r.read_primitive_type()? {
+        t @ (PrimitiveType::FuncRef | PrimitiveType::ExternRef) => inst!(RefNull::new(t)),
+        _ => Err(Error::UnexpectedData("Expected a reference type")),
+    },
+    0xD1 "ref.is_null" => inst!(RefIsNull::new()),
+    0xD2 "ref.func" => inst!(RefFunc::new(r.read_int()?)),
+    prefix 0xFC {
+        0x00 "i32.trunc_sat_f32_s" => inst!(CvtOp::new(CvtOpType::TruncSat(
+            Signedness::Signed,
+            PrimitiveType::F32,
+            PrimitiveType::I32,
+        ))),
+        0x01 "i32.trunc_sat_f32_u" => inst!(CvtOp::new(CvtOpType::TruncSat(
+            Signedness::Unsigned,
+            PrimitiveType::F32,
+            PrimitiveType::I32,
+        ))),
+        0x02 "i32.trunc_sat_f64_s" => inst!(CvtOp::new(CvtOpType::TruncSat(
+            Signedness::Signed,
+            PrimitiveType::F64,
+            PrimitiveType::I32,
+        ))),
+        0x03 "i32.trunc_sat_f64_u" => inst!(CvtOp::new(CvtOpType::TruncSat(
+            Signedness::Unsigned,
+            PrimitiveType::F64,
+            PrimitiveType::I32,
+        ))),
+        0x04 "i64.trunc_sat_f32_s" => inst!(CvtOp::new(CvtOpType::TruncSat(
+            Signedness::Signed,
+            PrimitiveType::F32,
+            PrimitiveType::I64,
+        ))),
+        0x05 "i64.trunc_sat_f32_u" => inst!(CvtOp::new(CvtOpType::TruncSat(
+            Signedness::Unsigned,
+            PrimitiveType::F32,
+            PrimitiveType::I64,
+        ))),
+        0x06 "i64.trunc_sat_f64_s" => inst!(CvtOp::new(CvtOpType::TruncSat(
+            Signedness::Signed,
+            PrimitiveType::F64,
+            PrimitiveType::I64,
+        ))),
+        0x07 "i64.trunc_sat_f64_u" => inst!(CvtOp::new(CvtOpType::TruncSat(
+            Signedness::Unsigned,
+            PrimitiveType::F64,
+            PrimitiveType::I64,
+        ))),
+        0x08 "memory.init" => {
+            let data_index = r.read_data_index()?;
+            inst!(MemoryInit::new(data_index, r.read_memory_index()?))
+        },
+        0x09 "data.drop" => inst!(DataDrop::new(r.read_data_index()?)),
+        0x0C "table.init" => {
+            let (element_index, element_type) = r.read_element_index()?;
+            let (table_index, table_type) = r.read_table_index()?;
+            if element_type != table_type {
+                return Err(Error::UnexpectedData(
+                    "Expected element type to match the table",
+                ));
+            }
+            inst!(TableInit::new(element_index, table_index))
+        },
+        0x0D "elem.drop" => inst!(ElemDrop::new(r.read_element_index()?.0)),
+        0x0E "table.copy" => {
+            let (dst_index, dst_type) = r.read_table_index()?;
+            let (src_index, src_type) = r.read_table_index()?;
+            if dst_type != src_type {
+                return Err(Error::UnexpectedData(
+                    "Expected tables with the same element type",
+                ));
+            }
+            inst!(TableCopy::new(dst_index, src_index))
+        },
+        0x0F "table.grow" => inst!(TableGrow::new(r.read_table_index()?.0)),
+        0x10 "table.size" => inst!(TableSize::new(r.read_table_index()?.0)),
+        0x11 "table.fill" => inst!(TableFill::new(r.read_table_index()?.0)),
+    }
+    prefix 0xFE {
+        0x00 "memory.atomic.notify" => inst!(AtomicNotify::new(r.read_atomic_memarg(32)?)),
+        0x01 "memory.atomic.wait32" => inst!(AtomicWait::new(
+            PrimitiveType::I32,
+            r.read_atomic_memarg(32)?
+        )),
+        0x02 "memory.atomic.wait64" => inst!(AtomicWait::new(
+            PrimitiveType::I64,
+            r.read_atomic_memarg(64)?
+        )),
+        0x03 "atomic.fence" => {
+            if r.read_byte()? != 0x00 {
+                return Err(Error::UnexpectedData(
+                    "Expected a zero byte after atomic.fence",
+                ));
+            }
+            inst!(AtomicFence::new())
+        },
+        0x10 "i32.atomic.load" => inst!(AtomicLoad::new(
+            PrimitiveType::I32,
+            32,
+            r.read_atomic_memarg(32)?
+        )),
+        0x11 "i64.atomic.load" => inst!(AtomicLoad::new(
+            PrimitiveType::I64,
+            64,
+            r.read_atomic_memarg(64)?
+        )),
+        0x17 "i32.atomic.store" => inst!(AtomicStore::new(
+            PrimitiveType::I32,
+            32,
+            r.read_atomic_memarg(32)?
+        )),
+        0x18 "i64.atomic.store" => inst!(AtomicStore::new(
+            PrimitiveType::I64,
+            64,
+            r.read_atomic_memarg(64)?
+        )),
+        0x1E "i32.atomic.rmw.add" => inst!(AtomicRmw::new(
+            PrimitiveType::I32,
+            32,
+            AtomicRmwOpType::Add,
+            r.read_atomic_memarg(32)?
+        )),
+        0x1F "i64.atomic.rmw.add" => inst!(AtomicRmw::new(
+            PrimitiveType::I64,
+            64,
+            AtomicRmwOpType::Add,
+            r.read_atomic_memarg(64)?
+        )),
+        0x25 "i32.atomic.rmw.sub" => inst!(AtomicRmw::new(
+            PrimitiveType::I32,
+            32,
+            AtomicRmwOpType::Sub,
+            r.read_atomic_memarg(32)?
+        )),
+        0x26 "i64.atomic.rmw.sub" => inst!(AtomicRmw::new(
+            PrimitiveType::I64,
+            64,
+            AtomicRmwOpType::Sub,
+            r.read_atomic_memarg(64)?
+        )),
+        0x2C "i32.atomic.rmw.and" => inst!(AtomicRmw::new(
+            PrimitiveType::I32,
+            32,
+            AtomicRmwOpType::And,
+            r.read_atomic_memarg(32)?
+        )),
+        0x2D "i64.atomic.rmw.and" => inst!(AtomicRmw::new(
+            PrimitiveType::I64,
+            64,
+            AtomicRmwOpType::And,
+            r.read_atomic_memarg(64)?
+        )),
+        0x33 "i32.atomic.rmw.or" => inst!(AtomicRmw::new(
+            PrimitiveType::I32,
+            32,
+            AtomicRmwOpType::Or,
+            r.read_atomic_memarg(32)?
+        )),
+        0x34 "i64.atomic.rmw.or" => inst!(AtomicRmw::new(
+            PrimitiveType::I64,
+            64,
+            AtomicRmwOpType::Or,
+            r.read_atomic_memarg(64)?
+        )),
+        0x3A "i32.atomic.rmw.xor" => inst!(AtomicRmw::new(
+            PrimitiveType::I32,
+            32,
+            AtomicRmwOpType::Xor,
+            r.read_atomic_memarg(32)?
+        )),
+        0x3B "i64.atomic.rmw.xor" => inst!(AtomicRmw::new(
+            PrimitiveType::I64,
+            64,
+            AtomicRmwOpType::Xor,
+            r.read_atomic_memarg(64)?
+        )),
+        0x41 "i32.atomic.rmw.xchg" => inst!(AtomicRmw::new(
+            PrimitiveType::I32,
+            32,
+            AtomicRmwOpType::Xchg,
+            r.read_atomic_memarg(32)?
+        )),
+        0x42 "i64.atomic.rmw.xchg" => inst!(AtomicRmw::new(
+            PrimitiveType::I64,
+            64,
+            AtomicRmwOpType::Xchg,
+            r.read_atomic_memarg(64)?
+        )),
+    }
+    unsupported {
+        0x09 => Err(Error::Misc("rethrow is unimplemented")),
+    }
+}
+
+/// Every single-byte opcode the parser accepts, with its name in the text format
+pub fn supported_opcodes() -> Vec<(u8, &'static str)> {
+    SUPPORTED_OPCODES.to_vec()
+}
+
+/// Every prefixed opcode the parser accepts, as (prefix byte, secondary opcode, name)
+pub fn supported_secondary_opcodes() -> Vec<(u8, u32, &'static str)> {
+    SUPPORTED_SECONDARY_OPCODES.to_vec()
 }
 
+/// Position of a known section in the order required by the spec.
+/// Custom and unknown sections may appear anywhere, so they have none.
+fn section_order(section_type: u8) -> Option<u8> {
+    match section_type {
+        1 => Some(1),   // Type
+        2 => Some(2),   // Import
+        3 => Some(3),   // Function
+        4 => Some(4),   // Table
+        5 => Some(5),   // Memory
+        13 => Some(6),  // Tag
+        6 => Some(7),   // Global
+        7 => Some(8),   // Export
+        8 => Some(9),   // Start
+        9 => Some(10),  // Element
+        12 => Some(11), // Data count
+        10 => Some(12), // Code
+        11 => Some(13), // Data
+        _ => None,
+    }
+}
+
+/// Load a module from a file. Call `Module::instantiate` on the result to copy in its
+/// segments and run its start function before calling into it.
+#[cfg(feature = "std")]
 pub fn parse_wasm(path: &str) -> Result<Module, Error> {
-    let file = File::open(path).unwrap();
+    parse_wasm_with_imports(path, &ImportedGlobals::new())
+}
+
+/// Same as `parse_wasm`, but with values for the globals the module imports
+#[cfg(feature = "std")]
+pub fn parse_wasm_with_imports(
+    path: &str,
+    imported_globals: &ImportedGlobals,
+) -> Result<Module, Error> {
+    parse_wasm_bytes_with_imports(&read_file(path)?, imported_globals)
+}
+
+/// Same as `parse_wasm`, but recovering from errors where it can. See `parse_wasm_bytes_lenient`.
+#[cfg(feature = "std")]
+pub fn parse_wasm_lenient(path: &str) -> Result<(Module, Vec<Error>), Error> {
+    parse_wasm_bytes_lenient(&read_file(path)?)
+}
+
+#[cfg(feature = "std")]
+fn read_file(path: &str) -> Result<Vec<u8>, Error> {
+    let file = match File::open(path) {
+        Ok(f) => f,
+        Err(_) => return Err(Error::Misc("Could not open the input file")),
+    };
     let mut reader = BufReader::new(file);
     let mut buf: Vec<u8> = Vec::new();
-    reader.read_to_end(&mut buf).unwrap();
+    if reader.read_to_end(&mut buf).is_err() {
+        return Err(Error::Misc("Could not read the input file"));
+    }
+    Ok(buf)
+}
+
+/// Parse a module that's already in memory; this doesn't need `std`
+pub fn parse_wasm_bytes(buf: &[u8]) -> Result<Module, Error> {
+    parse_wasm_bytes_with_imports(buf, &ImportedGlobals::new())
+}
+
+pub fn parse_wasm_bytes_with_imports(
+    buf: &[u8],
+    imported_globals: &ImportedGlobals,
+) -> Result<Module, Error> {
+    parse_wasm_bytes_with_limits(buf, imported_globals, &ParseLimits::default())
+}
 
+/// Same as `parse_wasm_bytes_with_imports`, but rejecting modules that go past `limits`
+/// instead of the default ones
+pub fn parse_wasm_bytes_with_limits(
+    buf: &[u8],
+    imported_globals: &ImportedGlobals,
+    limits: &ParseLimits,
+) -> Result<Module, Error> {
+    parse_module(buf, imported_globals, limits, None)
+}
+
+/// Parse a module, recording errors and carrying on where that's possible instead of stopping
+/// at the first one. A function body with an error keeps the instructions read before it,
+/// followed by `unreachable`. Only its first error is reported, since the rest of the body
+/// is skipped. A section with any other error is left out, undoing whatever of it was already
+/// applied. Gives the partial module along with the errors. A malformed header or section
+/// layout still fails outright.
+pub fn parse_wasm_bytes_lenient(buf: &[u8]) -> Result<(Module, Vec<Error>), Error> {
+    let mut diagnostics = Vec::new();
+    let module = parse_module(
+        buf,
+        &ImportedGlobals::new(),
+        &ParseLimits::default(),
+        Some(&mut diagnostics),
+    )?;
+    Ok((module, diagnostics))
+}
+
+/// Summarize a module without building it. See `Module::inspect`.
+pub fn inspect_wasm_bytes(buf: &[u8]) -> Result<ModuleSummary, Error> {
+    let mut summary = ModuleSummary::default();
+    for mut section in read_sections(buf)? {
+        summary
+            .section_sizes
+            .push((section.section_type, section.content.content.len()));
+        section.update_summary(&mut summary)?;
+    }
+    Ok(summary)
+}
+
+/// Parse a module, collecting recoverable errors into `diagnostics` if it's given
+fn parse_module(
+    buf: &[u8],
+    imported_globals: &ImportedGlobals,
+    limits: &ParseLimits,
+    mut diagnostics: Option<&mut Vec<Error>>,
+) -> Result<Module, Error> {
+    let mut module = Module::new();
+
+    let mut has_code_section = false;
+    for mut section in read_sections(buf)? {
+        has_code_section |= section.section_type == 10;
+        match diagnostics.as_deref_mut() {
+            Some(diagnostics) => {
+                // A section that fails partway is left out entirely, not half applied
+                let before = module.clone();
+                section.diagnostics = Some(Vec::new());
+                let result = section.update_module(&mut module, imported_globals, limits);
+                diagnostics.extend(section.diagnostics.take().unwrap_or_default());
+                if let Err(e) = result {
+                    diagnostics.push(e);
+                    module = before;
+                }
+            }
+            None => section.update_module(&mut module, imported_globals, limits)?,
+        }
+    }
+
+    // Declared functions need bodies even when the code section is missing altogether
+    if !has_code_section && module.num_functions() > module.num_imported_functions() {
+        let e = Error::UnexpectedData("Expected one function body for each declared function");
+        match diagnostics {
+            Some(diagnostics) => diagnostics.push(e),
+            None => return Err(e),
+        }
+    }
+
+    Ok(module)
+}
+
+/// Checks the header and splits the rest of the module into its sections
+fn read_sections(buf: &[u8]) -> Result<Vec<ModuleSection>, Error> {
     // Check that this matches the WASM magic number
-    match buf[0..=3] {
-        [b'\0', b'a', b's', b'm'] => (),
+    match buf.get(0..=3) {
+        Some([b'\0', b'a', b's', b'm']) => (),
         _ => {
             return Err(Error::InvalidInput);
         }
     };
 
     // Check that this matches the only version of WASM we support
-    match buf[4..=7] {
-        [1, 0, 0, 0] => (),
+    match buf.get(4..=7) {
+        Some([1, 0, 0, 0]) => (),
         _ => {
             return Err(Error::BadVersion);
         }
@@ -764,24 +2026,997 @@ pub fn parse_wasm(path: &str) -> Result<Module, Error> {
 
     let mut sections: Vec<ModuleSection> = Vec::new();
     let mut start = 8;
+    let mut last_section_order = 0;
     while start < buf.len() {
         let section_type: u8 = buf[start];
-        let (section_length, bytes_read) = parse_unsigned_leb128(&buf[start + 1..]);
-        let section_end = 1 + bytes_read + section_length as usize;
+        if let Some(order) = section_order(section_type) {
+            if order <= last_section_order {
+                return Err(Error::UnexpectedData(
+                    "Expected known sections to be unique and in order",
+                ));
+            }
+            last_section_order = order;
+        }
+        let (section_length, bytes_read) = parse_unsigned_leb128(&buf[start + 1..])?;
+        let section_end = match usize::try_from(section_length)
+            .ok()
+            .and_then(|n| n.checked_add(1 + bytes_read))
+        {
+            Some(n) => n,
+            None => return Err(Error::EndOfData),
+        };
 
+        // A declared length running past the end of the buffer means the module is truncated
+        let content = match buf.get((start + 1 + bytes_read)..(start.saturating_add(section_end))) {
+            Some(c) => c,
+            None => return Err(Error::EndOfData),
+        };
         sections.push(ModuleSection::new(
             section_type,
-            &buf[(start + 1 + bytes_read)..(start + section_end)],
+            content,
+            start + 1 + bytes_read,
         ));
 
         start += section_end;
     }
 
-    let mut module = Module::new();
+    Ok(sections)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
 
-    for mut section in sections {
-        section.update_module(&mut module)?;
+    #[test]
+    fn global_get_as_a_segment_offset() {
+        let bytes = wasm(
+            r#"(module
+                (import "env" "base" (global i32))
+                (global $copy i32 (global.get 0))
+                (memory (export "memory") 1)
+                (data (global.get 0) "\2a"))"#,
+        );
+        let mut imports = ImportedGlobals::new();
+        imports.insert(("env".into(), "base".into()), Value::i32(8));
+        let mut module = parse_wasm_bytes_with_imports(&bytes, &imports).unwrap();
+        module.instantiate().unwrap();
+        assert_eq!(module.globals()[1].value.unpack(), TypedValue::I32(8));
+        assert_eq!(module.memory(0).unwrap().as_slice(8, 1).unwrap(), [0x2a]);
     }
 
-    Ok(module)
+    #[test]
+    fn arithmetic_in_a_constant_expression_is_rejected() {
+        let bytes = wasm(r#"(module (global i32 (i32.add (i32.const 1) (i32.const 2))))"#);
+        assert!(parse_wasm_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn name_section_names_functions_and_locals() {
+        let bytes = wasm(
+            r#"(module
+                (func $first)
+                (func $second (param $x i32) (local $y i64)))"#,
+        );
+        let module = parse_wasm_bytes(&bytes).unwrap();
+        let names = module.names().unwrap();
+        assert_eq!(names.functions.get(&0).map(String::as_str), Some("first"));
+        assert_eq!(names.functions.get(&1).map(String::as_str), Some("second"));
+        let locals = &names.locals[&1];
+        assert_eq!(locals.get(&0).map(String::as_str), Some("x"));
+        assert_eq!(locals.get(&1).map(String::as_str), Some("y"));
+        assert_eq!(module.function_name(1), Some("second"));
+    }
+
+    #[test]
+    fn custom_and_unknown_sections_are_kept_without_failing() {
+        let mut bytes = wasm(r#"(module (func (export "f")))"#);
+        // A custom section named "meta" and a section with an id no version of the spec uses
+        bytes.extend_from_slice(&[0, 7, 4, b'm', b'e', b't', b'a', 1, 2]);
+        bytes.extend_from_slice(&[0x7f, 2, 3, 4]);
+        let module = parse_wasm_bytes(&bytes).unwrap();
+        assert_eq!(module.custom_sections(), [("meta".into(), vec![1, 2])]);
+        assert_eq!(module.skipped_sections(), [(0x7f, vec![3, 4])]);
+        assert!(module.export_kind("f").is_some());
+    }
+
+    #[test]
+    fn duplicated_type_section_is_rejected() {
+        let header = [0, b'a', b's', b'm', 1, 0, 0, 0];
+        // Two empty type sections, and the same with a custom section between them
+        let duplicated = [&header[..], &[1, 1, 0, 1, 1, 0]].concat();
+        assert!(matches!(
+            parse_wasm_bytes(&duplicated),
+            Err(Error::UnexpectedData(_))
+        ));
+        let split = [&header[..], &[1, 1, 0, 0, 2, 1, b'x', 1, 1, 0]].concat();
+        assert!(matches!(
+            parse_wasm_bytes(&split),
+            Err(Error::UnexpectedData(_))
+        ));
+    }
+
+    #[test]
+    fn custom_sections_may_sit_between_known_sections() {
+        let header = [0, b'a', b's', b'm', 1, 0, 0, 0];
+        // Empty type and function sections with a custom section between them
+        let bytes = [&header[..], &[1, 1, 0, 0, 2, 1, b'x', 3, 1, 0]].concat();
+        assert!(parse_wasm_bytes(&bytes).is_ok());
+    }
+
+    #[test]
+    fn integer_constants_cover_the_signed_range() {
+        let text = r#"(module
+            (func (export "i32_min") (result i32) i32.const -2147483648)
+            (func (export "i32_max") (result i32) i32.const 2147483647)
+            (func (export "i32_all_ones") (result i32) i32.const 0xFFFFFFFF)
+            (func (export "i64_min") (result i64) i64.const -9223372036854775808)
+            (func (export "i64_all_ones") (result i64) i64.const 0xFFFFFFFFFFFFFFFF))"#;
+        let mut module = instantiate(text);
+        let mut result = |name| call(&mut module, name, &[]).unwrap();
+        assert_eq!(result("i32_min"), [TypedValue::I32(i32::MIN)]);
+        assert_eq!(result("i32_max"), [TypedValue::I32(i32::MAX)]);
+        assert_eq!(result("i32_all_ones"), [TypedValue::I32(-1)]);
+        assert_eq!(result("i64_min"), [TypedValue::I64(i64::MIN)]);
+        assert_eq!(result("i64_all_ones"), [TypedValue::I64(-1)]);
+    }
+
+    #[test]
+    fn imported_memory_base_offsets_a_data_segment() {
+        let bytes = wasm(
+            r#"(module
+                (import "env" "__memory_base" (global i32))
+                (memory 1)
+                (data (global.get 0) "\01\02"))"#,
+        );
+        let mut imports = ImportedGlobals::new();
+        imports.insert(("env".into(), "__memory_base".into()), Value::i32(1024));
+        let mut module = parse_wasm_bytes_with_imports(&bytes, &imports).unwrap();
+        module.instantiate().unwrap();
+        let memory = module.memory(0).unwrap();
+        assert_eq!(memory.as_slice(1023, 4).unwrap(), [0, 1, 2, 0]);
+    }
+
+    #[test]
+    fn constant_expressions_only_read_earlier_immutable_globals() {
+        let mutable = wasm(
+            r#"(module
+                (import "env" "base" (global (mut i32)))
+                (memory 1)
+                (data (global.get 0) "\01"))"#,
+        );
+        let mut imports = ImportedGlobals::new();
+        imports.insert(("env".into(), "base".into()), Value::i32(0));
+        assert!(matches!(
+            parse_wasm_bytes_with_imports(&mutable, &imports),
+            Err(Error::UnexpectedData(_))
+        ));
+
+        let later = wasm(r#"(module (global i32 (global.get 1)) (global i32 (i32.const 0)))"#);
+        assert!(matches!(
+            parse_wasm_bytes(&later),
+            Err(Error::UnexpectedData(_))
+        ));
+    }
+
+    #[test]
+    fn active_and_passive_element_segments() {
+        let text = r#"(module
+            (table 4 funcref)
+            (elem (i32.const 1) $a $b)
+            (elem $passive func $b $a)
+            (func $a)
+            (func $b)
+            (func (export "init")
+                (table.init $passive (i32.const 2) (i32.const 0) (i32.const 2))))"#;
+        let mut module = instantiate(text);
+        let elements = |module: &Module| -> Vec<TypedValue> {
+            let table = module.table(0).unwrap();
+            (0..4).map(|i| table.get(i).unwrap().unpack()).collect()
+        };
+        assert_eq!(
+            elements(&module),
+            [
+                TypedValue::FuncRef(None),
+                TypedValue::FuncRef(Some(0)),
+                TypedValue::FuncRef(Some(1)),
+                TypedValue::FuncRef(None)
+            ]
+        );
+        call(&mut module, "init", &[]).unwrap();
+        assert_eq!(
+            elements(&module),
+            [
+                TypedValue::FuncRef(None),
+                TypedValue::FuncRef(Some(0)),
+                TypedValue::FuncRef(Some(1)),
+                TypedValue::FuncRef(Some(0))
+            ]
+        );
+    }
+
+    #[test]
+    fn element_segment_must_match_its_table_type() {
+        let bytes = wasm(r#"(module (table 1 externref) (elem (i32.const 0) func 0) (func))"#);
+        assert!(parse_wasm_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn tables_of_different_element_types() {
+        let text = r#"(module
+            (table $funcs 2 funcref)
+            (table $externs 3 externref)
+            (elem (table $funcs) (i32.const 1) func $f)
+            (elem (table $externs) (i32.const 0) externref (ref.null extern))
+            (elem $passive externref (ref.null extern) (ref.null extern))
+            (func $f)
+            (func (export "init") (param i32)
+                (table.init $externs $passive (local.get 0) (i32.const 0) (i32.const 2)))
+            (func (export "size") (result i32) (table.size $externs)))"#;
+        let mut module = instantiate(text);
+        assert!(module.table(0).unwrap().element_type() == PrimitiveType::FuncRef);
+        assert!(module.table(1).unwrap().element_type() == PrimitiveType::ExternRef);
+        assert_eq!(
+            module.table(0).unwrap().get(1).unwrap().unpack(),
+            TypedValue::FuncRef(Some(0))
+        );
+        assert_eq!(
+            module.table(1).unwrap().get(0).unwrap().unpack(),
+            TypedValue::ExternRef(None)
+        );
+        assert_eq!(
+            call(&mut module, "size", &[]).unwrap(),
+            [TypedValue::I32(3)]
+        );
+        call(&mut module, "init", &[Value::i32(1)]).unwrap();
+        assert_eq!(
+            trap(call(&mut module, "init", &[Value::i32(2)])),
+            Trap::TableOutOfBounds
+        );
+
+        // Segments, table.init and table.copy must all agree on the element type
+        for mismatched in [
+            r#"(module (table 1 funcref) (elem (i32.const 0) externref (ref.null extern)))"#,
+            r#"(module (table 1 externref) (elem externref (ref.null func)))"#,
+            r#"(module (table 1 funcref) (elem $e externref)
+                (func (table.init $e (i32.const 0) (i32.const 0) (i32.const 0))))"#,
+            r#"(module (table $a 1 funcref) (table $b 1 externref)
+                (func (table.copy $a $b (i32.const 0) (i32.const 0) (i32.const 0))))"#,
+        ]
+        .iter()
+        {
+            assert!(matches!(
+                parse_wasm_bytes(&wasm(mismatched)),
+                Err(Error::UnexpectedData(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn table_count_and_initial_elements_are_capped() {
+        let limits = ParseLimits {
+            max_tables: 2,
+            max_initial_table_elements: 10,
+            ..ParseLimits::default()
+        };
+        let parse =
+            |text| parse_wasm_bytes_with_limits(&wasm(text), &ImportedGlobals::new(), &limits);
+        assert!(parse(r#"(module (table 4 funcref) (table 6 externref))"#).is_ok());
+        assert!(
+            parse(r#"(module (table 0 funcref) (table 0 funcref) (table 0 funcref))"#).is_err()
+        );
+        assert!(parse(r#"(module (table 5 funcref) (table 6 funcref))"#).is_err());
+        assert!(
+            parse(r#"(module (import "env" "t" (table 6 funcref)) (table 5 funcref))"#).is_err()
+        );
+    }
+
+    #[test]
+    fn loads_and_stores_target_their_memory() {
+        let text = r#"(module
+            (memory $a 1)
+            (memory $b 1)
+            (data (memory $a) (i32.const 0) "\01")
+            (data (memory $b) (i32.const 0) "\02")
+            (func (export "load_a") (result i32) (i32.load $a (i32.const 0)))
+            (func (export "load_b") (result i32) (i32.load $b (i32.const 0)))
+            (func (export "store_b") (i32.store $b (i32.const 4) (i32.const 7)))
+            (func (export "size_b") (result i32) (memory.size $b))
+            (func (export "grow_b") (result i32) (memory.grow $b (i32.const 1))))"#;
+        let mut module = instantiate(text);
+        assert_eq!(
+            call(&mut module, "load_a", &[]).unwrap(),
+            [TypedValue::I32(1)]
+        );
+        assert_eq!(
+            call(&mut module, "load_b", &[]).unwrap(),
+            [TypedValue::I32(2)]
+        );
+        call(&mut module, "store_b", &[]).unwrap();
+        assert_eq!(module.memory(1).unwrap().as_slice(4, 1).unwrap(), [7]);
+        assert_eq!(module.memory(0).unwrap().as_slice(4, 1).unwrap(), [0]);
+        assert_eq!(
+            call(&mut module, "grow_b", &[]).unwrap(),
+            [TypedValue::I32(1)]
+        );
+        assert_eq!(
+            call(&mut module, "size_b", &[]).unwrap(),
+            [TypedValue::I32(2)]
+        );
+        assert_eq!(module.memory(0).unwrap().size_pages(), 1);
+    }
+
+    #[test]
+    fn memory_count_and_initial_pages_are_capped() {
+        let limits = ParseLimits {
+            max_memories: 2,
+            max_initial_memory_pages: 10,
+            ..ParseLimits::default()
+        };
+        let parse =
+            |text| parse_wasm_bytes_with_limits(&wasm(text), &ImportedGlobals::new(), &limits);
+        assert!(parse(r#"(module (memory 4) (memory 6))"#).is_ok());
+        assert!(parse(r#"(module (memory 0) (memory 0) (memory 0))"#).is_err());
+        assert!(parse(r#"(module (memory 5) (memory 6))"#).is_err());
+        assert!(parse(r#"(module (import "env" "m" (memory 6)) (memory 5))"#).is_err());
+    }
+
+    #[test]
+    fn shared_memory_declaration() {
+        let module = instantiate(r#"(module (memory 1 2 shared) (memory 1))"#);
+        let shared = module.memory(0).unwrap();
+        assert!(shared.is_shared());
+        assert_eq!(shared.size_pages(), 1);
+        assert!(!module.memory(1).unwrap().is_shared());
+
+        // A shared memory must say how far it may grow
+        let header = [0, b'a', b's', b'm', 1, 0, 0, 0];
+        let no_maximum = [&header[..], &[5, 3, 1, 2, 1]].concat();
+        assert!(matches!(
+            parse_wasm_bytes(&no_maximum),
+            Err(Error::UnexpectedData(_))
+        ));
+    }
+
+    #[test]
+    fn opcode_coverage_report() {
+        let opcodes = supported_opcodes();
+        assert!(opcodes.contains(&(0x6A, "i32.add")));
+        assert!(opcodes.contains(&(0x28, "i32.load")));
+        // drop and select aren't implemented yet
+        assert!(!opcodes.iter().any(|(op, _)| *op == 0x1A || *op == 0x1B));
+
+        let secondary = supported_secondary_opcodes();
+        assert!(secondary.contains(&(0xFC, 0, "i32.trunc_sat_f32_s")));
+        // Nothing from SIMD
+        assert!(!secondary.iter().any(|(prefix, _, _)| *prefix == 0xFD));
+    }
+
+    #[test]
+    fn float_constants_keep_nan_payloads() {
+        let text = r#"(module
+            (func (export "f32") (result f32) f32.const nan:0x200001)
+            (func (export "f32_negative") (result f32) f32.const -nan:0x7fffff)
+            (func (export "f64") (result f64) f64.const nan:0x4000000000001))"#;
+        let mut module = instantiate(text);
+        let mut result = |name| module.call(name, Vec::new()).unwrap()[0];
+        let signaling = result("f32");
+        assert_eq!(signaling.as_f32_unchecked().to_bits(), 0x7fa0_0001);
+        assert_eq!(format!("{}", signaling), "(f32:nan:0x200001)");
+        assert_eq!(
+            result("f32_negative").as_f32_unchecked().to_bits(),
+            0xffff_ffff
+        );
+        assert_eq!(
+            result("f64").as_f64_unchecked().to_bits(),
+            0x7ff4_0000_0000_0001
+        );
+    }
+
+    #[test]
+    fn block_types() {
+        let pair = FunctionType::new(
+            vec![PrimitiveType::I32],
+            vec![PrimitiveType::I32, PrimitiveType::I64],
+        );
+        let read = |bytes: &[u8]| {
+            let mut reader = ByteReader::new(bytes);
+            reader.function_types = vec![FunctionType::default(), pair.clone()];
+            reader.read_block_type()
+        };
+        assert!(read(&[0x40]).unwrap() == FunctionType::default());
+        assert!(read(&[0x7F]).unwrap() == FunctionType::new(Vec::new(), vec![PrimitiveType::I32]));
+        assert!(read(&[0x01]).unwrap() == pair);
+        assert!(read(&[0x02]).is_err());
+
+        let text = r#"(module
+            (type $pair (func (param i32) (result i32 i64)))
+            (func (export "empty") (result i32)
+                i32.const 1
+                (block (br 0))
+                i32.const 2
+                i32.add)
+            (func (export "value") (result i32)
+                (block (result i32) (i32.const 3) (i32.const 4) (br 0)))
+            (func (export "indexed") (result i32 i64)
+                i32.const 5
+                (block (type $pair)
+                    i32.const 1
+                    i32.add
+                    i64.const 7)))"#;
+        let mut module = instantiate(text);
+        assert_eq!(
+            call(&mut module, "empty", &[]).unwrap(),
+            [TypedValue::I32(3)]
+        );
+        assert_eq!(
+            call(&mut module, "value", &[]).unwrap(),
+            [TypedValue::I32(4)]
+        );
+        assert_eq!(
+            call(&mut module, "indexed", &[]).unwrap(),
+            [TypedValue::I32(6), TypedValue::I64(7)]
+        );
+    }
+
+    #[test]
+    fn disassembly_offsets() {
+        #[rustfmt::skip]
+        let bytes = [
+            0, b'a', b's', b'm', 1, 0, 0, 0,
+            // Type section: () -> i32
+            1, 5, 1, 0x60, 0, 1, 0x7F,
+            // Function section
+            3, 2, 1, 0,
+            // Code section with one body of 7 bytes and no locals, starting at offset 24
+            10, 9, 1, 7, 0,
+            0x41, 1, 0x41, 2, 0x6A, 0x0B,
+        ];
+        let module = parse_wasm_bytes(&bytes).unwrap();
+        let expected = [
+            (24, "i32.const"),
+            (26, "i32.const"),
+            (28, "i32.add"),
+            (29, "end"),
+        ];
+        let disassembly = module.disassemble();
+        assert_eq!(disassembly.len(), expected.len());
+        for ((offset, mnemonic), (expected_offset, expected_mnemonic)) in
+            disassembly.iter().zip(expected)
+        {
+            assert_eq!(
+                (*offset, mnemonic.as_str()),
+                (expected_offset, expected_mnemonic)
+            );
+        }
+
+        // Offsets keep increasing through nested blocks and across function bodies
+        let module = parse_wasm_bytes(&wasm(
+            r#"(module
+                (func (block (loop (br_if 1 (i32.const 0)))))
+                (func (result i32) (if (result i32) (i32.const 1) (then (i32.const 2)) (else (i32.const 3)))))"#,
+        ))
+        .unwrap();
+        let offsets: Vec<usize> = module.disassemble().iter().map(|(o, _)| *o).collect();
+        assert!(offsets.len() > 10);
+        assert!(offsets.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn else_less_if_with_a_result() {
+        let text = r#"(module
+            (func (export "bump") (param i32 i32) (result i32)
+                local.get 0
+                local.get 1
+                if (param i32) (result i32)
+                    i32.const 1
+                    i32.add
+                end))"#;
+        // The taken path produces the result
+        assert_eq!(
+            run(text, "bump", &[Value::i32(41), Value::i32(1)]).unwrap(),
+            [TypedValue::I32(42)]
+        );
+        // The not-taken path leaves the param in place as the result
+        assert_eq!(
+            run(text, "bump", &[Value::i32(41), Value::i32(0)]).unwrap(),
+            [TypedValue::I32(41)]
+        );
+
+        // Without an else there is nothing to produce a result that isn't a param
+        let bytes = wasm(
+            r#"(module
+                (func (param i32) (result i32)
+                    local.get 0
+                    if (result i32)
+                        i32.const 1
+                    end))"#,
+        );
+        assert!(parse_wasm_bytes(&bytes).is_err());
+    }
+
+    #[test]
+    fn section_length_past_the_end_is_end_of_data() {
+        let header = [0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00];
+
+        // A type section claiming 10 bytes with only 3 left
+        let mut bytes = header.to_vec();
+        bytes.extend([1, 10, 1, 0x60, 0]);
+        assert!(matches!(parse_wasm_bytes(&bytes), Err(Error::EndOfData)));
+
+        // A length near the top of the u32 range must not wrap around
+        let mut bytes = header.to_vec();
+        bytes.extend([1, 0xFF, 0xFF, 0xFF, 0xFF, 0x0F, 0]);
+        assert!(matches!(parse_wasm_bytes(&bytes), Err(Error::EndOfData)));
+
+        // The same section with its real length parses
+        let mut bytes = header.to_vec();
+        bytes.extend([1, 4, 1, 0x60, 0, 0]);
+        assert!(parse_wasm_bytes(&bytes).is_ok());
+    }
+
+    #[test]
+    fn memory_init_with_a_data_count() {
+        let text = r#"(module
+            (memory (export "memory") 1)
+            (data "\01\02\03\04")
+            (func (export "init") (param i32 i32 i32)
+                (memory.init 0 (local.get 0) (local.get 1) (local.get 2)))
+            (func (export "drop") (data.drop 0)))"#;
+        let bytes = wasm(text);
+        let mut module = parse_wasm_bytes(&bytes).unwrap();
+        assert_eq!(module.data_count(), Some(1));
+        module.instantiate().unwrap();
+
+        let args = [Value::i32(8), Value::i32(1), Value::i32(3)];
+        call(&mut module, "init", &args).unwrap();
+        assert_eq!(
+            module.memory(0).unwrap().as_slice(7, 5).unwrap(),
+            [0, 2, 3, 4, 0]
+        );
+        call(&mut module, "drop", &[]).unwrap();
+        assert_eq!(
+            trap(call(&mut module, "init", &args)),
+            Trap::MemoryOutOfBounds
+        );
+
+        // The same module without its data count section fails to parse
+        let mut stripped = bytes[..8].to_vec();
+        let mut pos = 8;
+        while pos < bytes.len() {
+            let id = bytes[pos];
+            let (len, read) = parse_unsigned_leb128(&bytes[pos + 1..]).unwrap();
+            let end = pos + 1 + read + len as usize;
+            if id != 12 {
+                stripped.extend(&bytes[pos..end]);
+            }
+            pos = end;
+        }
+        assert!(stripped.len() < bytes.len());
+        assert!(parse_wasm_bytes(&stripped).is_err());
+    }
+
+    #[test]
+    fn loop_with_a_br_if_back_edge() {
+        // Sums 1..=n, branching back to the loop head until the counter hits zero
+        let text = r#"(module
+            (func (export "sum") (param $n i32) (result i32) (local $acc i32)
+                (block $done
+                    (br_if $done (i32.eqz (local.get $n)))
+                    (loop $again
+                        (local.set $acc (i32.add (local.get $acc) (local.get $n)))
+                        (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                        (br_if $again (local.get $n))))
+                (local.get $acc)))"#;
+        let mut module = instantiate(text);
+        for (n, sum) in [(0, 0), (1, 1), (10, 55), (1000, 500500)] {
+            assert_eq!(
+                call(&mut module, "sum", &[Value::i32(n)]).unwrap(),
+                [TypedValue::I32(sum)]
+            );
+        }
+    }
+
+    #[test]
+    fn absurd_vector_lengths_hit_the_limits() {
+        let header = [0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00];
+
+        // A function section declaring u32::MAX functions in a handful of bytes
+        let mut bytes = header.to_vec();
+        bytes.extend([1, 4, 1, 0x60, 0, 0]);
+        bytes.extend([3, 6, 0xFF, 0xFF, 0xFF, 0xFF, 0x0F, 0]);
+        assert!(matches!(
+            parse_wasm_bytes(&bytes),
+            Err(Error::Misc("Module declares too many functions"))
+        ));
+
+        let mut text = String::from("(module");
+        for _ in 0..10 {
+            text.push_str("(func (result i32) i32.const 1 i32.const 2 i32.add)");
+        }
+        text.push(')');
+        let bytes = wasm(&text);
+        let globals = ImportedGlobals::new();
+        let limits = ParseLimits {
+            max_functions: 10,
+            max_function_instructions: 4,
+            ..ParseLimits::default()
+        };
+        assert!(parse_wasm_bytes_with_limits(&bytes, &globals, &limits).is_ok());
+
+        let fewer_functions = ParseLimits {
+            max_functions: 9,
+            ..limits
+        };
+        assert!(parse_wasm_bytes_with_limits(&bytes, &globals, &fewer_functions).is_err());
+        let fewer_instructions = ParseLimits {
+            max_function_instructions: 3,
+            ..limits
+        };
+        assert!(parse_wasm_bytes_with_limits(&bytes, &globals, &fewer_instructions).is_err());
+    }
+
+    #[test]
+    fn custom_sections_are_told_apart_by_name() {
+        let mut bytes = wasm(r#"(module (func $f (export "f")))"#);
+        // Bodies that would mean something if read as the start of a known section
+        bytes.extend_from_slice(&[0, 5, 1, b'a', 1, 0x60, 0]);
+        // A name running past the end of its section
+        let mut truncated = bytes.clone();
+        truncated.extend_from_slice(&[0, 2, 5, b'x']);
+        bytes.extend_from_slice(&[0, 10, 8, b'p', b'r', b'o', b'd', b'u', b'c', b'e', b'r', 7]);
+
+        let module = parse_wasm_bytes(&bytes).unwrap();
+        assert_eq!(
+            module.custom_sections(),
+            [("a".into(), vec![1, 0x60, 0]), ("producer".into(), vec![7])]
+        );
+        // The "name" section was read as names, not kept as an unknown section
+        assert_eq!(module.function_name(0), Some("f"));
+        assert!(module.skipped_sections().is_empty());
+
+        assert!(parse_wasm_bytes(&truncated).is_err());
+    }
+
+    #[test]
+    fn memargs_with_and_without_a_memory_index() {
+        let read = |bytes: &[u8]| {
+            let mut reader = ByteReader::new(bytes);
+            reader.num_memories = 2;
+            let memarg = reader.read_memarg();
+            memarg.map(|m| (m.align, m.memory_index, m.offset, reader.offset))
+        };
+        // Without bit 6 of the alignment the access is to memory 0
+        assert_eq!(read(&[2, 8]).unwrap(), (2, 0, 8, 2));
+        // With it, the memory index follows the alignment, and may still be 0
+        assert_eq!(read(&[0x42, 1, 8]).unwrap(), (2, 1, 8, 3));
+        assert_eq!(read(&[0x42, 0, 0x80, 1]).unwrap(), (2, 0, 128, 4));
+        assert!(read(&[0x42, 2, 8]).is_err());
+
+        // The explicit encoding of memory 0 behaves like the implicit one in a whole module
+        let mut bytes = vec![0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00];
+        bytes.extend([1, 4, 1, 0x60, 0, 0]);
+        bytes.extend([3, 2, 1, 0]);
+        bytes.extend([5, 3, 1, 0, 1]);
+        bytes.extend([7, 9, 1, 5, b's', b't', b'o', b'r', b'e', 0, 0]);
+        // i32.store offset=4 (i32.const 0) (i32.const 7), naming memory 0
+        bytes.extend([10, 12, 1, 10, 0, 0x41, 0, 0x41, 7, 0x36, 0x42, 0, 4, 0x0B]);
+        let mut module = parse_wasm_bytes(&bytes).unwrap();
+        module.instantiate().unwrap();
+        call(&mut module, "store", &[]).unwrap();
+        assert_eq!(
+            module.memory(0).unwrap().as_slice(4, 4).unwrap(),
+            [7, 0, 0, 0]
+        );
+    }
+
+    /// A module binary made of the given sections, each a section id and its content
+    fn module_bytes(sections: &[(u8, &[u8])]) -> Vec<u8> {
+        let mut bytes = vec![0x00, 0x61, 0x73, 0x6D, 0x01, 0x00, 0x00, 0x00];
+        for (id, content) in sections {
+            bytes.push(*id);
+            bytes.push(content.len() as u8);
+            bytes.extend_from_slice(content);
+        }
+        bytes
+    }
+
+    const TYPES: (u8, &[u8]) = (1, &[1, 0x60, 0, 1, 0x7F]);
+    const THREE_FUNCTIONS: (u8, &[u8]) = (3, &[3, 0, 0, 0]);
+    const EXPORTS: (u8, &[u8]) = (7, &[2, 2, b'o', b'k', 0, 1, 3, b'b', b'a', b'd', 0, 0]);
+
+    #[test]
+    fn lenient_parsing_reports_every_broken_body() {
+        // The first and last bodies have different unknown opcodes, 0x27 and 0xC5
+        let code: &[u8] = &[
+            3, 5, 0, 0x41, 1, 0x27, 0x0B, 4, 0, 0x41, 7, 0x0B, 3, 0, 0xC5, 0x0B,
+        ];
+        let bytes = module_bytes(&[TYPES, THREE_FUNCTIONS, EXPORTS, (10, code)]);
+        assert!(matches!(
+            parse_wasm_bytes(&bytes),
+            Err(Error::UnknownOpcode(0x27))
+        ));
+
+        let (mut module, diagnostics) = parse_wasm_bytes_lenient(&bytes).unwrap();
+        assert_eq!(diagnostics.len(), 2);
+        assert!(matches!(diagnostics[0], Error::UnknownOpcode(0x27)));
+        assert!(matches!(diagnostics[1], Error::UnknownOpcode(0xC5)));
+        module.instantiate().unwrap();
+        assert_eq!(call(&mut module, "ok", &[]).unwrap(), [TypedValue::I32(7)]);
+        assert_eq!(trap(call(&mut module, "bad", &[])), Trap::Unreachable);
+    }
+
+    #[test]
+    fn body_count_must_match_the_function_section() {
+        let one_body: &[u8] = &[1, 4, 0, 0x41, 7, 0x0B];
+        let four_bodies: &[u8] = &[
+            4, 4, 0, 0x41, 7, 0x0B, 4, 0, 0x41, 7, 0x0B, 4, 0, 0x41, 7, 0x0B, 4, 0, 0x41, 7, 0x0B,
+        ];
+        let expected = "Expected one function body for each declared function";
+        for code in [Some(one_body), Some(four_bodies), None] {
+            let mut sections = vec![TYPES, THREE_FUNCTIONS, EXPORTS];
+            sections.extend(code.map(|c| (10, c)));
+            let bytes = module_bytes(&sections);
+            assert!(matches!(
+                parse_wasm_bytes(&bytes),
+                Err(Error::UnexpectedData(m)) if m == expected
+            ));
+            let (_, diagnostics) = parse_wasm_bytes_lenient(&bytes).unwrap();
+            assert!(matches!(
+                diagnostics[..],
+                [Error::UnexpectedData(m)] if m == expected
+            ));
+        }
+    }
+
+    #[test]
+    fn body_must_end_where_its_length_says() {
+        let expected = "Expected the function body to end where its length says";
+        // The first body claims a byte past its end, then one byte short of it
+        for code in [
+            &[2, 5, 0, 0x41, 7, 0x0B, 0x0B, 4, 0, 0x41, 7, 0x0B][..],
+            &[2, 3, 0, 0x41, 7, 0x0B, 4, 0, 0x41, 7, 0x0B, 0x0B][..],
+        ] {
+            let two_functions = (3, &[2, 0, 0][..]);
+            let bytes = module_bytes(&[TYPES, two_functions, (10, code)]);
+            assert!(matches!(
+                parse_wasm_bytes(&bytes),
+                Err(Error::UnexpectedData(m)) if m == expected
+            ));
+        }
+    }
+
+    #[test]
+    fn lenient_parsing_drops_a_failed_section_entirely() {
+        // The second export has an unknown kind, after the first was added
+        let exports: &[u8] = &[2, 2, b'o', b'k', 0, 0, 3, b'b', b'a', b'd', 9, 0];
+        let one_function = (3, &[1, 0][..]);
+        let code: &[u8] = &[1, 4, 0, 0x41, 7, 0x0B];
+        let bytes = module_bytes(&[TYPES, one_function, (7, exports), (10, code)]);
+        assert!(parse_wasm_bytes(&bytes).is_err());
+
+        let (module, diagnostics) = parse_wasm_bytes_lenient(&bytes).unwrap();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(module.export_kind("ok"), None);
+        assert_eq!(module.export_kind("bad"), None);
+        assert_eq!(module.num_functions(), 1);
+    }
+
+    #[test]
+    fn every_table_entry_decodes() {
+        // Zero immediates are valid for almost every instruction: block type 0, index 0, the
+        // constant 0 and a memarg with offset 0. Only the first byte needs to vary, as atomic
+        // accesses must give their natural alignment and ref.null a reference type.
+        let decode = |opcode: &[u8]| {
+            [0, 1, 2, 3, 0x70].iter().find_map(|&first| {
+                let mut bytes = opcode.to_vec();
+                bytes.push(first);
+                bytes.extend([0; 8]);
+                bytes.push(0x0B);
+                let mut reader = ByteReader::new(&bytes);
+                reader.function_types = vec![FunctionType::new(vec![], vec![])];
+                reader.data_count = Some(1);
+                reader.num_memories = 1;
+                reader.table_types = vec![PrimitiveType::FuncRef];
+                reader.element_types = vec![PrimitiveType::FuncRef];
+                reader.num_functions = 1;
+                reader.num_locals = 1;
+                match reader.read_inst() {
+                    Ok(_) => Some(reader.disassembly.swap_remove(0).1),
+                    _ => None,
+                }
+            })
+        };
+        for (opcode, name) in supported_opcodes() {
+            assert_eq!(decode(&[opcode]).as_deref(), Some(name), "{:#04x}", opcode);
+        }
+        for (prefix, opcode, name) in supported_secondary_opcodes() {
+            // Every secondary opcode so far fits in one LEB128 byte
+            assert!(opcode < 0x80);
+            let mnemonic = decode(&[prefix, opcode as u8]);
+            assert_eq!(
+                mnemonic.as_deref(),
+                Some(name),
+                "{:#04x} {}",
+                prefix,
+                opcode
+            );
+        }
+    }
+
+    #[test]
+    fn block_type_indices_take_several_bytes() {
+        let last = FunctionType::new(vec![PrimitiveType::F64], vec![]);
+        let read = |bytes: &[u8]| {
+            let mut reader = ByteReader::new(bytes);
+            reader.function_types = vec![FunctionType::default(); 200];
+            reader.function_types.push(last.clone());
+            reader.read_block_type().map(|t| (t, reader.offset))
+        };
+        // As a signed LEB128, 64 already needs a second byte, which a single-byte read would
+        // have taken for the negative 0x40, the empty type
+        let (t, read_bytes) = read(&[0xC0, 0x00]).unwrap();
+        assert!(t == FunctionType::default());
+        assert_eq!(read_bytes, 2);
+        let (t, read_bytes) = read(&[0xC8, 0x01]).unwrap();
+        assert!(t == last);
+        assert_eq!(read_bytes, 2);
+        assert!(read(&[0xC9, 0x01]).is_err());
+        // Negative values other than the empty and value types are reserved
+        assert!(read(&[0x60]).is_err());
+        assert_eq!(read(&[0x40]).unwrap().1, 1);
+    }
+
+    #[test]
+    fn local_indices_are_checked_while_parsing() {
+        let expected = "Expected a valid local index";
+        // One param and two declared locals give indices 0 to 2
+        for (body, valid) in [
+            ("i32.const 1 local.tee 2", true),
+            ("i32.const 1 local.tee 3", false),
+            ("local.get 2", true),
+            ("local.get 3", false),
+            ("i32.const 1 local.set 3 i32.const 0", false),
+            ("block (result i32) i32.const 1 local.tee 4 end", false),
+        ] {
+            let bytes = wasm(&format!(
+                "(module (func (param i32) (result i32) (local i32 i32) {}))",
+                body
+            ));
+            match parse_wasm_bytes(&bytes) {
+                Ok(_) => assert!(valid, "{}", body),
+                Err(Error::UnexpectedData(m)) => assert!(!valid && m == expected, "{}", body),
+                Err(_) => panic!("{} failed with another error", body),
+            }
+        }
+    }
+
+    #[test]
+    fn memory_grow_reads_its_memory_index_byte() {
+        // memory.grow with the reserved byte 0 after it, then one naming a second memory
+        let code = |memory_index: u8| vec![1, 6, 0, 0x41, 1, 0x40, memory_index, 0x0B];
+        let grow = |memories: &[u8], memory_index: u8| {
+            let grow_type = (1, &[1, 0x60, 0, 1, 0x7F][..]);
+            let one_function = (3, &[1, 0][..]);
+            let export = (7, &[1, 4, b'g', b'r', b'o', b'w', 0, 0][..]);
+            let code = code(memory_index);
+            let bytes =
+                module_bytes(&[grow_type, one_function, (5, memories), export, (10, &code)]);
+            parse_wasm_bytes(&bytes)
+        };
+        let one_memory: &[u8] = &[1, 0, 1];
+        let two_memories: &[u8] = &[2, 0, 1, 0, 3];
+
+        let mut module = grow(one_memory, 0).unwrap();
+        module.instantiate().unwrap();
+        assert_eq!(
+            call(&mut module, "grow", &[]).unwrap(),
+            [TypedValue::I32(1)]
+        );
+        assert_eq!(module.memory(0).unwrap().size_pages(), 2);
+
+        assert!(matches!(
+            grow(one_memory, 1),
+            Err(Error::UnexpectedData("Expected a valid memory index"))
+        ));
+        let mut module = grow(two_memories, 1).unwrap();
+        module.instantiate().unwrap();
+        assert_eq!(
+            call(&mut module, "grow", &[]).unwrap(),
+            [TypedValue::I32(3)]
+        );
+        assert_eq!(module.memory(0).unwrap().size_pages(), 1);
+        assert_eq!(module.memory(1).unwrap().size_pages(), 4);
+    }
+
+    #[test]
+    fn summary_of_a_known_module() {
+        let types: &[u8] = &[2, 0x60, 0, 1, 0x7F, 0x60, 1, 0x7F, 0];
+        let imports: &[u8] = &[
+            2, 3, b'e', b'n', b'v', 1, b'f', 0, 1, 3, b'e', b'n', b'v', 1, b'm', 2, 1, 1, 2,
+        ];
+        let functions: &[u8] = &[2, 0, 0];
+        let memories: &[u8] = &[1, 0, 3];
+        let exports: &[u8] = &[1, 1, b'a', 0, 1];
+        // The first body has the unknown opcode 0x27, which inspecting never gets to
+        let code: &[u8] = &[2, 3, 0, 0x27, 0x0B, 4, 0, 0x41, 1, 0x0B];
+        let custom: &[u8] = &[1, b'x', 9];
+        let bytes = module_bytes(&[
+            (1, types),
+            (2, imports),
+            (3, functions),
+            (5, memories),
+            (7, exports),
+            (10, code),
+            (0, custom),
+        ]);
+
+        let summary = Module::inspect(&bytes).unwrap();
+        assert_eq!(
+            summary,
+            ModuleSummary {
+                num_types: 2,
+                num_functions: 3,
+                num_imports: 2,
+                num_exports: 1,
+                memory_limits: vec![(1, 2), (3, u32::MAX)],
+                section_sizes: vec![(1, 9), (2, 19), (3, 3), (5, 3), (7, 5), (10, 10), (0, 3)],
+            }
+        );
+        assert!(matches!(
+            parse_wasm_bytes(&bytes),
+            Err(Error::UnknownOpcode(0x27))
+        ));
+
+        // The header and section layout are still checked
+        assert!(matches!(
+            Module::inspect(&bytes[..bytes.len() - 1]),
+            Err(Error::EndOfData)
+        ));
+        assert!(Module::inspect(&bytes[4..]).is_err());
+    }
+
+    #[test]
+    fn reference_global_initializers() {
+        let mut module = instantiate(
+            r#"(module
+                (global $null funcref (ref.null func))
+                (global $extern externref (ref.null extern))
+                (global $f funcref (ref.func $f))
+                (func $f)
+                (func (export "null") (result funcref) global.get $null)
+                (func (export "extern") (result externref) global.get $extern)
+                (func (export "f") (result funcref) global.get $f))"#,
+        );
+        assert_eq!(
+            call(&mut module, "null", &[]).unwrap(),
+            [TypedValue::FuncRef(None)]
+        );
+        assert_eq!(
+            call(&mut module, "extern", &[]).unwrap(),
+            [TypedValue::ExternRef(None)]
+        );
+        assert_eq!(
+            call(&mut module, "f", &[]).unwrap(),
+            [TypedValue::FuncRef(Some(0))]
+        );
+
+        // A null reference must match the global's type
+        let mismatched = wasm("(module (global funcref (ref.null extern)))");
+        assert!(matches!(
+            parse_wasm_bytes(&mismatched),
+            Err(Error::UnexpectedData(_))
+        ));
+    }
+
+    #[test]
+    fn call_index_must_name_a_function() {
+        let imported = r#"(module (import "env" "f" (func)) (func call 1))"#;
+        assert!(parse_wasm_bytes(&wasm(imported)).is_ok());
+        let missing = r#"(module (import "env" "f" (func)) (func call 2))"#;
+        assert!(matches!(
+            parse_wasm_bytes(&wasm(missing)),
+            Err(Error::UnexpectedData("Expected a valid function index"))
+        ));
+    }
 }
@@ -0,0 +1,42 @@
+//! Helpers shared by the unit tests
+
+use alloc::vec::Vec;
+use core::fmt::Debug;
+
+use crate::error::Error;
+use crate::parser::parse_wasm_bytes;
+use crate::wasm::{Module, Trap, TypedValue, Value};
+
+/// Assemble a module written in the text format
+pub fn wasm(text: &str) -> Vec<u8> {
+    wat::parse_str(text).unwrap()
+}
+
+/// Parse and instantiate a module written in the text format
+pub fn instantiate(text: &str) -> Module {
+    let mut module = parse_wasm_bytes(&wasm(text)).unwrap();
+    module.instantiate().unwrap();
+    module
+}
+
+/// Call the export `name`, giving its results as plain Rust values
+pub fn call(module: &mut Module, name: &str, args: &[Value]) -> Result<Vec<TypedValue>, Error> {
+    let results = module.call(name, args.to_vec())?;
+    Ok(results.iter().map(Value::unpack).collect())
+}
+
+/// Instantiate a module written in the text format and call its export `name`
+pub fn run(text: &str, name: &str, args: &[Value]) -> Result<Vec<TypedValue>, Error> {
+    call(&mut instantiate(text), name, args)
+}
+
+/// The trap `result` failed with, looking past the function it happened in
+pub fn trap<T: Debug>(result: Result<T, Error>) -> Trap {
+    match result {
+        Err(e) => match e.root() {
+            Error::Trap(t) => t.clone(),
+            e => panic!("expected a trap, got {:?}", e),
+        },
+        Ok(v) => panic!("expected a trap, got {:?}", v),
+    }
+}
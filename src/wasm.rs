@@ -1,5 +1,13 @@
-use std::collections::HashMap;
-use std::convert::TryFrom;
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cell::RefCell;
+use core::convert::TryFrom;
+use core::fmt;
 
 use crate::error::Error;
 
@@ -10,6 +18,8 @@ pub enum PrimitiveType {
     I64,
     F32,
     F64,
+    FuncRef,
+    ExternRef,
 }
 
 impl From<i32> for PrimitiveType {
@@ -36,6 +46,19 @@ impl From<f64> for PrimitiveType {
     }
 }
 
+impl fmt::Display for PrimitiveType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PrimitiveType::I32 => write!(f, "i32"),
+            PrimitiveType::I64 => write!(f, "i64"),
+            PrimitiveType::F32 => write!(f, "f32"),
+            PrimitiveType::F64 => write!(f, "f64"),
+            PrimitiveType::FuncRef => write!(f, "funcref"),
+            PrimitiveType::ExternRef => write!(f, "externref"),
+        }
+    }
+}
+
 /// Storage type for all wasm values
 #[derive(Copy, Clone)]
 pub union InternalValue {
@@ -69,7 +92,25 @@ impl From<f64> for InternalValue {
     }
 }
 
-/// Representation of all wasm values
+/// How a null reference is stored, since no valid index can have all its bits set
+const NULL_REF: i64 = -1;
+
+/// Representation of all wasm values.
+///
+/// Each numeric type has a constructor named after it, and `unpack` gives the value back for
+/// matching on:
+///
+/// ```
+/// use wasm_interpreter::wasm::{PrimitiveType, TypedValue, Value};
+///
+/// let answer = Value::i32(42);
+/// assert!(answer.get_type() == PrimitiveType::I32);
+/// assert_eq!(answer.unpack(), TypedValue::I32(42));
+///
+/// let pi = Value::f64(3.14);
+/// assert!(pi.get_type() == PrimitiveType::F64);
+/// assert_eq!(pi.unpack(), TypedValue::F64(3.14));
+/// ```
 #[derive(Copy, Clone)]
 pub struct Value {
     t: PrimitiveType,
@@ -84,6 +125,50 @@ impl Value {
         }
     }
 
+    pub fn i32(x: i32) -> Self {
+        Self::new(x)
+    }
+
+    pub fn i64(x: i64) -> Self {
+        Self::new(x)
+    }
+
+    pub fn f32(x: f32) -> Self {
+        Self::new(x)
+    }
+
+    pub fn f64(x: f64) -> Self {
+        Self::new(x)
+    }
+
+    /// Build a value of type `t` from its raw bit pattern, keeping only the low bits for 32-bit types.
+    /// Unlike `new`, which converts a Rust number, this never alters the bits, so float values
+    /// keep their sign and NaN payloads exactly.
+    pub fn from_bits(t: PrimitiveType, bits: u64) -> Value {
+        let v = match t {
+            PrimitiveType::I32 => InternalValue::from(bits as u32 as i32),
+            PrimitiveType::I64 => InternalValue::from(bits as i64),
+            // Floats are stored through the integer fields, since moving a signaling NaN
+            // through a float register can quiet it on some targets
+            PrimitiveType::F32 => InternalValue::from(bits as u32 as i32),
+            PrimitiveType::F64 => InternalValue::from(bits as i64),
+            PrimitiveType::FuncRef | PrimitiveType::ExternRef => InternalValue::from(bits as i64),
+        };
+        Self { t, v }
+    }
+
+    /// The raw bit pattern of the value, the inverse of `from_bits`. 32-bit types are zero-extended.
+    pub fn to_bits(&self) -> u64 {
+        match self.t {
+            PrimitiveType::I32 => self.as_i32_unchecked() as u32 as u64,
+            PrimitiveType::F32 => self.as_f32_unchecked().to_bits() as u64,
+            PrimitiveType::F64 => self.as_f64_unchecked().to_bits(),
+            PrimitiveType::I64 | PrimitiveType::FuncRef | PrimitiveType::ExternRef => {
+                self.as_i64_unchecked() as u64
+            }
+        }
+    }
+
     pub fn from_explicit_type(t: PrimitiveType, v: u64) -> Value {
         Self {
             t,
@@ -91,6 +176,94 @@ impl Value {
         }
     }
 
+    /// A null reference of the reference type `t`
+    pub fn null(t: PrimitiveType) -> Value {
+        Self {
+            t,
+            v: InternalValue { i64: NULL_REF },
+        }
+    }
+
+    /// The value a local or table element of type `t` starts with: zero for numbers, and null
+    /// for references
+    pub fn default_for(t: PrimitiveType) -> Value {
+        match t {
+            PrimitiveType::I32 => Value::i32(0),
+            PrimitiveType::I64 => Value::i64(0),
+            PrimitiveType::F32 => Value::f32(0.0),
+            PrimitiveType::F64 => Value::f64(0.0),
+            PrimitiveType::FuncRef | PrimitiveType::ExternRef => Value::null(t),
+        }
+    }
+
+    /// A reference to the function with the given index
+    pub fn func_ref(function_index: usize) -> Value {
+        Self {
+            t: PrimitiveType::FuncRef,
+            v: InternalValue {
+                i64: function_index as i64,
+            },
+        }
+    }
+
+    pub fn get_type(&self) -> PrimitiveType {
+        self.t
+    }
+
+    /// Whether this is a float NaN, of any sign or payload
+    pub fn is_nan(&self) -> bool {
+        match self.t {
+            PrimitiveType::F32 => self.as_f32_unchecked().is_nan(),
+            PrimitiveType::F64 => self.as_f64_unchecked().is_nan(),
+            _ => false,
+        }
+    }
+
+    /// Whether this is a numeric zero, counting both `0.0` and `-0.0` for floats
+    pub fn is_zero(&self) -> bool {
+        match self.t {
+            PrimitiveType::I32 => self.as_i32_unchecked() == 0,
+            PrimitiveType::I64 => self.as_i64_unchecked() == 0,
+            PrimitiveType::F32 => self.as_f32_unchecked() == 0.0,
+            PrimitiveType::F64 => self.as_f64_unchecked() == 0.0,
+            PrimitiveType::FuncRef | PrimitiveType::ExternRef => false,
+        }
+    }
+
+    /// The value with its type, for matching on without the unchecked accessors
+    pub fn unpack(&self) -> TypedValue {
+        match self.t {
+            PrimitiveType::I32 => TypedValue::I32(self.as_i32_unchecked()),
+            PrimitiveType::I64 => TypedValue::I64(self.as_i64_unchecked()),
+            PrimitiveType::F32 => TypedValue::F32(self.as_f32_unchecked()),
+            PrimitiveType::F64 => TypedValue::F64(self.as_f64_unchecked()),
+            PrimitiveType::FuncRef => TypedValue::FuncRef(self.as_ref_unchecked()),
+            PrimitiveType::ExternRef => TypedValue::ExternRef(self.as_ref_unchecked()),
+        }
+    }
+
+    /// Replace any float NaN with the canonical NaN, which has a clear sign and only the top
+    /// mantissa bit set. Other values are returned unchanged.
+    pub fn canonicalize_nan(self) -> Value {
+        match self.t {
+            PrimitiveType::F32 if self.as_f32_unchecked().is_nan() => {
+                Value::from_bits(PrimitiveType::F32, 0x7FC0_0000)
+            }
+            PrimitiveType::F64 if self.as_f64_unchecked().is_nan() => {
+                Value::from_bits(PrimitiveType::F64, 0x7FF8_0000_0000_0000)
+            }
+            _ => self,
+        }
+    }
+
+    /// The index a reference points to, or None for a null reference
+    pub fn as_ref_unchecked(&self) -> Option<usize> {
+        match self.as_i64_unchecked() {
+            NULL_REF => None,
+            n => Some(n as usize),
+        }
+    }
+
     #[inline]
     pub fn as_i32_unchecked(&self) -> i32 {
         unsafe { self.v.i32 }
@@ -145,6 +318,50 @@ impl From<f64> for Value {
     }
 }
 
+/// Booleans become an i32 0 or 1, as wasm comparisons produce
+impl From<bool> for Value {
+    fn from(v: bool) -> Self {
+        Self::from(v as i32)
+    }
+}
+
+/// A wasm value as a plain Rust enum, given by `Value::unpack`. References hold the index
+/// they point to, or None when null
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TypedValue {
+    I32(i32),
+    I64(i64),
+    F32(f32),
+    F64(f64),
+    FuncRef(Option<usize>),
+    ExternRef(Option<usize>),
+}
+
+impl From<Value> for TypedValue {
+    fn from(x: Value) -> Self {
+        x.unpack()
+    }
+}
+
+impl From<TypedValue> for Value {
+    fn from(x: TypedValue) -> Self {
+        match x {
+            TypedValue::I32(v) => Value::from(v),
+            TypedValue::I64(v) => Value::from(v),
+            TypedValue::F32(v) => Value::from(v),
+            TypedValue::F64(v) => Value::from(v),
+            TypedValue::FuncRef(Some(i)) => Value::func_ref(i),
+            TypedValue::FuncRef(None) => Value::null(PrimitiveType::FuncRef),
+            TypedValue::ExternRef(i) => Value {
+                t: PrimitiveType::ExternRef,
+                v: InternalValue {
+                    i64: i.map_or(NULL_REF, |i| i as i64),
+                },
+            },
+        }
+    }
+}
+
 impl TryFrom<Value> for u32 {
     type Error = Error;
     fn try_from(x: Value) -> Result<u32, Error> {
@@ -155,19 +372,159 @@ impl TryFrom<Value> for u32 {
     }
 }
 
+impl TryFrom<Value> for u64 {
+    type Error = Error;
+    fn try_from(x: Value) -> Result<u64, Error> {
+        match x.t {
+            PrimitiveType::I64 => Ok(unsafe { x.v.i64 as u64 }),
+            _ => Err(Error::Misc("Cannot extract as u64 from incorrect type")),
+        }
+    }
+}
+
+impl TryFrom<Value> for i32 {
+    type Error = Error;
+    fn try_from(x: Value) -> Result<i32, Error> {
+        match x.t {
+            PrimitiveType::I32 => Ok(unsafe { x.v.i32 }),
+            _ => Err(Error::Misc("Cannot extract as i32 from incorrect type")),
+        }
+    }
+}
+
+impl TryFrom<Value> for i64 {
+    type Error = Error;
+    fn try_from(x: Value) -> Result<i64, Error> {
+        match x.t {
+            PrimitiveType::I64 => Ok(unsafe { x.v.i64 }),
+            _ => Err(Error::Misc("Cannot extract as i64 from incorrect type")),
+        }
+    }
+}
+
+/// Native types a single wasm value can be extracted as
+pub trait FromValue: Sized {
+    fn from_value(x: Value) -> Result<Self, Error>;
+}
+
+macro_rules! impl_from_value {
+    ($t:ty, $variant:ident, $accessor:ident, $msg:literal) => {
+        impl FromValue for $t {
+            fn from_value(x: Value) -> Result<$t, Error> {
+                match x.t {
+                    PrimitiveType::$variant => Ok(x.$accessor()),
+                    _ => Err(Error::Misc($msg)),
+                }
+            }
+        }
+    };
+}
+
+impl_from_value!(
+    i32,
+    I32,
+    as_i32_unchecked,
+    "Cannot extract as i32 from incorrect type"
+);
+impl_from_value!(
+    i64,
+    I64,
+    as_i64_unchecked,
+    "Cannot extract as i64 from incorrect type"
+);
+impl_from_value!(
+    f32,
+    F32,
+    as_f32_unchecked,
+    "Cannot extract as f32 from incorrect type"
+);
+impl_from_value!(
+    f64,
+    F64,
+    as_f64_unchecked,
+    "Cannot extract as f64 from incorrect type"
+);
+
+/// Native types a function's results can be extracted as: `()`, a single value, or a tuple
+/// of two or three values
+pub trait FromValues: Sized {
+    fn from_values(values: Vec<Value>) -> Result<Self, Error>;
+}
+
+impl FromValues for () {
+    fn from_values(values: Vec<Value>) -> Result<(), Error> {
+        match values.len() {
+            0 => Ok(()),
+            _ => Err(Error::Misc("Wrong number of results")),
+        }
+    }
+}
+
+impl<T: FromValue> FromValues for T {
+    fn from_values(values: Vec<Value>) -> Result<T, Error> {
+        match values.as_slice() {
+            [a] => T::from_value(*a),
+            _ => Err(Error::Misc("Wrong number of results")),
+        }
+    }
+}
+
+impl<A: FromValue, B: FromValue> FromValues for (A, B) {
+    fn from_values(values: Vec<Value>) -> Result<(A, B), Error> {
+        match values.as_slice() {
+            [a, b] => Ok((A::from_value(*a)?, B::from_value(*b)?)),
+            _ => Err(Error::Misc("Wrong number of results")),
+        }
+    }
+}
+
+impl<A: FromValue, B: FromValue, C: FromValue> FromValues for (A, B, C) {
+    fn from_values(values: Vec<Value>) -> Result<(A, B, C), Error> {
+        match values.as_slice() {
+            [a, b, c] => Ok((A::from_value(*a)?, B::from_value(*b)?, C::from_value(*c)?)),
+            _ => Err(Error::Misc("Wrong number of results")),
+        }
+    }
+}
+
+/// Extract a function's results as native types, e.g.
+/// `let (a, b): (i32, f64) = into_results(module.call("f", args)?)?`
+pub fn into_results<T: FromValues>(values: Vec<Value>) -> Result<T, Error> {
+    T::from_values(values)
+}
+
 impl From<&PrimitiveType> for Value {
     fn from(x: &PrimitiveType) -> Value {
-        match x {
-            PrimitiveType::I32 => Value::new(0_i32),
-            PrimitiveType::I64 => Value::new(0_i64),
-            PrimitiveType::F32 => Value::new(0_f32),
-            PrimitiveType::F64 => Value::new(0_f64),
-        }
+        Value::default_for(*x)
+    }
+}
+
+impl fmt::Debug for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// Writes a NaN the way the text format does, e.g. `nan`, `-nan` or `nan:0x200000`, so its
+/// payload isn't lost
+fn write_nan(
+    f: &mut fmt::Formatter<'_>,
+    negative: bool,
+    payload: u64,
+    canonical: u64,
+) -> fmt::Result {
+    if negative {
+        write!(f, "-")?;
+    }
+    if payload == canonical {
+        write!(f, "nan")
+    } else {
+        write!(f, "nan:0x{:x}", payload)
     }
 }
 
-impl std::fmt::Display for Value {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         unsafe {
             match self.t {
                 PrimitiveType::I32 => {
@@ -176,11 +533,36 @@ impl std::fmt::Display for Value {
                 PrimitiveType::I64 => {
                     write!(f, "(i64:{})", self.v.i64)
                 }
+                PrimitiveType::F32 if self.v.f32.is_nan() => {
+                    let bits = self.v.i32 as u32;
+                    write!(f, "(f32:")?;
+                    write_nan(f, bits >> 31 != 0, (bits & 0x7F_FFFF) as u64, 0x40_0000)?;
+                    write!(f, ")")
+                }
+                // Debug formatting is the shortest that round-trips, keeps the sign of -0.0,
+                // and uses exponents for very large or small magnitudes
                 PrimitiveType::F32 => {
-                    write!(f, "(f32:{})", self.v.f32)
+                    write!(f, "(f32:{:?})", self.v.f32)
+                }
+                PrimitiveType::F64 if self.v.f64.is_nan() => {
+                    let bits = self.v.i64 as u64;
+                    write!(f, "(f64:")?;
+                    write_nan(
+                        f,
+                        bits >> 63 != 0,
+                        bits & 0xF_FFFF_FFFF_FFFF,
+                        0x8_0000_0000_0000,
+                    )?;
+                    write!(f, ")")
                 }
                 PrimitiveType::F64 => {
-                    write!(f, "(f64:{})", self.v.f64)
+                    write!(f, "(f64:{:?})", self.v.f64)
+                }
+                PrimitiveType::FuncRef | PrimitiveType::ExternRef => {
+                    match self.as_ref_unchecked() {
+                        Some(n) => write!(f, "({}:{})", self.t, n),
+                        None => write!(f, "({}:null)", self.t),
+                    }
                 }
             }
         }
@@ -188,37 +570,87 @@ impl std::fmt::Display for Value {
 }
 
 /// Represents expected runtime errors, i.e. problems with the program, not the interpreter
+#[derive(Debug, Clone, PartialEq)]
 pub enum Trap {
     MemoryOutOfBounds,
-    UndefinedDivision,
+    /// Integer division or remainder by zero
+    DivideByZero,
+    Unreachable,
+    /// `call_indirect` found a function whose type differs from the expected one
+    IndirectCallTypeMismatch,
+    /// `call_indirect` used a table index that is out of range or not initialized
+    UndefinedElement,
+    /// A table instruction accessed elements past the end of the table or an element segment
+    TableOutOfBounds,
+    /// An atomic memory access used an address that isn't a multiple of its width
+    UnalignedAtomic,
+    /// The deadline given to `Module::call_with_deadline` passed before the call finished
+    Timeout,
+    /// A function's operand stack grew past its maximum depth
+    StackOverflow,
+    /// Calls nested deeper than the maximum call depth
+    CallStackExhausted,
+    /// `trunc` was given a NaN, which has no integer value
+    InvalidConversionToInteger,
+    /// An integer result doesn't fit in its type, e.g. the minimum value divided by -1 or
+    /// `trunc` of a float that is too large
+    IntegerOverflow,
+}
+
+/// An exception raised by `throw`, carrying the values described by its tag's type
+#[derive(Debug, Clone)]
+pub struct Exception {
+    pub tag: usize,
+    pub values: Vec<Value>,
 }
 
 pub enum ControlInfo {
     Branch(u32),
     Return,
     Trap(Trap),
+    /// An exception unwinding towards the nearest `try` with a matching `catch`
+    Throw(Exception),
     None,
 }
 
+/// Most values a function's operand stack may hold unless `ExecutionConfig` says otherwise
+pub const DEFAULT_MAX_STACK_DEPTH: usize = 1 << 20;
+
+/// Most calls that may be nested unless `ExecutionConfig` says otherwise. Each one takes
+/// native stack, a few KiB in a debug build, so this stays clear of overflowing a 2 MiB thread
+/// stack. Hosts running on a larger stack can allow more.
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 300;
+
 /// Representation of a wasm stack.
 /// All functions use a new stack when called.
-#[derive(Default)]
 pub struct Stack {
     values: Vec<Value>,
+    max_depth: usize,
 }
 
 impl Stack {
-    fn new() -> Self {
-        Self::default()
+    /// An empty stack backed by `values`, which is cleared first so its allocation can be reused
+    fn with_values(mut values: Vec<Value>, max_depth: usize) -> Self {
+        values.clear();
+        Self { values, max_depth }
+    }
+
+    fn height(&self) -> usize {
+        self.values.len()
     }
 
-    fn push_value(&mut self, v: Value) {
-        log::debug!("Pushing {}", v);
+    /// Push a value, trapping if the stack is already at its maximum depth
+    fn push_value(&mut self, v: Value) -> Result<(), Error> {
+        debug!("Pushing {}", v);
+        if self.values.len() >= self.max_depth {
+            return Err(Error::Trap(Trap::StackOverflow));
+        }
         self.values.push(v);
+        Ok(())
     }
 
     pub fn pop_value(&mut self) -> Result<Value, Error> {
-        log::debug!("Current stack len {}", self.values.len());
+        debug!("Current stack len {}", self.values.len());
 
         if self.values.is_empty() {
             Err(Error::StackViolation)
@@ -230,16 +662,28 @@ impl Stack {
     /// Return the 0-indexed offset'th value from the stack (such that 0 is the most recently pushed value)
     pub fn fetch_value(&self, offset: usize) -> Result<&Value, Error> {
         let stack_size = self.values.len();
-        let offset_to_fetch = stack_size - 1 - offset;
-        match self.values.get(offset_to_fetch) {
-            Some(n) => Ok(n),
+        match offset
+            .checked_add(1)
+            .and_then(|n| stack_size.checked_sub(n))
+        {
+            Some(n) => Ok(&self.values[n]),
             None => {
-                log::debug!("Try to read {} stack size {}", offset_to_fetch, stack_size);
+                debug!("Try to read offset {} stack size {}", offset, stack_size);
                 Err(Error::StackViolation)
             }
         }
     }
 
+    /// Discard every value above `height`, except for the `keep` most recently pushed ones
+    pub fn unwind(&mut self, height: usize, keep: usize) -> Result<(), Error> {
+        let stack_size = self.values.len();
+        if height + keep > stack_size {
+            return Err(Error::StackViolation);
+        }
+        self.values.drain(height..(stack_size - keep));
+        Ok(())
+    }
+
     pub fn assert_empty(&self) -> Result<(), Error> {
         if self.values.is_empty() {
             Ok(())
@@ -249,8 +693,87 @@ impl Stack {
     }
 }
 
-impl std::fmt::Display for Stack {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> Result<(), std::fmt::Error> {
+/// The types of the values on an operand stack, for working out what a function does to the
+/// stack without running it
+pub struct TypeStack<'a> {
+    types: Vec<PrimitiveType>,
+    /// Types of the function's params followed by its declared locals
+    locals: Vec<PrimitiveType>,
+    module: &'a Module,
+}
+
+impl<'a> TypeStack<'a> {
+    fn new(module: &'a Module, function: &Function) -> Self {
+        let mut locals = function.r#type.params.clone();
+        locals.extend_from_slice(&function.local_types);
+        Self {
+            types: Vec::new(),
+            locals,
+            module,
+        }
+    }
+
+    pub fn push(&mut self, t: PrimitiveType) {
+        self.types.push(t);
+    }
+
+    /// Pop a value, which must have type `t`
+    pub fn pop(&mut self, t: PrimitiveType) -> Result<(), Error> {
+        match self.types.pop() {
+            Some(popped) if popped == t => Ok(()),
+            Some(_) => Err(Error::Misc("Operand type mismatch")),
+            None => Err(Error::StackViolation),
+        }
+    }
+
+    /// Pop a value of any type, giving its type
+    pub fn pop_any(&mut self) -> Result<PrimitiveType, Error> {
+        self.types.pop().ok_or(Error::StackViolation)
+    }
+
+    /// Pop the params of `function_type` and push its results
+    pub fn apply(&mut self, function_type: &FunctionType) -> Result<(), Error> {
+        for t in function_type.params.iter().rev() {
+            self.pop(*t)?;
+        }
+        self.types.extend_from_slice(&function_type.returns);
+        Ok(())
+    }
+
+    pub fn local(&self, index: usize) -> Result<PrimitiveType, Error> {
+        match self.locals.get(index) {
+            Some(t) => Ok(*t),
+            None => Err(Error::Misc("Local index is not valid")),
+        }
+    }
+
+    pub fn global(&self, index: usize) -> Result<PrimitiveType, Error> {
+        match self.module.globals().get(index) {
+            Some(g) => Ok(g.value.get_type()),
+            None => Err(Error::Misc("Global index out of range")),
+        }
+    }
+
+    /// Type of the elements of the table with the given index
+    pub fn table_type(&self, index: usize) -> Result<PrimitiveType, Error> {
+        Ok(self.module.ctx.table(index)?.element_type())
+    }
+
+    /// Type of the references in the element segment with the given index
+    pub fn element_type(&self, index: usize) -> Result<PrimitiveType, Error> {
+        match self.module.ctx.element_types.get(index) {
+            Some(t) => Ok(*t),
+            None => Err(Error::Misc("Element segment index is not valid")),
+        }
+    }
+
+    pub fn module(&self) -> &'a Module {
+        self.module
+    }
+}
+
+impl fmt::Display for Stack {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "Current stack:\n[")?;
         for v in self.values.iter() {
             writeln!(f, "  {}", v)?;
@@ -260,170 +783,680 @@ impl std::fmt::Display for Stack {
     }
 }
 
-pub trait Instruction {
+pub trait Instruction: InstructionClone {
     /// A wasm instruction may modify any state of the program
     fn execute(
         &self,
         stack: &mut Stack,
-        memory: &mut Memory,
+        ctx: &mut ExecutionContext,
         locals: &mut Vec<Value>,
-        functions: &Vec<Function>,
+        functions: &[Function],
     ) -> Result<ControlInfo, Error>;
-}
 
-pub mod inst;
+    /// The instruction's name in the wasm text format, e.g. `i32.add`
+    fn mnemonic(&self) -> String;
 
-#[derive(Default)]
-struct Table {
-    functions: Vec<usize>,
+    /// Apply what the instruction does to the types on the operand stack, failing if its
+    /// operands have the wrong types. Instructions after which the stack can't be known, such
+    /// as `br` and `unreachable`, always fail.
+    fn check_types(&self, _types: &mut TypeStack) -> Result<(), Error> {
+        Err(Error::Misc(
+            "Operand stack is unknown after this instruction",
+        ))
+    }
 }
 
-pub struct Function {
-    r#type: FunctionType,
-    local_types: Vec<PrimitiveType>,
-    instructions: Vec<Box<dyn Instruction>>,
+/// Cloning for boxed instructions, provided for every `Instruction` that is `Clone`
+pub trait InstructionClone {
+    fn clone_box(&self) -> Box<dyn Instruction>;
 }
 
-impl Function {
-    pub fn new(r#type: FunctionType) -> Self {
-        Self {
-            r#type,
-            local_types: Vec::new(),
-            instructions: Vec::new(),
-        }
+impl<T: Instruction + Clone + 'static> InstructionClone for T {
+    fn clone_box(&self) -> Box<dyn Instruction> {
+        Box::new(self.clone())
     }
+}
 
-    pub fn push_inst(&mut self, i: Box<dyn Instruction>) {
-        self.instructions.push(i);
+impl Clone for Box<dyn Instruction> {
+    fn clone(&self) -> Self {
+        self.clone_box()
     }
+}
 
-    pub fn num_params(&self) -> usize {
-        self.r#type.num_params()
-    }
+/// Number of times each instruction was executed, keyed by mnemonic
+pub type ProfileCounters = BTreeMap<String, u64>;
 
-    pub fn num_locals(&self) -> usize {
-        self.local_types.len()
-    }
+/// How much the interpreter records about a call as it runs
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
+pub enum Verbosity {
+    #[default]
+    Off,
+    /// Record each instruction executed and what it did, readable with `Module::take_trace`
+    Trace,
+}
 
-    pub fn new_locals(&mut self, count: usize, t: PrimitiveType) {
-        self.local_types.reserve(count);
-        for _ in 0..count {
-            self.local_types.push(t);
-        }
+/// Options that change how instructions behave during a call
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ExecutionConfig {
+    /// Make float results identical on every platform. Any NaN produced by float arithmetic,
+    /// `sqrt`, rounding, `min`/`max` or `promote`/`demote` becomes the canonical NaN, so its
+    /// sign and payload no longer depend on the host. `abs`, `neg`, `copysign` and
+    /// reinterpretations only touch bits and are left alone. Subnormals are always kept as they
+    /// are, never flushed to zero, so they need no special handling.
+    pub deterministic: bool,
+    /// Most values a function's operand stack may hold, or None for
+    /// `DEFAULT_MAX_STACK_DEPTH`. Pushing past it traps with `Trap::StackOverflow`.
+    pub max_stack_depth: Option<usize>,
+    /// Most calls that may be nested, or None for `DEFAULT_MAX_CALL_DEPTH`. A call past it
+    /// traps with `Trap::CallStackExhausted`.
+    pub max_call_depth: Option<usize>,
+    /// Tracing works without the `log` feature or `RUST_LOG`
+    pub verbosity: Verbosity,
+}
+
+/// State shared by every function executed during a call into a module
+#[derive(Clone, Default)]
+pub struct ExecutionContext {
+    function_types: Vec<FunctionType>,
+    /// Types of the exception tags, whose params are the values an exception carries
+    tags: Vec<FunctionType>,
+    memories: Vec<Memory>,
+    globals: Vec<Global>,
+    tables: Vec<Table>,
+    /// Element segments by index, for `table.init`. Active and declarative segments are
+    /// left empty, since they're dropped once the module is loaded.
+    elements: Vec<Vec<Value>>,
+    /// Reference type of each element segment, which is kept when the segment is dropped
+    element_types: Vec<PrimitiveType>,
+    /// Data segments by index, for `memory.init`. Active segments are left empty, since
+    /// they're dropped once the module is instantiated.
+    data: Vec<Vec<u8>>,
+    profile: Option<ProfileCounters>,
+    /// Cleared vectors left over from finished calls, reused for the locals, stacks and results
+    /// of later ones
+    spare_values: Vec<Vec<Value>>,
+    /// Give back the whole stack when a function returns with the wrong number of values
+    debug_stack: bool,
+    /// Cap on memory size set by the embedder, on top of the module's own maximum
+    memory_limit_pages: Option<u32>,
+    config: ExecutionConfig,
+    /// Calls into functions with bodies that haven't returned yet
+    call_depth: usize,
+    /// Messages recorded while `config.verbosity` is `Trace`
+    trace: Vec<String>,
+    /// Wall-clock time at which the current call is cut short
+    #[cfg(feature = "std")]
+    deadline: Option<std::time::Instant>,
+    /// Instructions between checks of the deadline, or None for the default
+    #[cfg(feature = "std")]
+    deadline_check_interval: Option<u32>,
+    /// Instructions left to run before the deadline is next checked
+    #[cfg(feature = "std")]
+    deadline_countdown: u32,
+}
+
+/// Instructions run between checks of the clock when a call has a deadline
+#[cfg(feature = "std")]
+pub const DEFAULT_DEADLINE_CHECK_INTERVAL: u32 = 1000;
+
+/// Most spare vectors `ExecutionContext` keeps for reuse. Deep recursion frees more than this
+/// at once, and there's no point holding on to all of them.
+const MAX_SPARE_VALUE_VECS: usize = 64;
+
+impl ExecutionContext {
+    /// An empty vector for values, reusing the allocation of one given to `recycle_values`
+    /// if there is one
+    pub fn take_values(&mut self) -> Vec<Value> {
+        self.spare_values.pop().unwrap_or_default()
     }
 
-    fn do_return(mut stack: Stack) -> Result<Value, Error> {
-        let ret = stack.pop_value();
-        stack.assert_empty()?;
-        ret
+    /// Keep a vector that's no longer needed so that `take_values` can hand it out again
+    pub fn recycle_values(&mut self, mut values: Vec<Value>) {
+        if self.spare_values.len() < MAX_SPARE_VALUE_VECS && values.capacity() > 0 {
+            values.clear();
+            self.spare_values.push(values);
+        }
     }
 
-    pub fn call(
-        &self,
-        functions: &Vec<Function>,
-        memory: &mut Memory,
-        args: Vec<Value>,
-    ) -> Result<Value, Error> {
-        let mut stack = Stack::new();
-        let mut locals = Vec::with_capacity(self.num_params() + self.num_locals());
-        for arg in args {
-            locals.push(arg);
+    /// Hook run by the interpreter loops ahead of every instruction. Gives a trap if the
+    /// instruction must not be run.
+    fn before_execute(&mut self, inst: &dyn Instruction) -> Result<(), Trap> {
+        if let Some(profile) = &mut self.profile {
+            *profile.entry(inst.mnemonic()).or_insert(0) += 1;
         }
-        for t in &self.local_types {
-            locals.push(Value::from(t));
+        if self.config.verbosity == Verbosity::Trace {
+            self.trace.push(format!("Executing {}", inst.mnemonic()));
         }
-        for instruction in &self.instructions {
-            match instruction.execute(&mut stack, memory, &mut locals, functions)? {
-                ControlInfo::Return => {
-                    return Self::do_return(stack);
+        #[cfg(feature = "std")]
+        if let Some(deadline) = self.deadline {
+            if self.deadline_countdown == 0 {
+                if std::time::Instant::now() >= deadline {
+                    return Err(Trap::Timeout);
                 }
-                ControlInfo::Trap(Trap::MemoryOutOfBounds) => panic!(), //TODO: don't panic, handle traps gracefully
-                ControlInfo::Trap(Trap::UndefinedDivision) => panic!(),
-                _ => (),
-            };
+                self.deadline_countdown = self
+                    .deadline_check_interval
+                    .unwrap_or(DEFAULT_DEADLINE_CHECK_INTERVAL);
+            }
+            self.deadline_countdown -= 1;
         }
-        Self::do_return(stack)
+        Ok(())
     }
-}
-
-#[derive(Default)]
-pub struct Memory {
-    bytes: Vec<u8>,
-    virtual_size_pages: u32,
-    upper_limit_pages: u32,
-}
 
-const PAGE_SIZE: u64 = 0x10000;
-impl Memory {
-    pub fn new(min: u32, max: u32) -> Self {
-        let mut s = Self {
-            bytes: Vec::with_capacity((PAGE_SIZE * min as u64) as usize),
-            virtual_size_pages: min,
-            upper_limit_pages: max,
-        };
-        s.write(PAGE_SIZE * min as u64, 32, 4); // It looks like
-        s
+    /// Apply the configured treatment of NaNs to a float instruction's result
+    fn float_result(&self, v: Value) -> Value {
+        if self.config.deterministic {
+            v.canonicalize_nan()
+        } else {
+            v
+        }
     }
 
-    pub fn write(&mut self, mut value: u64, bitwidth: u8, address: u64) -> Option<()> {
-        log::debug!(
-            "Write to address 0x{:x} with bitwidth {} and value 0x{:x}",
-            address,
-            bitwidth,
-            value
-        );
-        if bitwidth % 8 != 0 {
-            // Probably don't even need to implement this
-            panic!();
+    fn memory(&self, index: usize) -> Result<&Memory, Error> {
+        match self.memories.get(index) {
+            Some(m) => Ok(m),
+            None => Err(Error::Misc("Memory index is not valid")),
         }
+    }
 
-        let bytes_to_write = bitwidth / 8;
-        let last_write_address = address + bytes_to_write as u64;
-
-        // Check for out of bounds access
-        if last_write_address > PAGE_SIZE * self.virtual_size_pages as u64 {
-            return None;
+    fn memory_mut(&mut self, index: usize) -> Result<&mut Memory, Error> {
+        match self.memories.get_mut(index) {
+            Some(m) => Ok(m),
+            None => Err(Error::Misc("Memory index is not valid")),
         }
+    }
 
-        // Resize internal vector if needed
-        if self.bytes.is_empty() || last_write_address > (self.bytes.len() - 1) as u64 {
-            self.bytes.resize((last_write_address + 1) as usize, 0);
+    fn table(&self, index: usize) -> Result<&Table, Error> {
+        match self.tables.get(index) {
+            Some(t) => Ok(t),
+            None => Err(Error::Misc("Table index is not valid")),
         }
+    }
 
-        for i in (address..(address + bytes_to_write as u64)).rev() {
-            self.bytes[i as usize] = (value & 0xFF) as u8;
-            value >>= 8;
+    fn table_mut(&mut self, index: usize) -> Result<&mut Table, Error> {
+        match self.tables.get_mut(index) {
+            Some(t) => Ok(t),
+            None => Err(Error::Misc("Table index is not valid")),
         }
-
-        Some(())
     }
 
-    pub fn read(
-        &mut self,
-        result_type: PrimitiveType,
-        bitwidth: u8,
-        address: u64,
-    ) -> Option<Value> {
-        let bytes_to_read = (bitwidth / 8) as u64;
-
-        let mut result = 0_u64;
-
-        for i in address..(address + bytes_to_read) {
-            result <<= 8;
-            result += self.bytes[i as usize] as u64;
+    /// Grow a memory by `delta` pages unless that would exceed the embedder's limit
+    fn grow_memory(&mut self, index: usize, delta: u32) -> Result<Option<u32>, Error> {
+        let limit = self.memory_limit_pages;
+        let memory = self.memory_mut(index)?;
+        if let Some(limit) = limit {
+            match memory.size_pages().checked_add(delta) {
+                Some(n) if n <= limit => (),
+                _ => return Ok(None),
+            }
         }
-
-        log::debug!(
-            "Read from address 0x{:x} with bitwidth {} and value 0x{:x}",
-            address,
-            bitwidth,
-            result
-        );
-        Some(Value::from_explicit_type(result_type, result))
+        Ok(memory.grow(delta))
     }
 }
 
-#[derive(Default, Clone)]
+pub mod inst;
+pub mod linker;
+
+/// A table of references, such as the functions indexed by `call_indirect`
+#[derive(Clone)]
+pub struct Table {
+    element_type: PrimitiveType,
+    elements: Vec<Value>,
+    upper_limit: u32,
+}
+
+impl Default for Table {
+    fn default() -> Self {
+        Self::new(0, 0)
+    }
+}
+
+impl Table {
+    /// A funcref table of `min` nulls, that may grow to `max` elements
+    pub fn new(min: u32, max: u32) -> Self {
+        Self::with_type(PrimitiveType::FuncRef, min, max)
+    }
+
+    /// A table of `min` nulls of the reference type `element_type`
+    pub fn with_type(element_type: PrimitiveType, min: u32, max: u32) -> Self {
+        Self {
+            element_type,
+            elements: vec![Value::null(element_type); min as usize],
+            upper_limit: max,
+        }
+    }
+
+    pub fn element_type(&self) -> PrimitiveType {
+        self.element_type
+    }
+
+    pub fn size(&self) -> u32 {
+        self.elements.len() as u32
+    }
+
+    /// Write the references of an element segment into the table starting at `offset`, or
+    /// return None if they don't fit
+    pub fn init(&mut self, offset: u64, values: &[Value]) -> Option<()> {
+        self.set_range(u32::try_from(offset).ok()?, values)
+    }
+
+    /// The reference stored at `index`, if it's in range
+    pub fn get(&self, index: u32) -> Option<Value> {
+        self.elements.get(index as usize).copied()
+    }
+
+    pub fn set(&mut self, index: u32, value: Value) -> Option<()> {
+        *self.elements.get_mut(index as usize)? = value;
+        Some(())
+    }
+
+    /// The references from `start` up to `end`, if they're all in range
+    pub fn get_range(&self, start: u32, end: u32) -> Option<&[Value]> {
+        self.elements.get(start as usize..end as usize)
+    }
+
+    /// Overwrite the elements starting at `offset` with `values`, or return None if they
+    /// don't fit
+    pub fn set_range(&mut self, offset: u32, values: &[Value]) -> Option<()> {
+        let end = (offset as usize).checked_add(values.len())?;
+        self.elements
+            .get_mut(offset as usize..end)?
+            .copy_from_slice(values);
+        Some(())
+    }
+
+    /// Add `delta` elements set to `value`, returning the old size, or None if the table
+    /// would exceed its maximum or the elements can't be allocated
+    pub fn grow(&mut self, delta: u32, value: Value) -> Option<u32> {
+        let old_size = self.size();
+        let new_size = old_size.checked_add(delta)?;
+        if new_size > self.upper_limit {
+            return None;
+        }
+        self.elements.try_reserve_exact(delta as usize).ok()?;
+        self.elements.resize(new_size as usize, value);
+        Some(old_size)
+    }
+
+    /// Set `count` elements starting at `offset` to `value`
+    pub fn fill(&mut self, offset: u32, value: Value, count: u32) -> Option<()> {
+        let end = offset.checked_add(count)?;
+        self.elements
+            .get_mut(offset as usize..end as usize)?
+            .fill(value);
+        Some(())
+    }
+
+    /// Copy `count` elements from `src` to `dst`, where the two ranges may overlap
+    pub fn copy_within(&mut self, dst: u32, src: u32, count: u32) -> Option<()> {
+        let src_end = src.checked_add(count)?;
+        let dst_end = dst.checked_add(count)?;
+        if src_end > self.size() || dst_end > self.size() {
+            return None;
+        }
+        self.elements
+            .copy_within(src as usize..src_end as usize, dst as usize);
+        Some(())
+    }
+}
+
+/// A global variable along with whether it may be modified by `global.set`
+#[derive(Copy, Clone)]
+pub struct Global {
+    pub value: Value,
+    pub mutable: bool,
+}
+
+impl Global {
+    pub fn new(value: Value, mutable: bool) -> Self {
+        Self { value, mutable }
+    }
+}
+
+#[derive(Clone)]
+pub struct Function {
+    /// Shared between the functions of a module that have the same type
+    r#type: Rc<FunctionType>,
+    /// Types of the declared locals. The locals themselves are created for each call, so
+    /// recursive and repeated calls never share them.
+    local_types: Vec<PrimitiveType>,
+    instructions: Vec<Box<dyn Instruction>>,
+    /// Debug name from the "name" custom section, if present
+    name: Option<String>,
+    /// The (module, name) an imported function comes from. Imported functions have no body.
+    import: Option<(String, String)>,
+    /// Module an imported function was linked to, which runs it when it's called
+    linked: Option<Rc<RefCell<Module>>>,
+}
+
+impl Function {
+    pub fn new<T: Into<Rc<FunctionType>>>(r#type: T) -> Self {
+        Self {
+            r#type: r#type.into(),
+            local_types: Vec::new(),
+            instructions: Vec::new(),
+            name: None,
+            import: None,
+            linked: None,
+        }
+    }
+
+    /// A function imported as `name` from `module`. Calling it fails until `Linker` links it.
+    pub fn imported<T: Into<Rc<FunctionType>>>(r#type: T, module: String, name: String) -> Self {
+        Self {
+            import: Some((module, name)),
+            ..Self::new(r#type)
+        }
+    }
+
+    /// The module and name the function is imported as, if it is imported
+    pub fn import(&self) -> Option<(&str, &str)> {
+        self.import
+            .as_ref()
+            .map(|(module, name)| (module.as_str(), name.as_str()))
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn push_inst(&mut self, i: Box<dyn Instruction>) {
+        self.instructions.push(i);
+    }
+
+    pub fn num_params(&self) -> usize {
+        self.r#type.num_params()
+    }
+
+    pub fn num_results(&self) -> usize {
+        self.r#type.returns.len()
+    }
+
+    pub fn signature(&self) -> &FunctionType {
+        &self.r#type
+    }
+
+    pub fn num_locals(&self) -> usize {
+        self.local_types.len()
+    }
+
+    pub fn new_locals(&mut self, count: usize, t: PrimitiveType) {
+        self.local_types.reserve(count);
+        for _ in 0..count {
+            self.local_types.push(t);
+        }
+    }
+
+    /// Pop exactly as many results as the function declares, in order. Functions without
+    /// results give an empty vector. With `debug_stack` set, any other stack height gives
+    /// `Error::FinalStack` holding every value left on the stack.
+    fn do_return(
+        &self,
+        stack: &mut Stack,
+        ctx: &mut ExecutionContext,
+    ) -> Result<Vec<Value>, Error> {
+        if ctx.debug_stack && stack.height() != self.num_results() {
+            return Err(Error::FinalStack(core::mem::take(&mut stack.values)));
+        }
+        let mut results = ctx.take_values();
+        for _ in 0..self.num_results() {
+            results.push(stack.pop_value()?);
+        }
+        results.reverse();
+        stack.assert_empty()?;
+        Ok(results)
+    }
+
+    /// Call the function. `args` becomes its locals, so passing a vector from
+    /// `ExecutionContext::take_values` and recycling the results avoids allocating.
+    pub fn call(
+        &self,
+        functions: &[Function],
+        ctx: &mut ExecutionContext,
+        args: Vec<Value>,
+    ) -> Result<Vec<Value>, Error> {
+        if let Some((_, name)) = &self.import {
+            return self.call_linked(name, args);
+        }
+        let max_call_depth = ctx.config.max_call_depth.unwrap_or(DEFAULT_MAX_CALL_DEPTH);
+        if ctx.call_depth >= max_call_depth {
+            ctx.recycle_values(args);
+            return Err(Error::Trap(Trap::CallStackExhausted));
+        }
+        ctx.call_depth += 1;
+        let mut stack = Stack::with_values(
+            ctx.take_values(),
+            ctx.config
+                .max_stack_depth
+                .unwrap_or(DEFAULT_MAX_STACK_DEPTH),
+        );
+        let mut locals = args;
+        locals.reserve(self.num_locals());
+        for t in &self.local_types {
+            locals.push(Value::default_for(*t));
+        }
+        let result = self.run(functions, ctx, &mut stack, &mut locals);
+        ctx.call_depth -= 1;
+        ctx.recycle_values(locals);
+        ctx.recycle_values(stack.values);
+        result
+    }
+
+    /// Run an imported function in the module it's linked to, with that module's own state
+    fn call_linked(&self, name: &str, args: Vec<Value>) -> Result<Vec<Value>, Error> {
+        let instance = match &self.linked {
+            Some(m) => m,
+            None => return Err(Error::Misc("Imported function is not linked")),
+        };
+        match instance.try_borrow_mut() {
+            Ok(mut m) => m.call(name, args),
+            // Calls back into a module that's still running would need its state twice
+            Err(_) => Err(Error::Misc("Linked module is already running")),
+        }
+    }
+
+    fn run(
+        &self,
+        functions: &[Function],
+        ctx: &mut ExecutionContext,
+        stack: &mut Stack,
+        locals: &mut Vec<Value>,
+    ) -> Result<Vec<Value>, Error> {
+        for instruction in &self.instructions {
+            if let Err(t) = ctx.before_execute(instruction.as_ref()) {
+                return Err(Error::Trap(t));
+            }
+            match instruction.execute(stack, ctx, locals, functions)? {
+                // Branching to the function's own body block is the same as returning
+                ControlInfo::Return | ControlInfo::Branch(_) => {
+                    // An explicit return may leave operands underneath the results
+                    stack.unwind(0, self.r#type.returns.len())?;
+                    return self.do_return(stack, ctx);
+                }
+                ControlInfo::Trap(t) => return Err(Error::Trap(t)),
+                ControlInfo::Throw(e) => return Err(Error::UncaughtException(e)),
+                _ => (),
+            };
+        }
+        self.do_return(stack, ctx)
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Memory {
+    /// Always exactly as long as the memory's current size
+    bytes: Vec<u8>,
+    virtual_size_pages: u32,
+    upper_limit_pages: u32,
+    /// Declared shared by the threads proposal. Execution is single-threaded, so this only
+    /// records the declaration.
+    shared: bool,
+}
+
+const PAGE_SIZE: u64 = 0x10000;
+/// Most pages a 32-bit memory can have, i.e. 4GiB
+pub const MAX_PAGES: u32 = 0x10000;
+
+/// Size in bytes of `pages` pages, or None if that overflows
+fn pages_to_bytes(pages: u32) -> Option<u64> {
+    (pages as u64).checked_mul(PAGE_SIZE)
+}
+
+impl Memory {
+    /// A memory of `min` pages, all zero, that may grow to `max` pages
+    pub fn new(min: u32, max: u32) -> Self {
+        let min_bytes = pages_to_bytes(min).unwrap_or(0);
+        Self {
+            bytes: vec![0; min_bytes as usize],
+            virtual_size_pages: min,
+            upper_limit_pages: max,
+            shared: false,
+        }
+    }
+
+    /// Current size of the memory in bytes, or None if it can't be represented. Accesses are
+    /// treated as out of bounds in that case.
+    pub fn size_bytes(&self) -> Option<u64> {
+        pages_to_bytes(self.virtual_size_pages)
+    }
+
+    /// Address accessed by a load or store. Both parts are 32 bits, so the sum can't overflow
+    /// a u64, and `read` and `write` check the end of the access against the memory.
+    pub fn effective_addr(base: u32, offset: u32) -> u64 {
+        base as u64 + offset as u64
+    }
+
+    pub fn is_shared(&self) -> bool {
+        self.shared
+    }
+
+    pub fn set_shared(&mut self, shared: bool) {
+        self.shared = shared;
+    }
+
+    pub fn size_pages(&self) -> u32 {
+        self.virtual_size_pages
+    }
+
+    /// Grow the memory by `delta` pages, returning the previous size or None if it can't grow.
+    /// The new pages always read as zero. Growing either fully succeeds or leaves the memory
+    /// unchanged, including when the host can't allocate the new pages.
+    pub fn grow(&mut self, delta: u32) -> Option<u32> {
+        let old_pages = self.virtual_size_pages;
+        let new_pages = old_pages.checked_add(delta)?;
+        if new_pages > self.upper_limit_pages || new_pages > MAX_PAGES {
+            return None;
+        }
+        let new_len = usize::try_from(pages_to_bytes(new_pages)?).ok()?;
+        self.bytes
+            .try_reserve_exact(new_len - self.bytes.len())
+            .ok()?;
+
+        self.bytes.resize(new_len, 0);
+        self.virtual_size_pages = new_pages;
+        Some(old_pages)
+    }
+
+    /// Shrink or grow the memory to `pages` pages, all zero apart from `image` at address 0,
+    /// keeping its allocation
+    fn reset(&mut self, pages: u32, image: &[u8]) {
+        self.bytes.clear();
+        self.bytes
+            .resize(pages_to_bytes(pages).unwrap_or(0) as usize, 0);
+        self.bytes[..image.len()].copy_from_slice(image);
+        self.virtual_size_pages = pages;
+    }
+
+    /// The memory's contents up to the last non-zero byte
+    fn image(&self) -> Vec<u8> {
+        let len = self
+            .bytes
+            .iter()
+            .rposition(|b| *b != 0)
+            .map_or(0, |i| i + 1);
+        self.bytes[..len].to_vec()
+    }
+
+    pub fn write(&mut self, mut value: u64, bitwidth: u8, address: u64) -> Option<()> {
+        debug!(
+            "Write to address 0x{:x} with bitwidth {} and value 0x{:x}",
+            address, bitwidth, value
+        );
+        if !bitwidth.is_multiple_of(8) {
+            // Probably don't even need to implement this
+            panic!();
+        }
+
+        let bytes_to_write = bitwidth / 8;
+        let last_write_address = address.checked_add(bytes_to_write as u64)?;
+
+        // Check for out of bounds access
+        if last_write_address > self.size_bytes()? {
+            return None;
+        }
+
+        // wasm memory is little endian
+        for i in address..(address + bytes_to_write as u64) {
+            self.bytes[i as usize] = (value & 0xFF) as u8;
+            value >>= 8;
+        }
+
+        Some(())
+    }
+
+    /// Borrow `len` bytes starting at `address`, without copying them
+    pub fn as_slice(&self, address: u32, len: u32) -> Result<&[u8], Error> {
+        let range = self.checked_range(address, len)?;
+        Ok(&self.bytes[range])
+    }
+
+    /// Mutably borrow `len` bytes starting at `address`, e.g. to fill a buffer wasm will read
+    pub fn as_mut_slice(&mut self, address: u32, len: u32) -> Result<&mut [u8], Error> {
+        let range = self.checked_range(address, len)?;
+        Ok(&mut self.bytes[range])
+    }
+
+    fn checked_range(&self, address: u32, len: u32) -> Result<core::ops::Range<usize>, Error> {
+        let end = address as u64 + len as u64;
+        if end > self.bytes.len() as u64 {
+            return Err(Error::Trap(Trap::MemoryOutOfBounds));
+        }
+        Ok(address as usize..end as usize)
+    }
+
+    /// Copy raw bytes into memory starting at `address`, e.g. for data segments
+    pub fn write_bytes(&mut self, address: u64, data: &[u8]) -> Option<()> {
+        let end = address.checked_add(data.len() as u64)?;
+        if end > self.size_bytes()? {
+            return None;
+        }
+        self.bytes[address as usize..end as usize].copy_from_slice(data);
+        Some(())
+    }
+
+    pub fn read(&self, result_type: PrimitiveType, bitwidth: u8, address: u64) -> Option<Value> {
+        let bytes_to_read = (bitwidth / 8) as u64;
+
+        // Check for out of bounds access
+        if address.checked_add(bytes_to_read)? > self.size_bytes()? {
+            return None;
+        }
+
+        let mut result = 0_u64;
+
+        for i in (address..(address + bytes_to_read)).rev() {
+            result <<= 8;
+            result += self.bytes[i as usize] as u64;
+        }
+
+        debug!(
+            "Read from address 0x{:x} with bitwidth {} and value 0x{:x}",
+            address, bitwidth, result
+        );
+        Some(Value::from_bits(result_type, result))
+    }
+}
+
+#[derive(Default, Clone, PartialEq)]
 pub struct FunctionType {
     pub params: Vec<PrimitiveType>,
     pub returns: Vec<PrimitiveType>,
@@ -438,11 +1471,37 @@ impl FunctionType {
         self.params.len()
     }
 
-    pub fn params_iter(&self) -> std::slice::Iter<PrimitiveType> {
+    pub fn params_iter(&self) -> core::slice::Iter<'_, PrimitiveType> {
         self.params.iter()
     }
 }
 
+/// Debug names parsed from the "name" custom section
+#[derive(Clone, Default)]
+pub struct Names {
+    pub module: Option<String>,
+    /// Function index to function name
+    pub functions: BTreeMap<usize, String>,
+    /// Function index to a map of local index to local name
+    pub locals: BTreeMap<usize, BTreeMap<usize, String>>,
+}
+
+/// Counts and sizes read from a module by `Module::inspect`, without decoding its code
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ModuleSummary {
+    pub num_types: usize,
+    /// Imported and defined functions
+    pub num_functions: usize,
+    pub num_imports: usize,
+    pub num_exports: usize,
+    /// (min, max) pages of each imported and defined memory. The max is `u32::MAX` when the
+    /// memory doesn't declare one.
+    pub memory_limits: Vec<(u32, u32)>,
+    /// (section id, content length in bytes) of each section, in the order they appear
+    pub section_sizes: Vec<(u8, usize)>,
+}
+
+#[derive(Clone)]
 pub enum Export {
     Function(usize),
     Table(usize),
@@ -450,14 +1509,94 @@ pub enum Export {
     Global(usize),
 }
 
-#[derive(Default)]
+impl Export {
+    pub fn kind(&self) -> ExportKind {
+        match self {
+            Export::Function(_) => ExportKind::Function,
+            Export::Table(_) => ExportKind::Table,
+            Export::Memory(_) => ExportKind::Memory,
+            Export::Global(_) => ExportKind::Global,
+        }
+    }
+}
+
+/// What kind of item an export refers to, without its index
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ExportKind {
+    Function,
+    Table,
+    Memory,
+    Global,
+}
+
+/// An active element segment, copied into a table when the module is instantiated
+#[derive(Clone)]
+pub struct ActiveElementSegment {
+    pub table_index: usize,
+    pub offset: u64,
+    pub elements: Vec<Value>,
+}
+
+/// An active data segment, copied into a memory when the module is instantiated
+#[derive(Clone)]
+pub struct ActiveDataSegment {
+    pub memory_index: usize,
+    pub offset: u64,
+    pub bytes: Vec<u8>,
+}
+
+#[derive(Clone)]
+pub enum ImportDescriptor {
+    Function(usize),
+    Table,
+    Memory,
+    Global(PrimitiveType, bool),
+}
+
+#[derive(Clone)]
+pub struct Import {
+    pub module: String,
+    pub name: String,
+    pub descriptor: ImportDescriptor,
+}
+
+/// What a module held before `instantiate` copied its segments in, for `Module::reset`
+#[derive(Clone)]
+struct InitialState {
+    memory_pages: Vec<u32>,
+    /// What each memory held before the data segments went in, e.g. from `set_memory_image`,
+    /// without its trailing zeros
+    memory_images: Vec<Vec<u8>>,
+    globals: Vec<Global>,
+    tables: Vec<Table>,
+    elements: Vec<Vec<Value>>,
+    data: Vec<Vec<u8>>,
+}
+
+/// Values supplied by the host for imported globals, keyed by (module, name)
+pub type ImportedGlobals = BTreeMap<(String, String), Value>;
+
+/// A loaded module along with its state. A clone is fully independent, so a freshly parsed
+/// module can be cloned for each run instead of parsing it again. Modules its imports are
+/// linked to are the one exception, and stay shared.
+#[derive(Clone, Default)]
 pub struct Module {
-    function_types: Vec<FunctionType>,
     functions: Vec<Function>,
-    exports: HashMap<String, Export>,
-    table: Table,
-    memory: Memory,
-    globals: Vec<Value>,
+    exports: BTreeMap<String, Export>,
+    imports: Vec<Import>,
+    ctx: ExecutionContext,
+    start: Option<usize>,
+    active_elements: Vec<ActiveElementSegment>,
+    active_data: Vec<ActiveDataSegment>,
+    instantiated: bool,
+    /// Set by `instantiate`
+    initial_state: Option<InitialState>,
+    names: Option<Names>,
+    skipped_sections: Vec<(u8, Vec<u8>)>,
+    custom_sections: Vec<(String, Vec<u8>)>,
+    disassembly: Vec<(usize, String)>,
+    /// Number of data segments declared by the data count section, if there is one
+    data_count: Option<u32>,
 }
 
 impl Module {
@@ -465,47 +1604,1456 @@ impl Module {
         Self::default()
     }
 
-    pub fn call(&mut self, function_name: &str, args: Vec<Value>) -> Result<Value, Error> {
-        let function_index = match self.exports.get(function_name) {
-            Some(Export::Function(n)) => *n,
-            _ => return Err(Error::Misc("On module call, given name is not a function")),
-        };
-        let function = match self.functions.get(function_index) {
-            Some(n) => n,
-            None => {
-                return Err(Error::Misc(
-                    "Function index given by export section is not valid",
-                ))
-            }
-        };
-        function.call(&self.functions, &mut self.memory, args)
+    /// Read a module's counts and section sizes without building it. Function bodies are
+    /// skipped rather than decoded, so this is much cheaper than a full parse.
+    pub fn inspect(bytes: &[u8]) -> Result<ModuleSummary, Error> {
+        crate::parser::inspect_wasm_bytes(bytes)
     }
 
-    pub fn add_function_type(&mut self, ft: FunctionType) {
-        self.function_types.push(ft);
+    /// Call an exported function, giving its results in order. The arguments must match the
+    /// function's params in number and type.
+    pub fn call(&mut self, function_name: &str, args: Vec<Value>) -> Result<Vec<Value>, Error> {
+        let function = &self.functions[self.exported_function_index(function_name)?];
+        let params = &function.signature().params;
+        if args.len() != params.len() || args.iter().zip(params).any(|(a, t)| a.get_type() != *t) {
+            return Err(Error::Misc("Arguments don't match the function's params"));
+        }
+        function.call(&self.functions, &mut self.ctx, args)
     }
 
-    pub fn get_function_type(&self, i: usize) -> FunctionType {
-        self.function_types[i].clone()
+    /// Params and results of an exported function
+    pub fn function_signature(&self, function_name: &str) -> Result<&FunctionType, Error> {
+        let function_index = self.exported_function_index(function_name)?;
+        Ok(self.functions[function_index].signature())
     }
 
-    pub fn add_function(&mut self, f: Function) {
-        self.functions.push(f);
+    /// Index of an exported function, checked to be in range
+    fn exported_function_index(&self, function_name: &str) -> Result<usize, Error> {
+        match self.exports.get(function_name) {
+            Some(Export::Function(n)) if *n < self.functions.len() => Ok(*n),
+            Some(Export::Function(_)) => Err(Error::Misc(
+                "Function index given by export section is not valid",
+            )),
+            _ => Err(Error::Misc("On module call, given name is not a function")),
+        }
     }
 
-    pub fn add_memory(&mut self, m: Memory) {
-        self.memory = m;
+    /// Same as `call`, but also counts how many times each instruction was executed
+    pub fn call_profiled(
+        &mut self,
+        function_name: &str,
+        args: Vec<Value>,
+    ) -> Result<(Vec<Value>, ProfileCounters), Error> {
+        self.ctx.profile = Some(ProfileCounters::new());
+        let result = self.call(function_name, args);
+        let profile = self.ctx.profile.take().unwrap_or_default();
+        Ok((result?, profile))
     }
 
-    pub fn add_export(&mut self, name: String, export: Export) -> Result<(), Error> {
-        if self.exports.contains_key(&name) {
-            return Err(Error::UnexpectedData("Expected a unique export name"));
-        }
-        self.exports.insert(name, export);
-        Ok(())
+    /// Same as `call`, but a function returning with the wrong number of values on its stack
+    /// gives `Error::FinalStack` with the whole stack, bottom first, instead of a bare
+    /// `Error::StackViolation`. Meant for debugging malformed function bodies.
+    pub fn call_debug(
+        &mut self,
+        function_name: &str,
+        args: Vec<Value>,
+    ) -> Result<Vec<Value>, Error> {
+        self.ctx.debug_stack = true;
+        let result = self.call(function_name, args);
+        self.ctx.debug_stack = false;
+        result
     }
 
-    pub fn get_mut_function(&mut self, i: usize) -> &mut Function {
-        &mut self.functions[i]
+    /// Same as `call`, but with the given options in effect for the duration of the call
+    pub fn call_with_config(
+        &mut self,
+        function_name: &str,
+        args: Vec<Value>,
+        config: ExecutionConfig,
+    ) -> Result<Vec<Value>, Error> {
+        let previous = core::mem::replace(&mut self.ctx.config, config);
+        let result = self.call(function_name, args);
+        self.ctx.config = previous;
+        result
+    }
+
+    /// Same as `call`, but traps with `Trap::Timeout` if the call is still running at `deadline`.
+    /// The clock is read every `DEFAULT_DEADLINE_CHECK_INTERVAL` instructions unless
+    /// `set_deadline_check_interval` says otherwise.
+    #[cfg(feature = "std")]
+    pub fn call_with_deadline(
+        &mut self,
+        function_name: &str,
+        args: Vec<Value>,
+        deadline: std::time::Instant,
+    ) -> Result<Vec<Value>, Error> {
+        self.ctx.deadline = Some(deadline);
+        self.ctx.deadline_countdown = 0;
+        let result = self.call(function_name, args);
+        self.ctx.deadline = None;
+        result
+    }
+
+    /// How many instructions run between checks of a deadline. Checking more often stops
+    /// closer to the deadline but slows execution down.
+    #[cfg(feature = "std")]
+    pub fn set_deadline_check_interval(&mut self, instructions: u32) {
+        self.ctx.deadline_check_interval = Some(instructions.max(1));
+    }
+
+    /// Get the module ready to be called, in the order the spec requires. Globals already
+    /// hold their initial values once the module is loaded, so this copies the active element
+    /// segments into the tables, then the active data segments into memory, and finally runs
+    /// the start function. A segment that doesn't fit traps, leaving earlier ones in place.
+    pub fn instantiate(&mut self) -> Result<(), Error> {
+        if self.instantiated {
+            return Err(Error::Misc("Module is already instantiated"));
+        }
+        // Only a `Linker` can resolve imported functions
+        if let Some((module, field)) = self
+            .functions
+            .iter()
+            .filter(|f| f.linked.is_none())
+            .find_map(|f| f.import())
+        {
+            return Err(Error::UnresolvedImport {
+                module: module.to_string(),
+                field: field.to_string(),
+            });
+        }
+        self.instantiated = true;
+        self.initial_state = Some(InitialState {
+            memory_pages: self.ctx.memories.iter().map(|m| m.size_pages()).collect(),
+            memory_images: self.ctx.memories.iter().map(Memory::image).collect(),
+            globals: self.ctx.globals.clone(),
+            tables: self.ctx.tables.clone(),
+            elements: self.ctx.elements.clone(),
+            data: self.ctx.data.clone(),
+        });
+        self.initialize()
+    }
+
+    /// Put memory, globals, tables and the passive segments back the way they were before
+    /// `instantiate`, then copy the active segments in and run the start function again. This
+    /// is much cheaper than parsing the module again to run another input. Memories start out
+    /// at the size they had when the module was instantiated, holding what they held just
+    /// before it (such as a memory image) and then the data segments.
+    pub fn reset(&mut self) -> Result<(), Error> {
+        let initial = match &self.initial_state {
+            Some(s) => s,
+            None => return Err(Error::Misc("Module is not instantiated")),
+        };
+        let sizes = initial.memory_pages.iter().zip(&initial.memory_images);
+        for (memory, (pages, image)) in self.ctx.memories.iter_mut().zip(sizes) {
+            memory.reset(*pages, image);
+        }
+        self.ctx.globals.clone_from(&initial.globals);
+        self.ctx.tables.clone_from(&initial.tables);
+        self.ctx.elements.clone_from(&initial.elements);
+        self.ctx.data.clone_from(&initial.data);
+        self.initialize()
+    }
+
+    /// Copy in the active segments and run the start function
+    fn initialize(&mut self) -> Result<(), Error> {
+        for segment in &self.active_elements {
+            let table = self.ctx.table_mut(segment.table_index)?;
+            if table.init(segment.offset, &segment.elements).is_none() {
+                return Err(Error::Trap(Trap::TableOutOfBounds));
+            }
+        }
+        for segment in &self.active_data {
+            let memory = self.ctx.memory_mut(segment.memory_index)?;
+            if memory.write_bytes(segment.offset, &segment.bytes).is_none() {
+                return Err(Error::Trap(Trap::MemoryOutOfBounds));
+            }
+        }
+        self.run_start()
+    }
+
+    /// Take the messages recorded by calls made with `Verbosity::Trace`, oldest first
+    pub fn take_trace(&mut self) -> Vec<String> {
+        core::mem::take(&mut self.ctx.trace)
+    }
+
+    /// Run the start function, if the module declares one
+    pub fn run_start(&mut self) -> Result<(), Error> {
+        let function_index = match self.start {
+            Some(n) => n,
+            None => return Ok(()),
+        };
+        let function = match self.functions.get(function_index) {
+            Some(n) => n,
+            None => return Err(Error::Misc("Start function index is not valid")),
+        };
+        function.call(&self.functions, &mut self.ctx, Vec::new())?;
+        Ok(())
+    }
+
+    pub fn add_function_type(&mut self, ft: FunctionType) {
+        self.ctx.function_types.push(ft);
+    }
+
+    pub fn function_types(&self) -> &[FunctionType] {
+        &self.ctx.function_types
+    }
+
+    pub fn get_function_type(&self, i: usize) -> Option<FunctionType> {
+        self.ctx.function_types.get(i).cloned()
+    }
+
+    pub fn add_function(&mut self, f: Function) {
+        self.functions.push(f);
+    }
+
+    /// Add a linear memory, which gets the next memory index
+    pub fn add_memory(&mut self, m: Memory) {
+        self.ctx.memories.push(m);
+    }
+
+    /// One of the module's linear memories, e.g. for the host to read what a function stored
+    pub fn memory(&self, index: usize) -> Option<&Memory> {
+        self.ctx.memories.get(index)
+    }
+
+    pub fn num_memories(&self) -> usize {
+        self.ctx.memories.len()
+    }
+
+    pub fn memory_mut(&mut self, index: usize) -> Option<&mut Memory> {
+        self.ctx.memories.get_mut(index)
+    }
+
+    /// Grow memory 0 to at least `pages` pages and copy `bytes` into it from address 0, e.g. to
+    /// carry on from the memory of an earlier run. Fails without changing the memory if it may
+    /// not grow that far or `bytes` doesn't fit.
+    pub fn set_memory_image(&mut self, pages: u32, bytes: &[u8]) -> Result<(), Error> {
+        let current_pages = self.ctx.memory_mut(0)?.size_pages();
+        let pages = pages.max(current_pages);
+        match pages_to_bytes(pages) {
+            Some(n) if bytes.len() as u64 <= n => (),
+            _ => return Err(Error::Misc("Memory image is larger than the memory")),
+        }
+        if self.ctx.grow_memory(0, pages - current_pages)?.is_none() {
+            return Err(Error::Misc("Memory can't grow to the size of the image"));
+        }
+        match self.ctx.memory_mut(0)?.write_bytes(0, bytes) {
+            Some(()) => Ok(()),
+            None => Err(Error::Trap(Trap::MemoryOutOfBounds)),
+        }
+    }
+
+    /// Keep every memory at or below `max_pages`, even if the module allows it to grow further.
+    /// Fails if a memory is already larger than that.
+    pub fn set_memory_limit(&mut self, max_pages: u32) -> Result<(), Error> {
+        if self.ctx.memories.iter().any(|m| m.size_pages() > max_pages) {
+            return Err(Error::Misc("A memory is already larger than the limit"));
+        }
+        self.ctx.memory_limit_pages = Some(max_pages);
+        Ok(())
+    }
+
+    /// Add a table, which gets the next table index
+    pub fn add_table(&mut self, t: Table) {
+        self.ctx.tables.push(t);
+    }
+
+    pub fn table(&self, index: usize) -> Option<&Table> {
+        self.ctx.tables.get(index)
+    }
+
+    pub fn num_tables(&self) -> usize {
+        self.ctx.tables.len()
+    }
+
+    pub fn table_mut(&mut self, index: usize) -> Option<&mut Table> {
+        self.ctx.tables.get_mut(index)
+    }
+
+    /// Add an element segment holding references of type `element_type`, which gets the next
+    /// element index
+    pub fn add_element_segment(&mut self, element_type: PrimitiveType, elements: Vec<Value>) {
+        self.ctx.elements.push(elements);
+        self.ctx.element_types.push(element_type);
+    }
+
+    /// Add an element segment to be copied into a table by `instantiate`
+    pub fn add_active_element_segment(&mut self, segment: ActiveElementSegment) {
+        self.active_elements.push(segment);
+    }
+
+    /// Add a data segment to be copied into memory by `instantiate`
+    pub fn add_active_data_segment(&mut self, segment: ActiveDataSegment) {
+        self.active_data.push(segment);
+    }
+
+    pub fn element_segment(&self, i: usize) -> Option<&[Value]> {
+        self.ctx.elements.get(i).map(|e| e.as_slice())
+    }
+
+    /// Reference type of each element segment, by element index
+    pub fn element_types(&self) -> &[PrimitiveType] {
+        &self.ctx.element_types
+    }
+
+    pub fn add_data_segment(&mut self, bytes: Vec<u8>) {
+        self.ctx.data.push(bytes);
+    }
+
+    pub fn data_segment(&self, i: usize) -> Option<&[u8]> {
+        self.ctx.data.get(i).map(|d| d.as_slice())
+    }
+
+    pub fn set_data_count(&mut self, count: u32) {
+        self.data_count = Some(count);
+    }
+
+    /// Number of data segments declared ahead of the code, which `memory.init` and
+    /// `data.drop` need in order to be validated
+    pub fn data_count(&self) -> Option<u32> {
+        self.data_count
+    }
+
+    pub fn add_tag(&mut self, t: FunctionType) {
+        self.ctx.tags.push(t);
+    }
+
+    pub fn add_global(&mut self, g: Global) {
+        self.ctx.globals.push(g);
+    }
+
+    pub fn globals(&self) -> &[Global] {
+        &self.ctx.globals
+    }
+
+    fn exported_global_index(&self, name: &str) -> Result<usize, Error> {
+        match self.exports.get(name) {
+            Some(Export::Global(n)) if *n < self.ctx.globals.len() => Ok(*n),
+            Some(Export::Global(_)) => {
+                Err(Error::Misc("Global index given by export is not valid"))
+            }
+            _ => Err(Error::Misc("Given name is not an exported global")),
+        }
+    }
+
+    /// Current value of the global exported as `name`
+    pub fn get_global(&self, name: &str) -> Result<Value, Error> {
+        let index = self.exported_global_index(name)?;
+        Ok(self.ctx.globals[index].value)
+    }
+
+    /// Set the global exported as `name`, which must be mutable and of the same type as `v`
+    pub fn set_global(&mut self, name: &str, v: Value) -> Result<(), Error> {
+        let index = self.exported_global_index(name)?;
+        let global = &mut self.ctx.globals[index];
+        if !global.mutable {
+            return Err(Error::Misc("Cannot set an immutable global"));
+        }
+        if global.value.t != v.t {
+            return Err(Error::Misc("Global type mismatch"));
+        }
+        global.value = v;
+        Ok(())
+    }
+
+    pub fn add_import(&mut self, import: Import) {
+        self.imports.push(import);
+    }
+
+    pub fn imports(&self) -> &[Import] {
+        &self.imports
+    }
+
+    pub fn add_export(&mut self, name: String, export: Export) -> Result<(), Error> {
+        if self.exports.contains_key(&name) {
+            return Err(Error::UnexpectedData("Expected a unique export name"));
+        }
+        self.exports.insert(name, export);
+        Ok(())
+    }
+
+    /// The kind of the export with the given name, or None if there is no such export
+    pub fn export_kind(&self, name: &str) -> Option<ExportKind> {
+        self.exports.get(name).map(Export::kind)
+    }
+
+    pub fn set_start(&mut self, function_index: usize) {
+        self.start = Some(function_index);
+    }
+
+    pub fn names(&self) -> Option<&Names> {
+        self.names.as_ref()
+    }
+
+    /// Attach debug names to the module, also naming its functions for traces
+    pub fn set_names(&mut self, names: Names) {
+        for (i, name) in &names.functions {
+            if let Some(function) = self.functions.get_mut(*i) {
+                function.name = Some(name.clone());
+            }
+        }
+        self.names = Some(names);
+    }
+
+    pub fn add_skipped_section(&mut self, section_type: u8, content: Vec<u8>) {
+        self.skipped_sections.push((section_type, content));
+    }
+
+    /// The id and raw contents of each section the parser doesn't implement
+    pub fn skipped_sections(&self) -> &[(u8, Vec<u8>)] {
+        &self.skipped_sections
+    }
+
+    pub fn add_disassembly(&mut self, instructions: Vec<(usize, String)>) {
+        self.disassembly.extend(instructions);
+    }
+
+    /// The offset in the module binary and mnemonic of each instruction in the code section,
+    /// in binary order. Block terminators (`end`, `catch`, `catch_all`) are included
+    pub fn disassemble(&self) -> Vec<(usize, String)> {
+        self.disassembly.clone()
+    }
+
+    /// The debug name of the function with the given index, from the name section
+    pub fn function_name(&self, i: usize) -> Option<&str> {
+        self.functions.get(i).and_then(|f| f.name())
+    }
+
+    pub fn add_custom_section(&mut self, name: String, content: Vec<u8>) {
+        self.custom_sections.push((name, content));
+    }
+
+    /// The name and contents, after the name, of each custom section the parser doesn't use
+    pub fn custom_sections(&self) -> &[(String, Vec<u8>)] {
+        &self.custom_sections
+    }
+
+    pub fn get_function(&self, i: usize) -> Option<&Function> {
+        self.functions.get(i)
+    }
+
+    pub fn get_mut_function(&mut self, i: usize) -> Option<&mut Function> {
+        self.functions.get_mut(i)
+    }
+
+    /// Types on the operand stack just before instruction `inst` of function `func` runs, worked
+    /// out from the types each earlier instruction takes and gives. Only the instructions of the
+    /// function body itself are counted, so a block and everything in it is one instruction.
+    /// Fails if an earlier instruction has operands of the wrong type or leaves the stack
+    /// unknown, as `br` does.
+    pub fn stack_trace_types(&self, func: usize, inst: usize) -> Result<Vec<PrimitiveType>, Error> {
+        let function = match self.functions.get(func) {
+            Some(f) if f.import.is_none() => f,
+            Some(_) => return Err(Error::Misc("Imported functions have no body")),
+            None => return Err(Error::Misc("Function index is not valid")),
+        };
+        let instructions = match function.instructions.get(..inst) {
+            Some(i) => i,
+            None => return Err(Error::Misc("Instruction index is not valid")),
+        };
+        let mut types = TypeStack::new(self, function);
+        for instruction in instructions {
+            instruction.check_types(&mut types)?;
+        }
+        Ok(types.types)
+    }
+
+    /// Number of functions, imported and defined
+    pub fn num_functions(&self) -> usize {
+        self.functions.len()
+    }
+
+    /// Imported functions come first in the function index space, ahead of those with bodies
+    pub fn num_imported_functions(&self) -> usize {
+        self.functions
+            .iter()
+            .take_while(|f| f.import.is_some())
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_wasm_bytes;
+    use crate::test_util::*;
+
+    #[test]
+    fn trap_in_start_function_fails_instantiation() {
+        let bytes = wasm(r#"(module (func unreachable) (start 0))"#);
+        let mut module = parse_wasm_bytes(&bytes).unwrap();
+        assert_eq!(trap(module.instantiate()), Trap::Unreachable);
+    }
+
+    #[test]
+    fn division_by_zero_is_returned_as_a_trap() {
+        let text = r#"(module (func (export "f") (result i32) i32.const 1 i32.const 0 i32.div_s))"#;
+        let result = run(text, "f", &[]);
+        assert!(matches!(
+            result.as_ref().map_err(Error::root),
+            Err(Error::Trap(Trap::DivideByZero))
+        ));
+    }
+
+    #[test]
+    fn profile_counts_instructions_in_a_loop() {
+        let mut module = instantiate(
+            r#"(module
+                (func (export "f") (local i32)
+                    (loop
+                        local.get 0
+                        i32.const 1
+                        i32.add
+                        local.tee 0
+                        i32.const 100
+                        i32.lt_u
+                        br_if 0)))"#,
+        );
+        let (_, profile) = module.call_profiled("f", Vec::new()).unwrap();
+        assert_eq!(profile.get("i32.add"), Some(&100));
+        assert_eq!(profile.get("br_if"), Some(&100));
+    }
+
+    #[test]
+    fn grown_pages_read_as_zero_after_a_shrink() {
+        let mut memory = Memory::new(1, 2);
+        let snapshot = memory.clone();
+        assert_eq!(memory.grow(1), Some(1));
+        memory.write(0xff, 8, PAGE_SIZE + 5).unwrap();
+
+        // Shrinking keeps the old allocation, whose bytes must not leak back in
+        memory.reset(1, &[]);
+        assert_eq!(memory.grow(1), Some(1));
+        let read = memory.read(PrimitiveType::I32, 8, PAGE_SIZE + 5).unwrap();
+        assert_eq!(read.unpack(), TypedValue::I32(0));
+
+        // The same goes for restoring a copy taken before the memory grew
+        memory.write(0xff, 8, PAGE_SIZE + 5).unwrap();
+        memory = snapshot;
+        assert_eq!(memory.grow(1), Some(1));
+        let read = memory.read(PrimitiveType::I32, 8, PAGE_SIZE + 5).unwrap();
+        assert_eq!(read.unpack(), TypedValue::I32(0));
+    }
+
+    #[test]
+    fn effective_address_overflow_is_caught() {
+        assert_eq!(
+            Memory::effective_addr(0xFFFF_FFFF, 0xFFFF_FFFF),
+            0x1_FFFF_FFFE
+        );
+        assert_eq!(Memory::effective_addr(0, 0), 0);
+
+        // The sum fits in a u64, but is far past the end of any 32-bit memory
+        let text = r#"(module
+            (memory 1)
+            (func (export "load") (param i32) (result i32)
+                local.get 0
+                i32.load offset=0xFFFFFFFF)
+            (func (export "store") (param i32)
+                local.get 0
+                i32.const 1
+                i32.store offset=0xFFFFFFFF))"#;
+        let mut module = instantiate(text);
+        let base = Value::i32(-1);
+        assert_eq!(
+            trap(call(&mut module, "load", &[base])),
+            Trap::MemoryOutOfBounds
+        );
+        assert_eq!(
+            trap(call(&mut module, "store", &[base])),
+            Trap::MemoryOutOfBounds
+        );
+    }
+
+    #[test]
+    fn parsed_function_reports_its_arity() {
+        let bytes = wasm(r#"(module (func (param i32 f64) (result i64) i64.const 0))"#);
+        let module = parse_wasm_bytes(&bytes).unwrap();
+        let function = module.get_function(0).unwrap();
+        assert_eq!(function.num_params(), 2);
+        assert_eq!(function.num_results(), 1);
+        let signature = function.signature();
+        assert!(signature.params == [PrimitiveType::I32, PrimitiveType::F64]);
+        assert!(signature.returns == [PrimitiveType::I64]);
+    }
+
+    #[test]
+    fn float_bits_round_trip_exactly() {
+        let negative_zero = Value::from_bits(PrimitiveType::F32, 0x8000_0000);
+        assert_eq!(negative_zero.to_bits(), 0x8000_0000);
+        assert!(negative_zero.as_f32_unchecked().is_sign_negative());
+
+        // A quiet NaN with a payload, and a signaling one
+        for bits in [0x7fc0_1234, 0x7f80_0001] {
+            let nan = Value::from_bits(PrimitiveType::F32, bits);
+            assert!(nan.as_f32_unchecked().is_nan());
+            assert_eq!(nan.as_f32_unchecked().to_bits() as u64, bits);
+            assert_eq!(nan.to_bits(), bits);
+        }
+
+        let negative_zero = Value::from_bits(PrimitiveType::F64, 0x8000_0000_0000_0000);
+        assert_eq!(negative_zero.to_bits(), 0x8000_0000_0000_0000);
+        assert!(negative_zero.as_f64_unchecked().is_sign_negative());
+        let nan = Value::from_bits(PrimitiveType::F64, 0x7ff8_0000_dead_beef);
+        assert_eq!(nan.as_f64_unchecked().to_bits(), 0x7ff8_0000_dead_beef);
+        assert_eq!(nan.to_bits(), 0x7ff8_0000_dead_beef);
+    }
+
+    #[test]
+    fn host_sees_stores_through_the_memory_accessors() {
+        let mut module = instantiate(
+            r#"(module
+                (memory (export "memory") 1)
+                (func (export "store") (param i32 i32)
+                    local.get 0
+                    local.get 1
+                    i32.store)
+                (func (export "load") (param i32) (result i32)
+                    local.get 0
+                    i32.load))"#,
+        );
+        call(
+            &mut module,
+            "store",
+            &[Value::i32(16), Value::i32(0x0403_0201)],
+        )
+        .unwrap();
+        let memory = module.memory(0).unwrap();
+        assert_eq!(memory.as_slice(16, 4).unwrap(), [1, 2, 3, 4]);
+
+        let memory = module.memory_mut(0).unwrap();
+        memory
+            .as_mut_slice(32, 4)
+            .unwrap()
+            .copy_from_slice(&[5, 6, 7, 8]);
+        assert_eq!(
+            call(&mut module, "load", &[Value::i32(32)]).unwrap(),
+            [TypedValue::I32(0x0807_0605)]
+        );
+        assert!(module.memory(1).is_none());
+    }
+
+    #[test]
+    fn call_indirect_traps() {
+        let mut module = instantiate(
+            r#"(module
+                (type $unary (func (param i32) (result i32)))
+                (type $nullary (func (result i32)))
+                (table 3 funcref)
+                (elem (i32.const 0) $answer $double)
+                (func $answer (result i32) i32.const 42)
+                (func $double (param i32) (result i32) local.get 0 i32.const 2 i32.mul)
+                (func (export "nullary") (param i32) (result i32)
+                    local.get 0
+                    call_indirect (type $nullary)))"#,
+        );
+        let mut call_slot = |slot| call(&mut module, "nullary", &[Value::i32(slot)]);
+        assert_eq!(call_slot(0).unwrap(), [TypedValue::I32(42)]);
+        assert_eq!(trap(call_slot(1)), Trap::IndirectCallTypeMismatch);
+        // Slot 2 was never initialized and slot 3 is past the end of the table
+        assert_eq!(trap(call_slot(2)), Trap::UndefinedElement);
+        assert_eq!(trap(call_slot(3)), Trap::UndefinedElement);
+        assert_eq!(trap(call_slot(-1)), Trap::UndefinedElement);
+    }
+
+    #[test]
+    fn memory_limit_caps_growth_below_the_declared_maximum() {
+        let mut module = instantiate(
+            r#"(module
+                (memory 1 10)
+                (func (export "grow") (param i32) (result i32)
+                    local.get 0
+                    memory.grow))"#,
+        );
+        module.set_memory_limit(3).unwrap();
+        let mut grow = |delta| call(&mut module, "grow", &[Value::i32(delta)]).unwrap();
+        assert_eq!(grow(1), [TypedValue::I32(1)]);
+        assert_eq!(grow(2), [TypedValue::I32(-1)]);
+        assert_eq!(grow(1), [TypedValue::I32(2)]);
+        assert_eq!(grow(1), [TypedValue::I32(-1)]);
+        assert_eq!(module.memory(0).unwrap().size_pages(), 3);
+
+        // Loading a memory image is held to the same limit
+        assert!(module.set_memory_image(4, &[]).is_err());
+        assert!(module.set_memory_limit(2).is_err());
+    }
+
+    #[test]
+    fn deterministic_mode_canonicalizes_nans() {
+        let mut module = instantiate(
+            r#"(module
+                (func (export "add") (param f32 f32) (result f32)
+                    (f32.add (local.get 0) (local.get 1)))
+                (func (export "sqrt") (param f64) (result f64)
+                    (f64.sqrt (local.get 0)))
+                (func (export "promote") (param f32) (result f64)
+                    (f64.promote_f32 (local.get 0)))
+                (func (export "neg") (param f32) (result f32)
+                    (f32.neg (local.get 0))))"#,
+        );
+        let config = ExecutionConfig {
+            deterministic: true,
+            ..ExecutionConfig::default()
+        };
+        let mut bits = |name, args: &[Value]| {
+            let results = module
+                .call_with_config(name, args.to_vec(), config)
+                .unwrap();
+            results[0].to_bits()
+        };
+        let payload_nan = Value::from_bits(PrimitiveType::F32, 0xffa0_0001);
+        let negative_nan = Value::from_bits(PrimitiveType::F32, 0xffc0_0000);
+        for nan in [payload_nan, negative_nan] {
+            assert_eq!(bits("add", &[nan, Value::f32(1.0)]), 0x7fc0_0000);
+            assert_eq!(bits("add", &[Value::f32(1.0), nan]), 0x7fc0_0000);
+            assert_eq!(bits("promote", &[nan]), 0x7ff8_0000_0000_0000);
+        }
+        assert_eq!(
+            bits(
+                "add",
+                &[Value::f32(f32::INFINITY), Value::f32(f32::NEG_INFINITY)]
+            ),
+            0x7fc0_0000
+        );
+        assert_eq!(bits("sqrt", &[Value::f64(-1.0)]), 0x7ff8_0000_0000_0000);
+        // Sign operations only touch bits, so the payload survives them
+        assert_eq!(bits("neg", &[payload_nan]), 0x7fa0_0001);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn deadline_stops_an_infinite_loop() {
+        use std::time::{Duration, Instant};
+
+        let mut module = instantiate(r#"(module (func (export "spin") (loop (br 0))))"#);
+        module.set_deadline_check_interval(100);
+        let started = Instant::now();
+        let deadline = started + Duration::from_millis(20);
+        let result = module.call_with_deadline("spin", Vec::new(), deadline);
+        assert_eq!(trap(result), Trap::Timeout);
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        // The deadline only applies to that call
+        let text = r#"(module (func (export "f") (result i32) i32.const 1))"#;
+        let mut module = instantiate(text);
+        let past = Instant::now();
+        assert!(module.call_with_deadline("f", Vec::new(), past).is_err());
+        assert_eq!(call(&mut module, "f", &[]).unwrap(), [TypedValue::I32(1)]);
+    }
+
+    #[test]
+    fn results_convert_into_tuples() {
+        let mut module = instantiate(
+            r#"(module
+                (func (export "pair") (result i32 i64) i32.const -3 i64.const 1099511627776)
+                (func (export "none")))"#,
+        );
+        let pair = module.call("pair", Vec::new()).unwrap();
+        let (a, b): (i32, i64) = into_results(pair.clone()).unwrap();
+        assert_eq!((a, b), (-3, 1 << 40));
+        into_results::<()>(module.call("none", Vec::new()).unwrap()).unwrap();
+
+        assert!(matches!(
+            into_results::<(i32, f64)>(pair.clone()),
+            Err(Error::Misc(_))
+        ));
+        assert!(matches!(
+            into_results::<i32>(pair.clone()),
+            Err(Error::Misc(_))
+        ));
+        assert!(matches!(
+            into_results::<(i32, i64, i32)>(pair),
+            Err(Error::Misc(_))
+        ));
+    }
+
+    #[test]
+    fn cloned_modules_do_not_share_state() {
+        let mut original = instantiate(
+            r#"(module
+                (memory 1)
+                (global $count (mut i32) (i32.const 0))
+                (func (export "bump") (result i32)
+                    (i32.store8 (i32.const 0) (i32.const 9))
+                    (global.set $count (i32.add (global.get $count) (i32.const 1)))
+                    global.get $count))"#,
+        );
+        let mut copy = original.clone();
+        assert_eq!(call(&mut copy, "bump", &[]).unwrap(), [TypedValue::I32(1)]);
+        copy.memory_mut(0).unwrap().as_mut_slice(1, 1).unwrap()[0] = 5;
+
+        assert_eq!(original.memory(0).unwrap().as_slice(0, 2).unwrap(), [0, 0]);
+        assert_eq!(copy.memory(0).unwrap().as_slice(0, 2).unwrap(), [9, 5]);
+        assert_eq!(
+            call(&mut original, "bump", &[]).unwrap(),
+            [TypedValue::I32(1)]
+        );
+        assert_eq!(call(&mut copy, "bump", &[]).unwrap(), [TypedValue::I32(2)]);
+    }
+
+    #[test]
+    fn locals_are_fresh_for_every_call() {
+        let mut module = instantiate(
+            r#"(module
+                (func (export "count") (result i32) (local i32)
+                    (local.set 0 (i32.add (local.get 0) (i32.const 1)))
+                    local.get 0)
+                (func $sum (export "sum") (param i32) (result i32) (local i32)
+                    (local.set 1 (local.get 0))
+                    (if (result i32) (i32.eqz (local.get 0))
+                        (then i32.const 0)
+                        (else
+                            (call $sum (i32.sub (local.get 0) (i32.const 1)))
+                            local.get 1
+                            i32.add))))"#,
+        );
+        for _ in 0..2 {
+            assert_eq!(
+                call(&mut module, "count", &[]).unwrap(),
+                [TypedValue::I32(1)]
+            );
+        }
+        // Each level of the recursion keeps its own copy of local 1
+        let sum = call(&mut module, "sum", &[Value::i32(10)]).unwrap();
+        assert_eq!(sum, [TypedValue::I32(55)]);
+    }
+
+    #[test]
+    fn huge_page_counts_do_not_overflow() {
+        assert_eq!(pages_to_bytes(u32::MAX), Some(0xFFFF_FFFF_0000));
+        assert_eq!(pages_to_bytes(MAX_PAGES), Some(1 << 32));
+
+        let mut memory = Memory::new(1, u32::MAX);
+        assert_eq!(memory.grow(u32::MAX), None);
+        assert_eq!(memory.grow(MAX_PAGES), None);
+        assert_eq!(memory.size_pages(), 1);
+        assert_eq!(memory.size_bytes(), Some(PAGE_SIZE));
+        assert!(memory.read(PrimitiveType::I64, 64, u64::MAX - 3).is_none());
+        assert!(memory.write(1, 64, u64::MAX - 3).is_none());
+
+        let text = r#"(module
+            (memory 1)
+            (func (export "grow") (param i32) (result i32) (memory.grow (local.get 0))))"#;
+        let result = run(text, "grow", &[Value::i32(-1)]).unwrap();
+        assert_eq!(result, [TypedValue::I32(-1)]);
+    }
+
+    #[test]
+    fn host_reads_and_sets_exported_globals() {
+        let mut module = instantiate(
+            r#"(module
+                (global (export "heap") i32 (i32.const 1024))
+                (global $limit (export "limit") (mut i64) (i64.const 5))
+                (func (export "read_limit") (result i64) global.get $limit))"#,
+        );
+        assert_eq!(
+            module.get_global("heap").unwrap().unpack(),
+            TypedValue::I32(1024)
+        );
+        assert!(module.get_global("read_limit").is_err());
+        assert!(module.get_global("missing").is_err());
+
+        module.set_global("limit", Value::i64(7)).unwrap();
+        assert_eq!(
+            call(&mut module, "read_limit", &[]).unwrap(),
+            [TypedValue::I64(7)]
+        );
+        assert!(module.set_global("limit", Value::i32(7)).is_err());
+        assert!(matches!(
+            module.set_global("heap", Value::i32(0)),
+            Err(Error::Misc(_))
+        ));
+        assert_eq!(
+            module.get_global("heap").unwrap().unpack(),
+            TypedValue::I32(1024)
+        );
+    }
+
+    #[test]
+    fn integer_conversions_check_the_type() {
+        assert_eq!(u32::try_from(Value::i32(-1)).unwrap(), u32::MAX);
+        assert_eq!(u64::try_from(Value::i64(-1)).unwrap(), u64::MAX);
+        assert_eq!(i32::try_from(Value::i32(i32::MIN)).unwrap(), i32::MIN);
+        assert_eq!(i64::try_from(Value::i64(i64::MIN)).unwrap(), i64::MIN);
+
+        assert!(matches!(u32::try_from(Value::i64(1)), Err(Error::Misc(_))));
+        assert!(matches!(u64::try_from(Value::i32(1)), Err(Error::Misc(_))));
+        assert!(matches!(
+            i32::try_from(Value::f32(1.0)),
+            Err(Error::Misc(_))
+        ));
+        assert!(matches!(
+            i64::try_from(Value::f64(1.0)),
+            Err(Error::Misc(_))
+        ));
+    }
+
+    #[test]
+    fn pushing_past_the_stack_limit_traps() {
+        let mut stack = Stack::with_values(Vec::new(), 2);
+        stack.push_value(Value::i32(1)).unwrap();
+        stack.push_value(Value::i32(2)).unwrap();
+        assert_eq!(trap(stack.push_value(Value::i32(3))), Trap::StackOverflow);
+        assert_eq!(stack.height(), 2);
+
+        let mut module = instantiate(
+            r#"(module
+                (func (export "three") (result i32 i32 i32)
+                    i32.const 1 i32.const 2 i32.const 3))"#,
+        );
+        let config = |depth| ExecutionConfig {
+            max_stack_depth: Some(depth),
+            ..ExecutionConfig::default()
+        };
+        let result = module.call_with_config("three", Vec::new(), config(2));
+        assert_eq!(trap(result), Trap::StackOverflow);
+        assert!(module
+            .call_with_config("three", Vec::new(), config(3))
+            .is_ok());
+    }
+
+    #[test]
+    fn out_of_bounds_load_as_the_first_instruction_traps() {
+        let text = r#"(module
+            (memory 1)
+            (func (export "f") (param i32) (result i32)
+                (i32.load (local.get 0))))"#;
+        assert_eq!(
+            trap(run(text, "f", &[Value::i32(65536)])),
+            Trap::MemoryOutOfBounds
+        );
+
+        // The add after the load must not run on the value the load failed to push
+        let text = r#"(module
+            (memory 0)
+            (func (export "f") (result i32)
+                i32.const 0
+                i32.load
+                i32.const 1
+                i32.add))"#;
+        assert_eq!(trap(run(text, "f", &[])), Trap::MemoryOutOfBounds);
+    }
+
+    #[test]
+    fn segments_are_in_place_before_the_start_function_runs() {
+        let mut module = instantiate(
+            r#"(module
+                (memory 1)
+                (table 1 funcref)
+                (global $seen (mut i32) (i32.const 0))
+                (global $called (mut i32) (i32.const 0))
+                (data (i32.const 8) "\2a")
+                (elem (i32.const 0) $answer)
+                (func $answer (result i32) i32.const 7)
+                (func $start
+                    (global.set $seen (i32.load (i32.const 8)))
+                    (global.set $called (call_indirect (result i32) (i32.const 0))))
+                (start $start)
+                (func (export "seen") (result i32) global.get $seen)
+                (func (export "called") (result i32) global.get $called))"#,
+        );
+        assert_eq!(
+            call(&mut module, "seen", &[]).unwrap(),
+            [TypedValue::I32(42)]
+        );
+        assert_eq!(
+            call(&mut module, "called", &[]).unwrap(),
+            [TypedValue::I32(7)]
+        );
+        assert!(module.instantiate().is_err());
+    }
+
+    #[test]
+    fn bools_become_i32_zero_or_one() {
+        assert_eq!(i32::try_from(Value::from(true)).unwrap(), 1);
+        assert_eq!(i32::try_from(Value::from(false)).unwrap(), 0);
+        assert!(Value::from(true).get_type() == PrimitiveType::I32);
+    }
+
+    #[test]
+    fn void_functions_return_no_results() {
+        let mut module = instantiate(
+            r#"(module
+                (global $g (mut i32) (i32.const 0))
+                (func (export "empty"))
+                (func (export "set") (global.set $g (i32.const 3)))
+                (func (export "get") (result i32) global.get $g))"#,
+        );
+        assert!(module.call("empty", Vec::new()).unwrap().is_empty());
+        assert!(module.call("set", Vec::new()).unwrap().is_empty());
+        assert_eq!(call(&mut module, "get", &[]).unwrap(), [TypedValue::I32(3)]);
+    }
+
+    #[test]
+    fn trace_is_recorded_only_at_trace_level() {
+        let mut module = instantiate(
+            r#"(module
+                (func $double (param i32) (result i32) (i32.mul (local.get 0) (i32.const 2)))
+                (func (export "f") (result i32) (call $double (i32.const 21))))"#,
+        );
+        call(&mut module, "f", &[]).unwrap();
+        assert!(module.take_trace().is_empty());
+
+        let config = ExecutionConfig {
+            verbosity: Verbosity::Trace,
+            ..ExecutionConfig::default()
+        };
+        module.call_with_config("f", Vec::new(), config).unwrap();
+        let trace = module.take_trace();
+        assert!(trace.iter().any(|line| line.contains("Calling function")));
+        assert!(trace.iter().any(|line| line == "Pushed (i32:42)"));
+        assert!(module.take_trace().is_empty());
+    }
+
+    #[test]
+    fn memory_slices_are_bounds_checked_views() {
+        let mut module = instantiate(
+            r#"(module
+                (memory 1)
+                (func (export "fill") (param i32)
+                    (i32.store (local.get 0) (i32.const -1)))
+                (func (export "sum") (result i32)
+                    (i32.add (i32.load (i32.const 0)) (i32.load (i32.const 4)))))"#,
+        );
+        assert_eq!(module.memory(0).unwrap().as_slice(0, 4).unwrap(), [0; 4]);
+        call(&mut module, "fill", &[Value::i32(2)]).unwrap();
+        assert_eq!(
+            module.memory(0).unwrap().as_slice(0, 8).unwrap(),
+            [0, 0, 255, 255, 255, 255, 0, 0]
+        );
+
+        let view = module.memory_mut(0).unwrap().as_mut_slice(0, 8).unwrap();
+        view.copy_from_slice(&[1, 0, 0, 0, 2, 0, 0, 0]);
+        assert_eq!(call(&mut module, "sum", &[]).unwrap(), [TypedValue::I32(3)]);
+
+        let memory = module.memory(0).unwrap();
+        assert_eq!(memory.as_slice(65535, 1).unwrap().len(), 1);
+        assert_eq!(memory.as_slice(65536, 0).unwrap().len(), 0);
+        for (address, len) in [(65535, 2), (65536, 1), (u32::MAX, u32::MAX)] {
+            assert!(matches!(
+                memory.as_slice(address, len),
+                Err(Error::Trap(Trap::MemoryOutOfBounds))
+            ));
+        }
+    }
+
+    #[test]
+    fn unpack_gives_each_variant() {
+        assert_eq!(Value::i32(-7).unpack(), TypedValue::I32(-7));
+        assert_eq!(Value::i64(i64::MIN).unpack(), TypedValue::I64(i64::MIN));
+        assert_eq!(Value::f32(1.5).unpack(), TypedValue::F32(1.5));
+        assert_eq!(Value::f64(-0.25).unpack(), TypedValue::F64(-0.25));
+        assert_eq!(Value::func_ref(3).unpack(), TypedValue::FuncRef(Some(3)));
+        assert_eq!(
+            Value::null(PrimitiveType::ExternRef).unpack(),
+            TypedValue::ExternRef(None)
+        );
+    }
+
+    #[test]
+    fn narrow_stores_write_only_their_low_bytes() {
+        let mut module = instantiate(
+            r#"(module
+                (memory (export "memory") 1)
+                (data (i32.const 0) "\aa\aa\aa\aa\aa\aa\aa\aa\aa\aa\aa\aa\aa\aa\aa\aa")
+                (func (export "store")
+                    (i32.store8 (i32.const 0) (i32.const 0x1FF))
+                    (i32.store16 (i32.const 2) (i32.const 0x12345678))
+                    (i64.store8 (i32.const 5) (i64.const -1))
+                    (i64.store16 (i32.const 7) (i64.const 0x7FFF_0000_0000_BEEF))
+                    (i64.store32 (i32.const 10) (i64.const 0x0102_0304_0506_0708))))"#,
+        );
+        call(&mut module, "store", &[]).unwrap();
+        assert_eq!(
+            module.memory(0).unwrap().as_slice(0, 16).unwrap(),
+            [
+                0xFF, 0xAA, 0x78, 0x56, 0xAA, 0xFF, 0xAA, 0xEF, 0xBE, 0xAA, 0x08, 0x07, 0x06, 0x05,
+                0xAA, 0xAA
+            ]
+        );
+    }
+
+    #[test]
+    fn fetch_value_checks_the_offset() {
+        let mut stack = Stack::with_values(Vec::new(), DEFAULT_MAX_STACK_DEPTH);
+        assert!(matches!(stack.fetch_value(0), Err(Error::StackViolation)));
+        assert!(matches!(
+            stack.fetch_value(usize::MAX),
+            Err(Error::StackViolation)
+        ));
+
+        stack.push_value(Value::i32(1)).unwrap();
+        stack.push_value(Value::i32(2)).unwrap();
+        assert_eq!(stack.fetch_value(0).unwrap().unpack(), TypedValue::I32(2));
+        assert_eq!(stack.fetch_value(1).unwrap().unpack(), TypedValue::I32(1));
+        assert!(matches!(stack.fetch_value(2), Err(Error::StackViolation)));
+    }
+
+    #[test]
+    fn export_kind_tells_functions_from_memories() {
+        let module = instantiate(
+            r#"(module
+                (memory (export "memory") 1)
+                (table (export "table") 1 funcref)
+                (global (export "global") i32 (i32.const 0))
+                (func (export "main")))"#,
+        );
+        assert_eq!(module.export_kind("main"), Some(ExportKind::Function));
+        assert_eq!(module.export_kind("memory"), Some(ExportKind::Memory));
+        assert_eq!(module.export_kind("table"), Some(ExportKind::Table));
+        assert_eq!(module.export_kind("global"), Some(ExportKind::Global));
+        assert_eq!(module.export_kind("missing"), None);
+    }
+
+    #[test]
+    fn functions_of_one_type_share_it() {
+        let mut text = String::from("(module (type $t (func (param i32) (result i32)))");
+        text.push_str("(type (func))");
+        for _ in 0..1000 {
+            text.push_str("(func (type $t) local.get 0)");
+        }
+        text.push_str("(func (type 1)))");
+        let module = parse_wasm_bytes(&wasm(&text)).unwrap();
+
+        let first = &module.get_function(0).unwrap().r#type;
+        for i in 1..1000 {
+            assert!(Rc::ptr_eq(first, &module.get_function(i).unwrap().r#type));
+        }
+        let other = &module.get_function(1000).unwrap().r#type;
+        assert!(!Rc::ptr_eq(first, other));
+        assert_eq!(Rc::strong_count(first), 1000);
+    }
+
+    #[test]
+    fn floats_display_exactly() {
+        assert_eq!(Value::f32(0.0).to_string(), "(f32:0.0)");
+        assert_eq!(Value::f32(-0.0).to_string(), "(f32:-0.0)");
+        assert_eq!(Value::f64(-0.0).to_string(), "(f64:-0.0)");
+        // Enough digits to round-trip
+        assert_eq!(Value::f32(0.1).to_string(), "(f32:0.1)");
+        assert_eq!(
+            Value::f64(0.1 + 0.2).to_string(),
+            "(f64:0.30000000000000004)"
+        );
+        assert_eq!(Value::f32(16777217.0).to_string(), "(f32:16777216.0)");
+        assert_eq!(Value::f64(-f64::NAN).to_string(), "(f64:-nan)");
+        let payload = Value::from_bits(PrimitiveType::F32, 0x7F80_0001);
+        assert_eq!(payload.to_string(), "(f32:nan:0x1)");
+
+        for zero in [
+            Value::f32(-0.0),
+            Value::f64(0.0),
+            Value::i32(0),
+            Value::i64(0),
+        ] {
+            assert!(zero.is_zero() && !zero.is_nan(), "{}", zero);
+        }
+        for nan in [Value::f32(f32::NAN), Value::f64(-f64::NAN), payload] {
+            assert!(nan.is_nan() && !nan.is_zero(), "{}", nan);
+        }
+        for other in [Value::f32(1e-45), Value::i32(-1), Value::func_ref(0)] {
+            assert!(!other.is_zero() && !other.is_nan(), "{}", other);
+        }
+    }
+
+    #[test]
+    fn fresh_memory_reads_as_zero() {
+        let memory = Memory::new(1, 2);
+        assert_eq!(memory.size_bytes(), Some(PAGE_SIZE));
+        let last = PAGE_SIZE - 8;
+        for address in [0, 1, last] {
+            for bitwidth in [8, 16, 32] {
+                let v = memory.read(PrimitiveType::I32, bitwidth, address).unwrap();
+                assert_eq!(
+                    v.unpack(),
+                    TypedValue::I32(0),
+                    "{} bits at {}",
+                    bitwidth,
+                    address
+                );
+            }
+            for bitwidth in [8, 16, 32, 64] {
+                let v = memory.read(PrimitiveType::I64, bitwidth, address).unwrap();
+                assert_eq!(
+                    v.unpack(),
+                    TypedValue::I64(0),
+                    "{} bits at {}",
+                    bitwidth,
+                    address
+                );
+            }
+        }
+        assert!(memory.read(PrimitiveType::I64, 64, last + 1).is_none());
+
+        // The same through loads of every type in a module, before any store
+        let mut module = instantiate(
+            r#"(module
+                (memory 1)
+                (func (export "load") (result i32 i64 f32 f64)
+                    (i32.load (i32.const 0))
+                    (i64.load (i32.const 0))
+                    (f32.load (i32.const 0))
+                    (f64.load (i32.const 0))))"#,
+        );
+        assert_eq!(
+            call(&mut module, "load", &[]).unwrap(),
+            [
+                TypedValue::I32(0),
+                TypedValue::I64(0),
+                TypedValue::F32(0.0),
+                TypedValue::F64(0.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn call_debug_gives_the_leftover_stack() {
+        let text = r#"(module
+            (func (export "two") (result i32) i32.const 1 i32.const 2)
+            (func (export "one") (result i32) i32.const 3))"#;
+        let mut module = instantiate(text);
+        assert!(matches!(
+            module.call("two", vec![]),
+            Err(Error::StackViolation)
+        ));
+        match module.call_debug("two", vec![]) {
+            Err(Error::FinalStack(values)) => {
+                let values: Vec<TypedValue> = values.iter().map(Value::unpack).collect();
+                assert_eq!(values, [TypedValue::I32(1), TypedValue::I32(2)]);
+            }
+            _ => panic!("expected the final stack"),
+        }
+        // Well-formed functions are unaffected, and the mode ends with the call
+        assert_eq!(
+            module.call_debug("one", vec![]).unwrap()[0].unpack(),
+            TypedValue::I32(3)
+        );
+        assert!(matches!(
+            module.call("two", vec![]),
+            Err(Error::StackViolation)
+        ));
+    }
+
+    #[test]
+    fn preloaded_memory_is_read_by_wasm() {
+        let bytes = wasm(
+            r#"(module
+                (memory 1 3)
+                (data (i32.const 8) "\ff")
+                (func (export "load") (param i32) (result i32) (i32.load (local.get 0)))
+                (func (export "size") (result i32) memory.size))"#,
+        );
+        let mut module = parse_wasm_bytes(&bytes).unwrap();
+        let mut image = vec![0; PAGE_SIZE as usize + 4];
+        image[..4].copy_from_slice(&[1, 2, 3, 4]);
+        image[PAGE_SIZE as usize..].copy_from_slice(&[5, 6, 7, 8]);
+        // Past the declared maximum, or more bytes than the pages hold
+        assert!(module.set_memory_image(4, &image).is_err());
+        assert!(module.set_memory_image(1, &image).is_err());
+        assert_eq!(module.memory(0).unwrap().size_pages(), 1);
+
+        module.set_memory_image(2, &image).unwrap();
+        module.instantiate().unwrap();
+        assert_eq!(
+            call(&mut module, "size", &[]).unwrap(),
+            [TypedValue::I32(2)]
+        );
+        let load = |module: &mut Module, address: i32| call(module, "load", &[Value::i32(address)]);
+        assert_eq!(
+            load(&mut module, 0).unwrap(),
+            [TypedValue::I32(0x0403_0201)]
+        );
+        assert_eq!(
+            load(&mut module, PAGE_SIZE as i32).unwrap(),
+            [TypedValue::I32(0x0807_0605)]
+        );
+        // Data segments are copied in at instantiation, over the image
+        assert_eq!(load(&mut module, 8).unwrap(), [TypedValue::I32(0xFF)]);
+    }
+
+    #[test]
+    fn reset_restores_the_image_and_data_segments() {
+        let bytes = wasm(
+            r#"(module
+                (memory 1 3)
+                (data (i32.const 8) "\ff")
+                (func (export "load") (param i32) (result i32) (i32.load (local.get 0)))
+                (func (export "store") (param i32 i32) (i32.store (local.get 0) (local.get 1)))
+                (func (export "grow") (result i32) (memory.grow (i32.const 1))))"#,
+        );
+        let mut module = parse_wasm_bytes(&bytes).unwrap();
+        assert!(matches!(module.reset(), Err(Error::Misc(_))));
+        module.set_memory_image(1, &[1, 2, 3, 4]).unwrap();
+        module.instantiate().unwrap();
+
+        for address in [0, 8, 100].iter() {
+            call(
+                &mut module,
+                "store",
+                &[Value::i32(*address), Value::i32(-1)],
+            )
+            .unwrap();
+        }
+        call(&mut module, "grow", &[]).unwrap();
+        module.reset().unwrap();
+
+        let load = |module: &mut Module, address: i32| call(module, "load", &[Value::i32(address)]);
+        assert_eq!(
+            load(&mut module, 0).unwrap(),
+            [TypedValue::I32(0x0403_0201)]
+        );
+        assert_eq!(load(&mut module, 8).unwrap(), [TypedValue::I32(0xFF)]);
+        assert_eq!(load(&mut module, 100).unwrap(), [TypedValue::I32(0)]);
+        assert_eq!(module.memory(0).unwrap().size_pages(), 1);
+    }
+
+    #[test]
+    fn stack_types_before_an_add() {
+        let module = parse_wasm_bytes(&wasm(
+            r#"(module
+                (import "env" "f" (func))
+                (func (param i64) (result i32)
+                    i32.const 1
+                    local.get 0
+                    i32.wrap_i64
+                    i32.add))"#,
+        ))
+        .unwrap();
+        let types = |inst| module.stack_trace_types(1, inst).unwrap();
+        assert!(types(0).is_empty());
+        assert!(types(2) == [PrimitiveType::I32, PrimitiveType::I64]);
+        // Just before the i32.add
+        assert!(types(3) == [PrimitiveType::I32, PrimitiveType::I32]);
+        assert!(types(4) == [PrimitiveType::I32]);
+
+        assert!(module.stack_trace_types(1, 6).is_err());
+        assert!(module.stack_trace_types(0, 0).is_err());
+        assert!(module.stack_trace_types(2, 0).is_err());
+
+        // Operands of the wrong type stop the analysis
+        let module = parse_wasm_bytes(&wasm(
+            r#"(module (func (result i32) i64.const 1 i32.const 2 i32.add))"#,
+        ))
+        .unwrap();
+        assert!(module.stack_trace_types(0, 2).is_ok());
+        assert!(module.stack_trace_types(0, 3).is_err());
+    }
+
+    #[test]
+    fn missing_imports_are_named() {
+        let bytes = wasm(
+            r#"(module
+                (import "env" "foo" (func))
+                (import "env" "bar" (func (param i32))))"#,
+        );
+        let mut module = parse_wasm_bytes(&bytes).unwrap();
+        // The first missing import is the one named
+        assert!(matches!(
+            module.instantiate(),
+            Err(Error::UnresolvedImport { module, field }) if module == "env" && field == "foo"
+        ));
+
+        let linker = crate::wasm::linker::Linker::new();
+        assert!(matches!(
+            linker.instantiate(&mut module),
+            Err(Error::UnresolvedImport { module, field }) if module == "env" && field == "foo"
+        ));
+
+        // Globals are resolved while parsing, as their values are needed there
+        let bytes = wasm(r#"(module (import "env" "limit" (global i32)))"#);
+        assert!(matches!(
+            parse_wasm_bytes(&bytes),
+            Err(Error::UnresolvedImport { module, field }) if module == "env" && field == "limit"
+        ));
+    }
+
+    #[test]
+    fn failed_grow_leaves_the_memory_unchanged() {
+        let mut module = instantiate(
+            r#"(module
+                (memory (export "memory") 1 3)
+                (data (i32.const 0) "\2a")
+                (func (export "grow") (param i32) (result i32) (memory.grow (local.get 0)))
+                (func (export "size") (result i32) memory.size))"#,
+        );
+        let grow = |module: &mut Module, pages: i32| call(module, "grow", &[Value::i32(pages)]);
+        let size = |module: &mut Module| call(module, "size", &[]).unwrap();
+
+        // Past the declared maximum, including by a page count that wraps as a u32 sum
+        for pages in [3, 5, -1] {
+            assert_eq!(grow(&mut module, pages).unwrap(), [TypedValue::I32(-1)]);
+            assert_eq!(size(&mut module), [TypedValue::I32(1)]);
+            assert_eq!(module.memory(0).unwrap().size_bytes(), Some(PAGE_SIZE));
+        }
+        // Past a cap set by the embedder, below the declared maximum
+        module.set_memory_limit(2).unwrap();
+        assert_eq!(grow(&mut module, 2).unwrap(), [TypedValue::I32(-1)]);
+        assert_eq!(size(&mut module), [TypedValue::I32(1)]);
+
+        // A grow that fits still works, and keeps what was there
+        assert_eq!(grow(&mut module, 1).unwrap(), [TypedValue::I32(1)]);
+        assert_eq!(size(&mut module), [TypedValue::I32(2)]);
+        assert_eq!(module.memory(0).unwrap().as_slice(0, 1).unwrap(), [0x2a]);
+        assert_eq!(grow(&mut module, 1).unwrap(), [TypedValue::I32(-1)]);
+        assert_eq!(size(&mut module), [TypedValue::I32(2)]);
+    }
+
+    #[test]
+    fn declared_locals_start_at_zero_or_null() {
+        let mut module = instantiate(
+            r#"(module
+                (func (export "null") (result i32) (local funcref) (ref.is_null (local.get 0)))
+                (func (export "ref") (result funcref) (local funcref) local.get 0)
+                (func (export "zero") (result i64) (local i64) local.get 0))"#,
+        );
+        assert_eq!(
+            call(&mut module, "null", &[]).unwrap(),
+            [TypedValue::I32(1)]
+        );
+        assert_eq!(
+            call(&mut module, "ref", &[]).unwrap(),
+            [TypedValue::FuncRef(None)]
+        );
+        assert_eq!(
+            call(&mut module, "zero", &[]).unwrap(),
+            [TypedValue::I64(0)]
+        );
+        assert_eq!(
+            Value::default_for(PrimitiveType::ExternRef).unpack(),
+            TypedValue::ExternRef(None)
+        );
+    }
+
+    #[test]
+    fn unbounded_recursion_traps() {
+        let mut module = instantiate(
+            r#"(module
+                (func $f (export "f") (param i32) (result i32) (call $f (local.get 0)))
+                (func $down (export "down") (param i32) (result i32)
+                    (if (result i32) (local.get 0)
+                        (then (call $down (i32.sub (local.get 0) (i32.const 1))))
+                        (else (i32.const 7)))))"#,
+        );
+        let result = call(&mut module, "f", &[Value::i32(0)]);
+        assert_eq!(trap(result), Trap::CallStackExhausted);
+
+        // The depth goes back down, so the module can still be called, up to the limit
+        let config = |depth| ExecutionConfig {
+            max_call_depth: Some(depth),
+            ..ExecutionConfig::default()
+        };
+        let down = |module: &mut Module, n, depth| {
+            module.call_with_config("down", vec![Value::i32(n)], config(depth))
+        };
+        assert_eq!(
+            down(&mut module, 9, 10).unwrap()[0].unpack(),
+            TypedValue::I32(7)
+        );
+        assert_eq!(trap(down(&mut module, 10, 10)), Trap::CallStackExhausted);
+        let depth = (DEFAULT_MAX_CALL_DEPTH - 1) as i32;
+        assert_eq!(
+            call(&mut module, "down", &[Value::i32(depth)]).unwrap(),
+            [TypedValue::I32(7)]
+        );
     }
 }
@@ -1,7 +1,36 @@
 use super::*;
 
-use std::ops::Neg;
+use alloc::format;
+use core::ops::Neg;
+#[cfg(not(feature = "std"))]
+use num_traits::float::Float;
 
+#[derive(Clone, Default)]
+pub struct Unreachable {}
+
+impl Unreachable {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Instruction for Unreachable {
+    fn execute(
+        &self,
+        _: &mut Stack,
+        _: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        _: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        Ok(ControlInfo::Trap(Trap::Unreachable))
+    }
+
+    fn mnemonic(&self) -> String {
+        "unreachable".to_string()
+    }
+}
+
+#[derive(Clone)]
 pub struct Const {
     value: Value,
 }
@@ -16,20 +45,40 @@ impl Instruction for Const {
     fn execute(
         &self,
         stack: &mut Stack,
-        _: &mut Memory,
+        _: &mut ExecutionContext,
         _: &mut Vec<Value>,
-        _: &Vec<Function>,
+        _: &[Function],
     ) -> Result<ControlInfo, Error> {
-        stack.push_value(self.value);
+        stack.push_value(self.value)?;
         Ok(ControlInfo::None)
     }
+
+    fn mnemonic(&self) -> String {
+        format!("{}.const", self.value.t)
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        types.push(self.value.t);
+        Ok(())
+    }
 }
 
+#[derive(Clone)]
 pub enum Signedness {
     Signed,
     Unsigned,
 }
 
+impl fmt::Display for Signedness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Signedness::Signed => write!(f, "s"),
+            Signedness::Unsigned => write!(f, "u"),
+        }
+    }
+}
+
+#[derive(Clone)]
 pub enum IBinOpType {
     Add,
     Sub,
@@ -45,6 +94,7 @@ pub enum IBinOpType {
     Rotr,
 }
 
+#[derive(Clone)]
 pub struct IBinOp {
     result_type: PrimitiveType,
     op_type: IBinOpType,
@@ -63,13 +113,13 @@ impl Instruction for IBinOp {
     fn execute(
         &self,
         stack: &mut Stack,
-        _: &mut Memory,
+        ctx: &mut ExecutionContext,
         _: &mut Vec<Value>,
-        _: &Vec<Function>,
+        _: &[Function],
     ) -> Result<ControlInfo, Error> {
         let op_1 = stack.pop_value()?;
         let op_0 = stack.pop_value()?;
-        if !((op_0.t, op_1.t) == (op_1.t, self.result_type)) {
+        if op_0.t != self.result_type || op_1.t != self.result_type {
             return Err(Error::Misc("Operand type mismatch"));
         }
 
@@ -84,20 +134,25 @@ impl Instruction for IBinOp {
                     IBinOpType::Add => val_0.wrapping_add(val_1),
                     IBinOpType::Sub => val_0.wrapping_sub(val_1),
                     IBinOpType::Mul => val_0.wrapping_mul(val_1),
-                    IBinOpType::Div(Signedness::Signed) => match val_0.checked_div(val_1) {
-                        // checked_div will catch division by zero and TYPE_MIN / -1
-                        Some(v) => v,
-                        None => return Ok(ControlInfo::Trap(Trap::UndefinedDivision)),
-                    },
+                    IBinOpType::Div(Signedness::Signed) => {
+                        // checked_div fails for both of these, but they trap differently
+                        if val_1 == 0 {
+                            return Ok(ControlInfo::Trap(Trap::DivideByZero));
+                        }
+                        match val_0.checked_div(val_1) {
+                            Some(v) => v,
+                            None => return Ok(ControlInfo::Trap(Trap::IntegerOverflow)),
+                        }
+                    }
                     IBinOpType::Div(Signedness::Unsigned) => {
                         match (val_0 as UnsignedT).checked_div(val_1 as UnsignedT) {
                             Some(v) => v as SignedT,
-                            None => return Ok(ControlInfo::Trap(Trap::UndefinedDivision)),
+                            None => return Ok(ControlInfo::Trap(Trap::DivideByZero)),
                         }
                     }
                     IBinOpType::Rem(Signedness::Signed) => {
                         if val_1 == 0 {
-                            return Ok(ControlInfo::Trap(Trap::UndefinedDivision));
+                            return Ok(ControlInfo::Trap(Trap::DivideByZero));
                         } else {
                             val_0.wrapping_rem(val_1)
                         }
@@ -105,12 +160,12 @@ impl Instruction for IBinOp {
                     IBinOpType::Rem(Signedness::Unsigned) => {
                         match (val_0 as UnsignedT).checked_rem(val_1 as UnsignedT) {
                             Some(v) => v as SignedT,
-                            None => return Ok(ControlInfo::Trap(Trap::UndefinedDivision)),
+                            None => return Ok(ControlInfo::Trap(Trap::DivideByZero)),
                         }
                     }
-                    IBinOpType::And => (val_0 & val_1),
-                    IBinOpType::Or => (val_0 | val_1),
-                    IBinOpType::Xor => (val_0 ^ val_1),
+                    IBinOpType::And => val_0 & val_1,
+                    IBinOpType::Or => val_0 | val_1,
+                    IBinOpType::Xor => val_0 ^ val_1,
                     // shifts are modular in val_1, ie. shifting by 34 == shifting by 2
                     IBinOpType::Shl => val_0.wrapping_shl(val_1 as u32),
                     IBinOpType::Shr(Signedness::Signed) => val_0.wrapping_shr(val_1 as u32),
@@ -133,20 +188,25 @@ impl Instruction for IBinOp {
                     IBinOpType::Add => val_0.wrapping_add(val_1),
                     IBinOpType::Sub => val_0.wrapping_sub(val_1),
                     IBinOpType::Mul => val_0.wrapping_mul(val_1),
-                    IBinOpType::Div(Signedness::Signed) => match val_0.checked_div(val_1) {
-                        // checked_div will catch division by zero and TYPE_MIN / -1
-                        Some(v) => v,
-                        None => return Ok(ControlInfo::Trap(Trap::UndefinedDivision)),
-                    },
+                    IBinOpType::Div(Signedness::Signed) => {
+                        // checked_div fails for both of these, but they trap differently
+                        if val_1 == 0 {
+                            return Ok(ControlInfo::Trap(Trap::DivideByZero));
+                        }
+                        match val_0.checked_div(val_1) {
+                            Some(v) => v,
+                            None => return Ok(ControlInfo::Trap(Trap::IntegerOverflow)),
+                        }
+                    }
                     IBinOpType::Div(Signedness::Unsigned) => {
                         match (val_0 as UnsignedT).checked_div(val_1 as UnsignedT) {
                             Some(v) => v as SignedT,
-                            None => return Ok(ControlInfo::Trap(Trap::UndefinedDivision)),
+                            None => return Ok(ControlInfo::Trap(Trap::DivideByZero)),
                         }
                     }
                     IBinOpType::Rem(Signedness::Signed) => {
                         if val_1 == 0 {
-                            return Ok(ControlInfo::Trap(Trap::UndefinedDivision));
+                            return Ok(ControlInfo::Trap(Trap::DivideByZero));
                         } else {
                             val_0.wrapping_rem(val_1)
                         }
@@ -154,12 +214,12 @@ impl Instruction for IBinOp {
                     IBinOpType::Rem(Signedness::Unsigned) => {
                         match (val_0 as UnsignedT).checked_rem(val_1 as UnsignedT) {
                             Some(v) => v as SignedT,
-                            None => return Ok(ControlInfo::Trap(Trap::UndefinedDivision)),
+                            None => return Ok(ControlInfo::Trap(Trap::DivideByZero)),
                         }
                     }
-                    IBinOpType::And => (val_0 & val_1),
-                    IBinOpType::Or => (val_0 | val_1),
-                    IBinOpType::Xor => (val_0 ^ val_1),
+                    IBinOpType::And => val_0 & val_1,
+                    IBinOpType::Or => val_0 | val_1,
+                    IBinOpType::Xor => val_0 ^ val_1,
                     // shifts are modular in val_1, ie. shifting by 34 == shifting by 2
                     IBinOpType::Shl => val_0.wrapping_shl(val_1 as u32),
                     IBinOpType::Shr(Signedness::Signed) => val_0.wrapping_shr(val_1 as u32),
@@ -175,13 +235,39 @@ impl Instruction for IBinOp {
             _ => unreachable!(),
         };
 
-        stack.push_value(result);
-        log::debug!("Pushed {}", result);
+        stack.push_value(result)?;
+        trace!(ctx, "Pushed {}", result);
 
         Ok(ControlInfo::None)
     }
+
+    fn mnemonic(&self) -> String {
+        let op = match &self.op_type {
+            IBinOpType::Add => "add".to_string(),
+            IBinOpType::Sub => "sub".to_string(),
+            IBinOpType::Mul => "mul".to_string(),
+            IBinOpType::Div(s) => format!("div_{}", s),
+            IBinOpType::Rem(s) => format!("rem_{}", s),
+            IBinOpType::And => "and".to_string(),
+            IBinOpType::Or => "or".to_string(),
+            IBinOpType::Xor => "xor".to_string(),
+            IBinOpType::Shl => "shl".to_string(),
+            IBinOpType::Shr(s) => format!("shr_{}", s),
+            IBinOpType::Rotl => "rotl".to_string(),
+            IBinOpType::Rotr => "rotr".to_string(),
+        };
+        format!("{}.{}", self.result_type, op)
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        types.pop(self.result_type)?;
+        types.pop(self.result_type)?;
+        types.push(self.result_type);
+        Ok(())
+    }
 }
 
+#[derive(Clone)]
 pub enum FBinOpType {
     Add,
     Sub,
@@ -192,6 +278,7 @@ pub enum FBinOpType {
     CopySign,
 }
 
+#[derive(Clone)]
 pub struct FBinOp {
     result_type: PrimitiveType,
     op_type: FBinOpType,
@@ -210,13 +297,13 @@ impl Instruction for FBinOp {
     fn execute(
         &self,
         stack: &mut Stack,
-        _: &mut Memory,
+        ctx: &mut ExecutionContext,
         _: &mut Vec<Value>,
-        _: &Vec<Function>,
+        _: &[Function],
     ) -> Result<ControlInfo, Error> {
         let op_1 = stack.pop_value()?;
         let op_0 = stack.pop_value()?;
-        if !((op_0.t, op_1.t) == (op_1.t, self.result_type)) {
+        if op_0.t != self.result_type || op_1.t != self.result_type {
             return Err(Error::Misc("Operand type mismatch"));
         }
 
@@ -230,26 +317,31 @@ impl Instruction for FBinOp {
                     FBinOpType::Sub => val_0 - val_1,
                     FBinOpType::Mul => val_0 * val_1,
                     FBinOpType::Div => val_0 / val_1,
+                    // NaN operands give the canonical NaN, and -0.0 is considered less than +0.0
                     FBinOpType::Min => {
-                        if val_0.eq(&val_1) {
-                            f32::from_bits(val_0.to_bits() | val_1.to_bits())
-                        } else if val_0 < val_1 {
-                            val_0
-                        } else if val_0 > val_1 {
-                            val_1
-                        } else {
+                        if val_0.is_nan() || val_1.is_nan() {
                             f32::NAN
+                        } else if val_0 == 0.0 && val_1 == 0.0 {
+                            if val_0.is_sign_negative() {
+                                val_0
+                            } else {
+                                val_1
+                            }
+                        } else {
+                            val_0.min(val_1)
                         }
                     }
                     FBinOpType::Max => {
-                        if val_0.eq(&val_1) {
-                            f32::from_bits(val_0.to_bits() & val_1.to_bits())
-                        } else if val_0 > val_1 {
-                            val_0
-                        } else if val_0 < val_1 {
-                            val_1
-                        } else {
+                        if val_0.is_nan() || val_1.is_nan() {
                             f32::NAN
+                        } else if val_0 == 0.0 && val_1 == 0.0 {
+                            if val_0.is_sign_positive() {
+                                val_0
+                            } else {
+                                val_1
+                            }
+                        } else {
+                            val_0.max(val_1)
                         }
                     }
                     FBinOpType::CopySign => val_0.copysign(val_1),
@@ -266,26 +358,31 @@ impl Instruction for FBinOp {
                     FBinOpType::Sub => val_0 - val_1,
                     FBinOpType::Mul => val_0 * val_1,
                     FBinOpType::Div => val_0 / val_1,
+                    // NaN operands give the canonical NaN, and -0.0 is considered less than +0.0
                     FBinOpType::Min => {
-                        if val_0.eq(&val_1) {
-                            f64::from_bits(val_0.to_bits() | val_1.to_bits())
-                        } else if val_0 < val_1 {
-                            val_0
-                        } else if val_0 > val_1 {
-                            val_1
-                        } else {
+                        if val_0.is_nan() || val_1.is_nan() {
                             f64::NAN
+                        } else if val_0 == 0.0 && val_1 == 0.0 {
+                            if val_0.is_sign_negative() {
+                                val_0
+                            } else {
+                                val_1
+                            }
+                        } else {
+                            val_0.min(val_1)
                         }
                     }
                     FBinOpType::Max => {
-                        if val_0.eq(&val_1) {
-                            f64::from_bits(val_0.to_bits() & val_1.to_bits())
-                        } else if val_0 > val_1 {
-                            val_0
-                        } else if val_0 < val_1 {
-                            val_1
-                        } else {
+                        if val_0.is_nan() || val_1.is_nan() {
                             f64::NAN
+                        } else if val_0 == 0.0 && val_1 == 0.0 {
+                            if val_0.is_sign_positive() {
+                                val_0
+                            } else {
+                                val_1
+                            }
+                        } else {
+                            val_0.max(val_1)
                         }
                     }
                     FBinOpType::CopySign => val_0.copysign(val_1),
@@ -296,13 +393,39 @@ impl Instruction for FBinOp {
             _ => unreachable!(),
         };
 
-        stack.push_value(result);
-        log::debug!("Pushed {}", result);
+        // copysign only moves the sign bit, so it must keep any NaN payload
+        let result = match self.op_type {
+            FBinOpType::CopySign => result,
+            _ => ctx.float_result(result),
+        };
+        stack.push_value(result)?;
+        trace!(ctx, "Pushed {}", result);
 
         Ok(ControlInfo::None)
     }
+
+    fn mnemonic(&self) -> String {
+        let op = match self.op_type {
+            FBinOpType::Add => "add",
+            FBinOpType::Sub => "sub",
+            FBinOpType::Mul => "mul",
+            FBinOpType::Div => "div",
+            FBinOpType::Min => "min",
+            FBinOpType::Max => "max",
+            FBinOpType::CopySign => "copysign",
+        };
+        format!("{}.{}", self.result_type, op)
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        types.pop(self.result_type)?;
+        types.pop(self.result_type)?;
+        types.push(self.result_type);
+        Ok(())
+    }
 }
 
+#[derive(Clone)]
 pub enum RelOpType {
     Eq,
     Neq,
@@ -312,6 +435,7 @@ pub enum RelOpType {
     Ge(Signedness),
 }
 
+#[derive(Clone)]
 pub struct RelOp {
     arg_type: PrimitiveType,
     op_type: RelOpType,
@@ -327,13 +451,13 @@ impl Instruction for RelOp {
     fn execute(
         &self,
         stack: &mut Stack,
-        _: &mut Memory,
+        ctx: &mut ExecutionContext,
         _: &mut Vec<Value>,
-        _: &Vec<Function>,
+        _: &[Function],
     ) -> Result<ControlInfo, Error> {
         let op_1 = stack.pop_value()?;
         let op_0 = stack.pop_value()?;
-        if op_0.t != op_1.t {
+        if op_0.t != self.arg_type || op_1.t != self.arg_type {
             return Err(Error::Misc("Operand type mismatch"));
         }
 
@@ -342,9 +466,11 @@ impl Instruction for RelOp {
                 let val_0 = op_0.as_f32_unchecked();
                 let val_1 = op_1.as_f32_unchecked();
 
+                // IEEE comparisons, as for f64: -0.0 equals 0.0, and NaN is unequal to
+                // everything including itself, so only `ne` is true for it
                 let calc = match self.op_type {
                     RelOpType::Eq => val_0.eq(&val_1),
-                    RelOpType::Neq => val_0.eq(&val_1),
+                    RelOpType::Neq => val_0.ne(&val_1),
                     RelOpType::Lt(Signedness::Signed) => val_0 < val_1,
                     RelOpType::Gt(Signedness::Signed) => val_0 > val_1,
                     RelOpType::Le(Signedness::Signed) => val_0 <= val_1,
@@ -360,7 +486,7 @@ impl Instruction for RelOp {
 
                 let calc = match self.op_type {
                     RelOpType::Eq => val_0.eq(&val_1),
-                    RelOpType::Neq => val_0.eq(&val_1),
+                    RelOpType::Neq => val_0.ne(&val_1),
                     RelOpType::Lt(Signedness::Signed) => val_0 < val_1,
                     RelOpType::Gt(Signedness::Signed) => val_0 > val_1,
                     RelOpType::Le(Signedness::Signed) => val_0 <= val_1,
@@ -426,15 +552,42 @@ impl Instruction for RelOp {
 
                 Value::from_explicit_type(PrimitiveType::I32, calc as u64)
             }
+            _ => unreachable!(),
         };
 
-        stack.push_value(result);
-        log::debug!("Pushed {}", result);
+        stack.push_value(result)?;
+        trace!(ctx, "Pushed {}", result);
 
         Ok(ControlInfo::None)
     }
+
+    fn mnemonic(&self) -> String {
+        let (op, signedness) = match &self.op_type {
+            RelOpType::Eq => ("eq", None),
+            RelOpType::Neq => ("ne", None),
+            RelOpType::Lt(s) => ("lt", Some(s)),
+            RelOpType::Gt(s) => ("gt", Some(s)),
+            RelOpType::Le(s) => ("le", Some(s)),
+            RelOpType::Ge(s) => ("ge", Some(s)),
+        };
+        match (self.arg_type, signedness) {
+            // float comparisons have no signedness suffix
+            (PrimitiveType::I32, Some(s)) | (PrimitiveType::I64, Some(s)) => {
+                format!("{}.{}_{}", self.arg_type, op, s)
+            }
+            _ => format!("{}.{}", self.arg_type, op),
+        }
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        types.pop(self.arg_type)?;
+        types.pop(self.arg_type)?;
+        types.push(PrimitiveType::I32);
+        Ok(())
+    }
 }
 
+#[derive(Clone)]
 pub struct ITestOpEqz {
     arg_type: PrimitiveType,
 }
@@ -449,9 +602,9 @@ impl Instruction for ITestOpEqz {
     fn execute(
         &self,
         stack: &mut Stack,
-        _: &mut Memory,
+        ctx: &mut ExecutionContext,
         _: &mut Vec<Value>,
-        _: &Vec<Function>,
+        _: &[Function],
     ) -> Result<ControlInfo, Error> {
         let op = stack.pop_value()?;
         if op.t != self.arg_type {
@@ -472,18 +625,32 @@ impl Instruction for ITestOpEqz {
             _ => unreachable!(),
         };
 
-        stack.push_value(result);
-        log::debug!("Pushed {}", result);
+        stack.push_value(result)?;
+        trace!(ctx, "Pushed {}", result);
         Ok(ControlInfo::None)
     }
+
+    fn mnemonic(&self) -> String {
+        format!("{}.eqz", self.arg_type)
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        types.pop(self.arg_type)?;
+        types.push(PrimitiveType::I32);
+        Ok(())
+    }
 }
 
+#[derive(Clone)]
 pub enum IUnOpType {
     Clz,
     Ctz,
     Popcnt,
+    /// Sign-extend from the low number of bits given, e.g. `i32.extend8_s`
+    ExtendS(u32),
 }
 
+#[derive(Clone)]
 pub struct IUnOp {
     result_type: PrimitiveType,
     op_type: IUnOpType,
@@ -502,9 +669,9 @@ impl Instruction for IUnOp {
     fn execute(
         &self,
         stack: &mut Stack,
-        _: &mut Memory,
+        ctx: &mut ExecutionContext,
         _: &mut Vec<Value>,
-        _: &Vec<Function>,
+        _: &[Function],
     ) -> Result<ControlInfo, Error> {
         let op = stack.pop_value()?;
         if op.t != self.result_type {
@@ -519,6 +686,7 @@ impl Instruction for IUnOp {
                     IUnOpType::Clz => val_0.leading_zeros(),
                     IUnOpType::Ctz => val_0.trailing_zeros(),
                     IUnOpType::Popcnt => val_0.count_ones(),
+                    IUnOpType::ExtendS(bits) => ((val_0 << (32 - bits)) >> (32 - bits)) as u32,
                 };
 
                 Value::from_explicit_type(self.result_type, calc as u64)
@@ -527,23 +695,41 @@ impl Instruction for IUnOp {
                 let val_0 = op.as_i64_unchecked();
 
                 let calc = match self.op_type {
-                    IUnOpType::Clz => val_0.leading_zeros(),
-                    IUnOpType::Ctz => val_0.trailing_zeros(),
-                    IUnOpType::Popcnt => val_0.count_ones(),
+                    IUnOpType::Clz => val_0.leading_zeros() as u64,
+                    IUnOpType::Ctz => val_0.trailing_zeros() as u64,
+                    IUnOpType::Popcnt => val_0.count_ones() as u64,
+                    IUnOpType::ExtendS(bits) => ((val_0 << (64 - bits)) >> (64 - bits)) as u64,
                 };
 
-                Value::from_explicit_type(self.result_type, calc as u64)
+                Value::from_explicit_type(self.result_type, calc)
             }
             _ => unreachable!(),
         };
 
-        stack.push_value(result);
-        log::debug!("Pushed {}", result);
+        stack.push_value(result)?;
+        trace!(ctx, "Pushed {}", result);
 
         Ok(ControlInfo::None)
     }
+
+    fn mnemonic(&self) -> String {
+        let op = match self.op_type {
+            IUnOpType::Clz => "clz".to_string(),
+            IUnOpType::Ctz => "ctz".to_string(),
+            IUnOpType::Popcnt => "popcnt".to_string(),
+            IUnOpType::ExtendS(bits) => format!("extend{}_s", bits),
+        };
+        format!("{}.{}", self.result_type, op)
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        types.pop(self.result_type)?;
+        types.push(self.result_type);
+        Ok(())
+    }
 }
 
+#[derive(Clone)]
 pub enum FUnOpType {
     Abs,
     Neg,
@@ -554,6 +740,7 @@ pub enum FUnOpType {
     Nearest,
 }
 
+#[derive(Clone)]
 pub struct FUnOp {
     result_type: PrimitiveType,
     op_type: FUnOpType,
@@ -572,9 +759,9 @@ impl Instruction for FUnOp {
     fn execute(
         &self,
         stack: &mut Stack,
-        _: &mut Memory,
+        ctx: &mut ExecutionContext,
         _: &mut Vec<Value>,
-        _: &Vec<Function>,
+        _: &[Function],
     ) -> Result<ControlInfo, Error> {
         let op = stack.pop_value()?;
         if op.t != self.result_type {
@@ -646,14 +833,40 @@ impl Instruction for FUnOp {
             _ => unreachable!(),
         };
 
-        stack.push_value(result);
-        log::debug!("Pushed {}", result);
+        // abs and neg only touch the sign bit, so they must keep any NaN payload
+        let result = match self.op_type {
+            FUnOpType::Abs | FUnOpType::Neg => result,
+            _ => ctx.float_result(result),
+        };
+        stack.push_value(result)?;
+        trace!(ctx, "Pushed {}", result);
 
         Ok(ControlInfo::None)
     }
+
+    fn mnemonic(&self) -> String {
+        let op = match self.op_type {
+            FUnOpType::Abs => "abs",
+            FUnOpType::Neg => "neg",
+            FUnOpType::Sqrt => "sqrt",
+            FUnOpType::Ceil => "ceil",
+            FUnOpType::Floor => "floor",
+            FUnOpType::Trunc => "trunc",
+            FUnOpType::Nearest => "nearest",
+        };
+        format!("{}.{}", self.result_type, op)
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        types.pop(self.result_type)?;
+        types.push(self.result_type);
+        Ok(())
+    }
 }
 
 // variants declared with `PrimitiveType`s as (source, [result])
+// variants without types have fixed ones: wrap is i64 -> i32, extend is i32 -> i64
+#[derive(Clone)]
 pub enum CvtOpType {
     Wrap,
     Extend(Signedness),
@@ -665,6 +878,58 @@ pub enum CvtOpType {
     Reinterpret(PrimitiveType), // source type
 }
 
+impl CvtOpType {
+    /// Types of the operand and the result
+    fn operand_types(&self) -> (PrimitiveType, PrimitiveType) {
+        match *self {
+            CvtOpType::Wrap => (PrimitiveType::I64, PrimitiveType::I32),
+            CvtOpType::Extend(_) => (PrimitiveType::I32, PrimitiveType::I64),
+            CvtOpType::Trunc(_, src, dst)
+            | CvtOpType::TruncSat(_, src, dst)
+            | CvtOpType::Convert(_, src, dst) => (src, dst),
+            CvtOpType::Demote => (PrimitiveType::F64, PrimitiveType::F32),
+            CvtOpType::Promote => (PrimitiveType::F32, PrimitiveType::F64),
+            CvtOpType::Reinterpret(src) => {
+                let dst = match src {
+                    PrimitiveType::I32 => PrimitiveType::F32,
+                    PrimitiveType::I64 => PrimitiveType::F64,
+                    PrimitiveType::F32 => PrimitiveType::I32,
+                    PrimitiveType::F64 => PrimitiveType::I64,
+                    _ => unreachable!(),
+                };
+                (src, dst)
+            }
+        }
+    }
+}
+
+/// Whether `x` rounded towards zero fits in the integer type `dst`
+fn trunc_in_range(x: f64, s: &Signedness, dst: PrimitiveType) -> bool {
+    let t = x.trunc();
+    match (s, dst) {
+        (Signedness::Signed, PrimitiveType::I32) => t >= i32::MIN as f64 && t <= i32::MAX as f64,
+        // -0.0 compares equal to 0.0, so values in (-1, 0) are allowed
+        (Signedness::Unsigned, PrimitiveType::I32) => t >= 0.0 && t <= u32::MAX as f64,
+        // 2^63 and 2^64 are exact as f64s, while the maximums themselves are not
+        (Signedness::Signed, PrimitiveType::I64) => t >= i64::MIN as f64 && t < -(i64::MIN as f64),
+        (Signedness::Unsigned, PrimitiveType::I64) => t >= 0.0 && t < u64::MAX as f64,
+        _ => unreachable!(),
+    }
+}
+
+/// `x` rounded towards zero and clamped to the integer type `dst`, with NaN giving 0. These
+/// are the semantics of Rust's float to integer `as` casts. Gives the bits of the result.
+fn trunc_saturating(x: f64, s: &Signedness, dst: PrimitiveType) -> u64 {
+    match (s, dst) {
+        (Signedness::Signed, PrimitiveType::I32) => x as i32 as u32 as u64,
+        (Signedness::Unsigned, PrimitiveType::I32) => x as u32 as u64,
+        (Signedness::Signed, PrimitiveType::I64) => x as i64 as u64,
+        (Signedness::Unsigned, PrimitiveType::I64) => x as u64,
+        _ => unreachable!(),
+    }
+}
+
+#[derive(Clone)]
 pub struct CvtOp {
     op_type: CvtOpType,
 }
@@ -679,13 +944,13 @@ impl Instruction for CvtOp {
     fn execute(
         &self,
         stack: &mut Stack,
-        _: &mut Memory,
+        ctx: &mut ExecutionContext,
         _: &mut Vec<Value>,
-        _: &Vec<Function>,
+        _: &[Function],
     ) -> Result<ControlInfo, Error> {
         let op = stack.pop_value()?;
         let has_correct_type = match self.op_type {
-            CvtOpType::Wrap => op.t == PrimitiveType::I32,
+            CvtOpType::Wrap => op.t == PrimitiveType::I64,
             CvtOpType::Extend(_) => op.t == PrimitiveType::I32,
             CvtOpType::Trunc(_, src, _) => op.t == src,
             CvtOpType::TruncSat(_, src, _) => op.t == src,
@@ -708,23 +973,26 @@ impl Instruction for CvtOp {
             CvtOpType::Extend(Signedness::Unsigned) => {
                 Value::from_explicit_type(PrimitiveType::I64, op.as_i32_unchecked() as u32 as u64)
             }
-            CvtOpType::Trunc(Signedness::Unsigned, src, dst) => Value::from_explicit_type(
-                dst,
-                match src {
-                    PrimitiveType::F32 => op.as_f32_unchecked() as u32 as u64,
-                    PrimitiveType::F64 => op.as_f64_unchecked() as u64,
-                    _ => unreachable!(),
-                },
-            ),
-            CvtOpType::Trunc(Signedness::Signed, src, dst) => Value::from_explicit_type(
-                dst,
-                match src {
-                    PrimitiveType::F32 => op.as_f32_unchecked() as i32 as u32 as u64,
-                    PrimitiveType::F64 => op.as_f64_unchecked() as i64 as u64,
+            // trunc traps on values it can't represent, where trunc_sat clamps them (and NaN to 0)
+            CvtOpType::Trunc(ref s, src, dst) | CvtOpType::TruncSat(ref s, src, dst) => {
+                // every f32 is exactly representable as an f64
+                let x = match src {
+                    PrimitiveType::F32 => op.as_f32_unchecked() as f64,
+                    PrimitiveType::F64 => op.as_f64_unchecked(),
                     _ => unreachable!(),
-                },
-            ),
-            CvtOpType::Convert(Signedness::Unsigned, src, dst) => match (src, dst) {
+                };
+                if let CvtOpType::Trunc(..) = self.op_type {
+                    if x.is_nan() {
+                        return Ok(ControlInfo::Trap(Trap::InvalidConversionToInteger));
+                    }
+                    if !trunc_in_range(x, s, dst) {
+                        return Ok(ControlInfo::Trap(Trap::IntegerOverflow));
+                    }
+                }
+                Value::from_explicit_type(dst, trunc_saturating(x, s, dst))
+            }
+            // signed conversions sign-extend the source, unsigned ones zero-extend it
+            CvtOpType::Convert(Signedness::Signed, src, dst) => match (src, dst) {
                 (PrimitiveType::I32, PrimitiveType::F32) => {
                     Value::from(op.as_i32_unchecked() as f32)
                 }
@@ -739,7 +1007,7 @@ impl Instruction for CvtOp {
                 }
                 _ => unreachable!(),
             },
-            CvtOpType::Convert(Signedness::Signed, src, dst) => match (src, dst) {
+            CvtOpType::Convert(Signedness::Unsigned, src, dst) => match (src, dst) {
                 (PrimitiveType::I32, PrimitiveType::F32) => {
                     Value::from(op.as_i32_unchecked() as u32 as f32)
                 }
@@ -754,36 +1022,65 @@ impl Instruction for CvtOp {
                 }
                 _ => unreachable!(),
             },
-            CvtOpType::TruncSat(_, _, _) => unimplemented!(),
-            CvtOpType::Promote => Value::from(op.as_f32_unchecked() as f64),
-            CvtOpType::Demote => Value::from(op.as_f64_unchecked() as f32),
+            CvtOpType::Promote => ctx.float_result(Value::from(op.as_f32_unchecked() as f64)),
+            CvtOpType::Demote => ctx.float_result(Value::from(op.as_f64_unchecked() as f32)),
+            // go through the bit patterns so nothing (e.g. a NaN payload) can be altered
             CvtOpType::Reinterpret(src) => match src {
-                PrimitiveType::I32 => Value {
-                    t: PrimitiveType::F32,
-                    v: InternalValue::from(op.as_i32_unchecked()),
-                },
-                PrimitiveType::F32 => Value {
-                    t: PrimitiveType::I32,
-                    v: InternalValue::from(op.as_f32_unchecked()),
-                },
-                PrimitiveType::I64 => Value {
-                    t: PrimitiveType::F64,
-                    v: InternalValue::from(op.as_i64_unchecked()),
-                },
-                PrimitiveType::F64 => Value {
-                    t: PrimitiveType::I64,
-                    v: InternalValue::from(op.as_f64_unchecked()),
-                },
+                PrimitiveType::I32 => {
+                    Value::from_bits(PrimitiveType::F32, op.as_i32_unchecked() as u32 as u64)
+                }
+                PrimitiveType::F32 => {
+                    Value::from_bits(PrimitiveType::I32, op.as_f32_unchecked().to_bits() as u64)
+                }
+                PrimitiveType::I64 => {
+                    Value::from_bits(PrimitiveType::F64, op.as_i64_unchecked() as u64)
+                }
+                PrimitiveType::F64 => {
+                    Value::from_bits(PrimitiveType::I64, op.as_f64_unchecked().to_bits())
+                }
+                _ => unreachable!(),
             },
         };
 
-        stack.push_value(result);
-        log::debug!("Pushed {}", result);
+        stack.push_value(result)?;
+        trace!(ctx, "Pushed {}", result);
 
         Ok(ControlInfo::None)
     }
+
+    fn mnemonic(&self) -> String {
+        match &self.op_type {
+            CvtOpType::Wrap => "i32.wrap_i64".to_string(),
+            CvtOpType::Extend(s) => format!("i64.extend_i32_{}", s),
+            CvtOpType::Trunc(s, src, dst) => format!("{}.trunc_{}_{}", dst, src, s),
+            CvtOpType::TruncSat(s, src, dst) => format!("{}.trunc_sat_{}_{}", dst, src, s),
+            CvtOpType::Convert(s, src, dst) => format!("{}.convert_{}_{}", dst, src, s),
+            CvtOpType::Demote => "f32.demote_f64".to_string(),
+            CvtOpType::Promote => "f64.promote_f32".to_string(),
+            CvtOpType::Reinterpret(src) => {
+                let (_, dst) = self.op_type.operand_types();
+                format!("{}.reinterpret_{}", dst, src)
+            }
+        }
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        let (src, dst) = self.op_type.operand_types();
+        types.pop(src)?;
+        types.push(dst);
+        Ok(())
+    }
 }
 
+/// The local with the given index, which a corrupt module may not have declared
+fn local_mut(locals: &mut [Value], index: usize) -> Result<&mut Value, Error> {
+    match locals.get_mut(index) {
+        Some(l) => Ok(l),
+        None => Err(Error::Misc("Local index is not valid")),
+    }
+}
+
+#[derive(Clone)]
 pub struct LocalGet {
     index: usize,
 }
@@ -798,15 +1095,26 @@ impl Instruction for LocalGet {
     fn execute(
         &self,
         stack: &mut Stack,
-        _: &mut Memory,
+        _: &mut ExecutionContext,
         locals: &mut Vec<Value>,
-        _: &Vec<Function>,
+        _: &[Function],
     ) -> Result<ControlInfo, Error> {
-        stack.push_value(locals[self.index]);
+        stack.push_value(*local_mut(locals, self.index)?)?;
         Ok(ControlInfo::None)
     }
+
+    fn mnemonic(&self) -> String {
+        "local.get".to_string()
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        let t = types.local(self.index)?;
+        types.push(t);
+        Ok(())
+    }
 }
 
+#[derive(Clone)]
 pub struct LocalSet {
     index: usize,
 }
@@ -821,15 +1129,25 @@ impl Instruction for LocalSet {
     fn execute(
         &self,
         stack: &mut Stack,
-        _: &mut Memory,
+        _: &mut ExecutionContext,
         locals: &mut Vec<Value>,
-        _: &Vec<Function>,
+        _: &[Function],
     ) -> Result<ControlInfo, Error> {
-        locals[self.index] = stack.pop_value()?;
+        *local_mut(locals, self.index)? = stack.pop_value()?;
         Ok(ControlInfo::None)
     }
+
+    fn mnemonic(&self) -> String {
+        "local.set".to_string()
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        let t = types.local(self.index)?;
+        types.pop(t)
+    }
 }
 
+#[derive(Clone)]
 pub struct LocalTee {
     index: usize,
 }
@@ -844,24 +1162,146 @@ impl Instruction for LocalTee {
     fn execute(
         &self,
         stack: &mut Stack,
-        _: &mut Memory,
+        _: &mut ExecutionContext,
         locals: &mut Vec<Value>,
-        _: &Vec<Function>,
+        _: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        *local_mut(locals, self.index)? = *stack.fetch_value(0)?;
+        Ok(ControlInfo::None)
+    }
+
+    fn mnemonic(&self) -> String {
+        "local.tee".to_string()
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        let t = types.local(self.index)?;
+        types.pop(t)?;
+        types.push(t);
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct GlobalGet {
+    index: usize,
+}
+
+impl GlobalGet {
+    pub fn new(index: usize) -> Self {
+        Self { index }
+    }
+}
+
+impl Instruction for GlobalGet {
+    fn execute(
+        &self,
+        stack: &mut Stack,
+        ctx: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        _: &[Function],
     ) -> Result<ControlInfo, Error> {
-        locals[self.index] = *stack.fetch_value(0)?;
+        match ctx.globals.get(self.index) {
+            Some(g) => {
+                stack.push_value(g.value)?;
+                Ok(ControlInfo::None)
+            }
+            None => Err(Error::Misc("Global index out of range")),
+        }
+    }
+
+    fn mnemonic(&self) -> String {
+        "global.get".to_string()
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        let t = types.global(self.index)?;
+        types.push(t);
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct GlobalSet {
+    index: usize,
+}
+
+impl GlobalSet {
+    pub fn new(index: usize) -> Self {
+        Self { index }
+    }
+}
+
+impl Instruction for GlobalSet {
+    fn execute(
+        &self,
+        stack: &mut Stack,
+        ctx: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        _: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        let value = stack.pop_value()?;
+        let global = match ctx.globals.get_mut(self.index) {
+            Some(g) => g,
+            None => return Err(Error::Misc("Global index out of range")),
+        };
+        if !global.mutable {
+            return Err(Error::Misc("Cannot set an immutable global"));
+        }
+        if global.value.t != value.t {
+            return Err(Error::Misc("Operand type mismatch"));
+        }
+        global.value = value;
         Ok(ControlInfo::None)
     }
+
+    fn mnemonic(&self) -> String {
+        "global.set".to_string()
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        let t = types.global(self.index)?;
+        types.pop(t)
+    }
+}
+
+/// Width in bits of a number type
+fn width_of(t: PrimitiveType) -> u8 {
+    match t {
+        PrimitiveType::I32 | PrimitiveType::F32 => 32,
+        PrimitiveType::I64 | PrimitiveType::F64 => 64,
+        _ => unreachable!(),
+    }
+}
+
+/// Memory access mnemonics only spell out the width for narrow accesses, e.g. `i64.load32`
+fn width_suffix(t: PrimitiveType, bitwidth: u8) -> String {
+    if bitwidth == width_of(t) {
+        String::new()
+    } else {
+        bitwidth.to_string()
+    }
+}
+
+/// The immediates of a memory access
+#[derive(Clone)]
+pub struct MemArg {
+    pub align: u32,
+    pub memory_index: usize,
+    pub offset: u32,
 }
 
+#[derive(Clone)]
 pub struct Load {
     result_type: PrimitiveType,
     load_bitwidth: u8,
+    memory_index: usize,
     offset: u32,
 }
 
 impl Load {
-    pub fn new(result_type: PrimitiveType, load_bitwidth: u8, _align: u32, offset: u32) -> Self {
-        debug_assert!(load_bitwidth % 8 == 0);
+    pub fn new(result_type: PrimitiveType, load_bitwidth: u8, memarg: MemArg) -> Self {
+        debug_assert!(load_bitwidth.is_multiple_of(8));
         match result_type {
             PrimitiveType::I32 => {
                 debug_assert!(load_bitwidth <= 32);
@@ -875,11 +1315,13 @@ impl Load {
             PrimitiveType::F64 => {
                 debug_assert!(load_bitwidth == 64);
             }
+            _ => unreachable!(),
         }
         Self {
             result_type,
             load_bitwidth,
-            offset,
+            memory_index: memarg.memory_index,
+            offset: memarg.offset,
         }
     }
 }
@@ -888,29 +1330,58 @@ impl Instruction for Load {
     fn execute(
         &self,
         stack: &mut Stack,
-        memory: &mut Memory,
+        ctx: &mut ExecutionContext,
         _: &mut Vec<Value>,
-        _: &Vec<Function>,
+        _: &[Function],
     ) -> Result<ControlInfo, Error> {
-        let address = u32::try_from(stack.pop_value()?)? as u64 + self.offset as u64;
-        match memory.read(self.result_type, self.load_bitwidth, address) {
+        let base = u32::try_from(stack.pop_value()?)?;
+        let address = Memory::effective_addr(base, self.offset);
+        match ctx
+            .memory(self.memory_index)?
+            .read(self.result_type, self.load_bitwidth, address)
+        {
             Some(s) => {
-                stack.push_value(s);
+                stack.push_value(s)?;
                 Ok(ControlInfo::None)
             }
             None => Ok(ControlInfo::Trap(Trap::MemoryOutOfBounds)),
         }
     }
+
+    fn mnemonic(&self) -> String {
+        format!(
+            "{}.load{}",
+            self.result_type,
+            width_suffix(self.result_type, self.load_bitwidth)
+        )
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        types.pop(PrimitiveType::I32)?;
+        types.push(self.result_type);
+        Ok(())
+    }
 }
 
+/// Plain and narrow stores. The address is pushed first and the value second, so the value is
+/// on top of the stack and gets popped before the address. Every store, atomic ones included,
+/// takes its operands in this order.
+#[derive(Clone)]
 pub struct Store {
+    value_type: PrimitiveType,
     bitwidth: u8,
+    memory_index: usize,
     offset: u32,
 }
 
 impl Store {
-    pub fn new(bitwidth: u8, _align: u32, offset: u32) -> Self {
-        Self { bitwidth, offset }
+    pub fn new(value_type: PrimitiveType, bitwidth: u8, memarg: MemArg) -> Self {
+        Self {
+            value_type,
+            bitwidth,
+            memory_index: memarg.memory_index,
+            offset: memarg.offset,
+        }
     }
 }
 
@@ -918,197 +1389,3168 @@ impl Instruction for Store {
     fn execute(
         &self,
         stack: &mut Stack,
-        memory: &mut Memory,
+        ctx: &mut ExecutionContext,
         _: &mut Vec<Value>,
-        _: &Vec<Function>,
+        _: &[Function],
     ) -> Result<ControlInfo, Error> {
-        //TODO: popped values need to be checked
-        let value = stack.pop_value()?.as_i64_unchecked() as u64;
-        let address = u32::try_from(stack.pop_value()?)? as u64 + self.offset as u64;
-        match memory.write(value, self.bitwidth, address) {
+        // The value is on top, with the address underneath it
+        let value = stack.pop_value()?;
+        if value.t != self.value_type {
+            return Err(Error::StackViolation);
+        }
+        let bits = value.to_bits();
+        // Narrow stores keep only the low `bitwidth` bits of the value
+        let value = if self.bitwidth >= 64 {
+            bits
+        } else {
+            bits & ((1 << self.bitwidth) - 1)
+        };
+        let base = u32::try_from(stack.pop_value()?)?;
+        let address = Memory::effective_addr(base, self.offset);
+        match ctx
+            .memory_mut(self.memory_index)?
+            .write(value, self.bitwidth, address)
+        {
             Some(_) => Ok(ControlInfo::None),
             None => Ok(ControlInfo::Trap(Trap::MemoryOutOfBounds)),
         }
     }
-}
-
-pub struct Branch {
-    branch_index: u32,
-}
 
-impl Branch {
-    pub fn new(branch_index: u32) -> Self {
-        Self { branch_index }
+    fn mnemonic(&self) -> String {
+        format!(
+            "{}.store{}",
+            self.value_type,
+            width_suffix(self.value_type, self.bitwidth)
+        )
     }
-}
 
-impl Instruction for Branch {
-    fn execute(
-        &self,
-        _: &mut Stack,
-        _: &mut Memory,
-        _: &mut Vec<Value>,
-        _: &Vec<Function>,
-    ) -> Result<ControlInfo, Error> {
-        Ok(ControlInfo::Branch(self.branch_index))
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        types.pop(self.value_type)?;
+        types.pop(PrimitiveType::I32)
     }
 }
 
-pub struct BranchIf {
-    branch_index: u32,
+#[derive(Clone)]
+pub struct MemorySize {
+    memory_index: usize,
 }
 
-impl BranchIf {
-    pub fn new(branch_index: u32) -> Self {
-        Self { branch_index }
+impl MemorySize {
+    pub fn new(memory_index: usize) -> Self {
+        Self { memory_index }
     }
 }
 
-impl Instruction for BranchIf {
+impl Instruction for MemorySize {
     fn execute(
         &self,
         stack: &mut Stack,
-        _: &mut Memory,
+        ctx: &mut ExecutionContext,
         _: &mut Vec<Value>,
-        _: &Vec<Function>,
+        _: &[Function],
     ) -> Result<ControlInfo, Error> {
-        let condition = stack.pop_value()?.as_i64_unchecked() as u64;
-        if condition == 0 {
-            Ok(ControlInfo::None)
-        } else {
-            Ok(ControlInfo::Branch(self.branch_index))
-        }
+        let size = ctx.memory(self.memory_index)?.size_pages();
+        stack.push_value(Value::from(size as i32))?;
+        Ok(ControlInfo::None)
+    }
+
+    fn mnemonic(&self) -> String {
+        "memory.size".to_string()
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        types.push(PrimitiveType::I32);
+        Ok(())
     }
 }
 
-pub struct Call {
-    function_index: usize,
+#[derive(Clone)]
+pub struct MemoryGrow {
+    memory_index: usize,
 }
 
-impl Call {
-    pub fn new(function_index: usize) -> Self {
-        Self { function_index }
+impl MemoryGrow {
+    pub fn new(memory_index: usize) -> Self {
+        Self { memory_index }
     }
 }
 
-impl Instruction for Call {
+impl Instruction for MemoryGrow {
     fn execute(
         &self,
         stack: &mut Stack,
-        memory: &mut Memory,
+        ctx: &mut ExecutionContext,
         _: &mut Vec<Value>,
-        functions: &Vec<Function>,
+        _: &[Function],
     ) -> Result<ControlInfo, Error> {
-        log::debug!("Calling function with index {}", self.function_index);
-        let called_function = &functions[self.function_index];
-        let mut args = Vec::new();
-        for _ in 0..called_function.num_params() {
-            args.push(stack.pop_value()?);
-        }
-        args.reverse();
-        stack.push_value(called_function.call(functions, memory, args)?);
+        let delta = u32::try_from(stack.pop_value()?)?;
+        // Failing to grow isn't a trap, the program is just given -1 instead of the old size
+        let result = match ctx.grow_memory(self.memory_index, delta)? {
+            Some(old_pages) => old_pages as i32,
+            None => -1,
+        };
+        stack.push_value(Value::from(result))?;
         Ok(ControlInfo::None)
     }
-}
 
-pub struct Return {}
+    fn mnemonic(&self) -> String {
+        "memory.grow".to_string()
+    }
 
-impl Return {
-    pub fn new() -> Self {
-        Self {}
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        types.pop(PrimitiveType::I32)?;
+        types.push(PrimitiveType::I32);
+        Ok(())
     }
 }
 
-impl Instruction for Return {
+/// Address of an atomic access, which unlike other accesses must be aligned to its width
+fn atomic_addr(base: u32, offset: u32, bitwidth: u8) -> Result<u64, Trap> {
+    let address = Memory::effective_addr(base, offset);
+    if !address.is_multiple_of((bitwidth / 8) as u64) {
+        return Err(Trap::UnalignedAtomic);
+    }
+    Ok(address)
+}
+
+#[derive(Clone)]
+pub struct AtomicLoad {
+    result_type: PrimitiveType,
+    bitwidth: u8,
+    memory_index: usize,
+    offset: u32,
+}
+
+impl AtomicLoad {
+    pub fn new(result_type: PrimitiveType, bitwidth: u8, memarg: MemArg) -> Self {
+        Self {
+            result_type,
+            bitwidth,
+            memory_index: memarg.memory_index,
+            offset: memarg.offset,
+        }
+    }
+}
+
+impl Instruction for AtomicLoad {
     fn execute(
         &self,
         stack: &mut Stack,
-        memory: &mut Memory,
+        ctx: &mut ExecutionContext,
         _: &mut Vec<Value>,
-        functions: &Vec<Function>,
+        _: &[Function],
     ) -> Result<ControlInfo, Error> {
-        Ok(ControlInfo::Return)
+        let base = u32::try_from(stack.pop_value()?)?;
+        let address = match atomic_addr(base, self.offset, self.bitwidth) {
+            Ok(n) => n,
+            Err(t) => return Ok(ControlInfo::Trap(t)),
+        };
+        match ctx
+            .memory(self.memory_index)?
+            .read(self.result_type, self.bitwidth, address)
+        {
+            Some(v) => {
+                stack.push_value(v)?;
+                Ok(ControlInfo::None)
+            }
+            None => Ok(ControlInfo::Trap(Trap::MemoryOutOfBounds)),
+        }
+    }
+
+    fn mnemonic(&self) -> String {
+        format!(
+            "{}.atomic.load{}",
+            self.result_type,
+            width_suffix(self.result_type, self.bitwidth)
+        )
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        types.pop(PrimitiveType::I32)?;
+        types.push(self.result_type);
+        Ok(())
     }
 }
 
-pub enum BlockContinuation {
-    Loop,
-    Branch,
+#[derive(Clone)]
+pub struct AtomicStore {
+    value_type: PrimitiveType,
+    bitwidth: u8,
+    memory_index: usize,
+    offset: u32,
 }
 
-pub struct Block {
-    continuation: BlockContinuation,
-    instructions: Vec<Box<dyn Instruction>>,
+impl AtomicStore {
+    pub fn new(value_type: PrimitiveType, bitwidth: u8, memarg: MemArg) -> Self {
+        Self {
+            value_type,
+            bitwidth,
+            memory_index: memarg.memory_index,
+            offset: memarg.offset,
+        }
+    }
 }
 
-impl Block {
-    pub fn new(continuation: BlockContinuation, instructions: Vec<Box<dyn Instruction>>) -> Self {
+impl Instruction for AtomicStore {
+    fn execute(
+        &self,
+        stack: &mut Stack,
+        ctx: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        _: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        let value = stack.pop_value()?;
+        let base = u32::try_from(stack.pop_value()?)?;
+        if value.t != self.value_type {
+            return Err(Error::Misc("Operand type mismatch"));
+        }
+        let address = match atomic_addr(base, self.offset, self.bitwidth) {
+            Ok(n) => n,
+            Err(t) => return Ok(ControlInfo::Trap(t)),
+        };
+        match ctx
+            .memory_mut(self.memory_index)?
+            .write(value.to_bits(), self.bitwidth, address)
+        {
+            Some(_) => Ok(ControlInfo::None),
+            None => Ok(ControlInfo::Trap(Trap::MemoryOutOfBounds)),
+        }
+    }
+
+    fn mnemonic(&self) -> String {
+        format!(
+            "{}.atomic.store{}",
+            self.value_type,
+            width_suffix(self.value_type, self.bitwidth)
+        )
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        types.pop(self.value_type)?;
+        types.pop(PrimitiveType::I32)
+    }
+}
+
+#[derive(Clone)]
+pub enum AtomicRmwOpType {
+    Add,
+    Sub,
+    And,
+    Or,
+    Xor,
+    Xchg,
+}
+
+/// Atomic read-modify-write, which stores the result of the operation and pushes the old value
+#[derive(Clone)]
+pub struct AtomicRmw {
+    value_type: PrimitiveType,
+    bitwidth: u8,
+    op_type: AtomicRmwOpType,
+    memory_index: usize,
+    offset: u32,
+}
+
+impl AtomicRmw {
+    pub fn new(
+        value_type: PrimitiveType,
+        bitwidth: u8,
+        op_type: AtomicRmwOpType,
+        memarg: MemArg,
+    ) -> Self {
         Self {
-            continuation,
-            instructions,
+            value_type,
+            bitwidth,
+            op_type,
+            memory_index: memarg.memory_index,
+            offset: memarg.offset,
         }
     }
 }
 
-impl Instruction for Block {
+impl Instruction for AtomicRmw {
     fn execute(
         &self,
         stack: &mut Stack,
-        memory: &mut Memory,
-        locals: &mut Vec<Value>,
-        functions: &Vec<Function>,
+        ctx: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        _: &[Function],
     ) -> Result<ControlInfo, Error> {
-        // This outer loop is being used more as a goto than an actual loop.
-        let mut loop_restart;
-        loop {
-            loop_restart = false;
-            for inst in &self.instructions {
-                match inst.execute(stack, memory, locals, functions) {
-                    // Instruction returned a branch
-                    Ok(ControlInfo::Branch(branch_levels)) => {
-                        if branch_levels == 0 {
-                            // If we are a loop, continue execution from the beginning of our instrucitons.
-                            // Otherwise, halt execution and return to our parent block.
-                            match self.continuation {
-                                BlockContinuation::Loop => {
-                                    log::debug!("Branching to loop at depth 0");
-                                    loop_restart = true;
-                                }
-                                BlockContinuation::Branch => {
-                                    log::debug!("Branching out of a block with depth 0");
-                                    return Ok(ControlInfo::None);
-                                }
-                            }
-                        } else {
-                            // Both loops and branches need to pass the control information up to the higher block
-                            let new_depth = branch_levels - 1;
-                            log::debug!(
-                                "Branching out of block from branch depth {} to {}",
-                                branch_levels,
-                                new_depth
-                            );
-                            return Ok(ControlInfo::Branch(new_depth));
-                        }
-                    }
-                    Ok(ControlInfo::Return) => {
-                        // Unwrap up to the function's call handler
-                        log::debug!("Unwrapping return!");
-                        return Ok(ControlInfo::Return);
-                    }
-                    Ok(_) => (),
-                    Err(e) => {
-                        return Err(e);
-                    }
-                }
-                if loop_restart {
-                    break;
-                }
-            }
-            // Getting here implies that we need to fall through the block
-            if !loop_restart {
-                break;
-            }
+        let operand = stack.pop_value()?;
+        let base = u32::try_from(stack.pop_value()?)?;
+        if operand.t != self.value_type {
+            return Err(Error::Misc("Operand type mismatch"));
+        }
+        let address = match atomic_addr(base, self.offset, self.bitwidth) {
+            Ok(n) => n,
+            Err(t) => return Ok(ControlInfo::Trap(t)),
+        };
+        let memory = ctx.memory_mut(self.memory_index)?;
+        let old = match memory.read(self.value_type, self.bitwidth, address) {
+            Some(v) => v,
+            None => return Ok(ControlInfo::Trap(Trap::MemoryOutOfBounds)),
+        };
+        // Only the low `bitwidth` bits are stored, so the arithmetic can just wrap at 64 bits
+        let old_bits = old.to_bits();
+        let operand_bits = operand.to_bits();
+        let new_bits = match self.op_type {
+            AtomicRmwOpType::Add => old_bits.wrapping_add(operand_bits),
+            AtomicRmwOpType::Sub => old_bits.wrapping_sub(operand_bits),
+            AtomicRmwOpType::And => old_bits & operand_bits,
+            AtomicRmwOpType::Or => old_bits | operand_bits,
+            AtomicRmwOpType::Xor => old_bits ^ operand_bits,
+            AtomicRmwOpType::Xchg => operand_bits,
+        };
+        if memory.write(new_bits, self.bitwidth, address).is_none() {
+            return Ok(ControlInfo::Trap(Trap::MemoryOutOfBounds));
+        }
+        stack.push_value(old)?;
+        Ok(ControlInfo::None)
+    }
+
+    fn mnemonic(&self) -> String {
+        let op = match self.op_type {
+            AtomicRmwOpType::Add => "add",
+            AtomicRmwOpType::Sub => "sub",
+            AtomicRmwOpType::And => "and",
+            AtomicRmwOpType::Or => "or",
+            AtomicRmwOpType::Xor => "xor",
+            AtomicRmwOpType::Xchg => "xchg",
+        };
+        format!(
+            "{}.atomic.rmw{}.{}",
+            self.value_type,
+            width_suffix(self.value_type, self.bitwidth),
+            op
+        )
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        types.pop(self.value_type)?;
+        types.pop(PrimitiveType::I32)?;
+        types.push(self.value_type);
+        Ok(())
+    }
+}
+
+/// `memory.atomic.wait32`/`wait64`. With a single thread nothing can ever notify the waiter,
+/// so this returns "not-equal" (1) or "timed-out" (2) right away.
+#[derive(Clone)]
+pub struct AtomicWait {
+    value_type: PrimitiveType,
+    memory_index: usize,
+    offset: u32,
+}
+
+impl AtomicWait {
+    pub fn new(value_type: PrimitiveType, memarg: MemArg) -> Self {
+        Self {
+            value_type,
+            memory_index: memarg.memory_index,
+            offset: memarg.offset,
+        }
+    }
+}
+
+impl Instruction for AtomicWait {
+    fn execute(
+        &self,
+        stack: &mut Stack,
+        ctx: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        _: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        let _timeout = stack.pop_value()?;
+        let expected = stack.pop_value()?;
+        let base = u32::try_from(stack.pop_value()?)?;
+        if expected.t != self.value_type {
+            return Err(Error::Misc("Operand type mismatch"));
+        }
+        let bitwidth = width_of(self.value_type);
+        let address = match atomic_addr(base, self.offset, bitwidth) {
+            Ok(n) => n,
+            Err(t) => return Ok(ControlInfo::Trap(t)),
+        };
+        let loaded = match ctx
+            .memory(self.memory_index)?
+            .read(self.value_type, bitwidth, address)
+        {
+            Some(v) => v,
+            None => return Ok(ControlInfo::Trap(Trap::MemoryOutOfBounds)),
+        };
+        let result = if loaded.to_bits() == expected.to_bits() {
+            2
+        } else {
+            1
+        };
+        stack.push_value(Value::from(result))?;
+        Ok(ControlInfo::None)
+    }
+
+    fn mnemonic(&self) -> String {
+        format!("memory.atomic.wait{}", width_of(self.value_type))
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        // Address, expected value and timeout
+        types.pop(PrimitiveType::I64)?;
+        types.pop(self.value_type)?;
+        types.pop(PrimitiveType::I32)?;
+        types.push(PrimitiveType::I32);
+        Ok(())
+    }
+}
+
+/// `memory.atomic.notify`, which never has any waiters to wake with a single thread
+#[derive(Clone)]
+pub struct AtomicNotify {
+    memory_index: usize,
+    offset: u32,
+}
+
+impl AtomicNotify {
+    pub fn new(memarg: MemArg) -> Self {
+        Self {
+            memory_index: memarg.memory_index,
+            offset: memarg.offset,
+        }
+    }
+}
+
+impl Instruction for AtomicNotify {
+    fn execute(
+        &self,
+        stack: &mut Stack,
+        ctx: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        _: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        let _count = stack.pop_value()?;
+        let base = u32::try_from(stack.pop_value()?)?;
+        let address = match atomic_addr(base, self.offset, 32) {
+            Ok(n) => n,
+            Err(t) => return Ok(ControlInfo::Trap(t)),
+        };
+        if ctx
+            .memory(self.memory_index)?
+            .read(PrimitiveType::I32, 32, address)
+            .is_none()
+        {
+            return Ok(ControlInfo::Trap(Trap::MemoryOutOfBounds));
+        }
+        stack.push_value(Value::from(0_i32))?;
+        Ok(ControlInfo::None)
+    }
+
+    fn mnemonic(&self) -> String {
+        "memory.atomic.notify".to_string()
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        types.pop(PrimitiveType::I32)?;
+        types.pop(PrimitiveType::I32)?;
+        types.push(PrimitiveType::I32);
+        Ok(())
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct AtomicFence {}
+
+impl AtomicFence {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Instruction for AtomicFence {
+    fn execute(
+        &self,
+        _: &mut Stack,
+        _: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        _: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        Ok(ControlInfo::None)
+    }
+
+    fn mnemonic(&self) -> String {
+        "atomic.fence".to_string()
+    }
+
+    fn check_types(&self, _: &mut TypeStack) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct TableGet {
+    table_index: usize,
+}
+
+impl TableGet {
+    pub fn new(table_index: usize) -> Self {
+        Self { table_index }
+    }
+}
+
+impl Instruction for TableGet {
+    fn execute(
+        &self,
+        stack: &mut Stack,
+        ctx: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        _: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        let index = u32::try_from(stack.pop_value()?)?;
+        match ctx.table(self.table_index)?.get(index) {
+            Some(v) => stack.push_value(v)?,
+            None => return Ok(ControlInfo::Trap(Trap::TableOutOfBounds)),
+        }
+        Ok(ControlInfo::None)
+    }
+
+    fn mnemonic(&self) -> String {
+        "table.get".to_string()
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        types.pop(PrimitiveType::I32)?;
+        types.push(types.table_type(self.table_index)?);
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct TableSet {
+    table_index: usize,
+}
+
+impl TableSet {
+    pub fn new(table_index: usize) -> Self {
+        Self { table_index }
+    }
+}
+
+impl Instruction for TableSet {
+    fn execute(
+        &self,
+        stack: &mut Stack,
+        ctx: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        _: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        let value = stack.pop_value()?;
+        let index = u32::try_from(stack.pop_value()?)?;
+        let table = ctx.table_mut(self.table_index)?;
+        if value.t != table.element_type() {
+            return Err(Error::Misc("Operand type mismatch"));
+        }
+        if table.set(index, value).is_none() {
+            return Ok(ControlInfo::Trap(Trap::TableOutOfBounds));
         }
         Ok(ControlInfo::None)
     }
+
+    fn mnemonic(&self) -> String {
+        "table.set".to_string()
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        types.pop(types.table_type(self.table_index)?)?;
+        types.pop(PrimitiveType::I32)
+    }
+}
+
+#[derive(Clone)]
+pub struct TableSize {
+    table_index: usize,
+}
+
+impl TableSize {
+    pub fn new(table_index: usize) -> Self {
+        Self { table_index }
+    }
+}
+
+impl Instruction for TableSize {
+    fn execute(
+        &self,
+        stack: &mut Stack,
+        ctx: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        _: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        let size = ctx.table(self.table_index)?.size();
+        stack.push_value(Value::from(size as i32))?;
+        Ok(ControlInfo::None)
+    }
+
+    fn mnemonic(&self) -> String {
+        "table.size".to_string()
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        types.push(PrimitiveType::I32);
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct TableGrow {
+    table_index: usize,
+}
+
+impl TableGrow {
+    pub fn new(table_index: usize) -> Self {
+        Self { table_index }
+    }
+}
+
+impl Instruction for TableGrow {
+    fn execute(
+        &self,
+        stack: &mut Stack,
+        ctx: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        _: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        let delta = u32::try_from(stack.pop_value()?)?;
+        let value = stack.pop_value()?;
+        let table = ctx.table_mut(self.table_index)?;
+        if value.t != table.element_type() {
+            return Err(Error::Misc("Operand type mismatch"));
+        }
+        // Like memory.grow, failing gives -1 rather than a trap
+        let result = match table.grow(delta, value) {
+            Some(old_size) => old_size as i32,
+            None => -1,
+        };
+        stack.push_value(Value::from(result))?;
+        Ok(ControlInfo::None)
+    }
+
+    fn mnemonic(&self) -> String {
+        "table.grow".to_string()
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        types.pop(PrimitiveType::I32)?;
+        types.pop(types.table_type(self.table_index)?)?;
+        types.push(PrimitiveType::I32);
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct TableFill {
+    table_index: usize,
+}
+
+impl TableFill {
+    pub fn new(table_index: usize) -> Self {
+        Self { table_index }
+    }
+}
+
+impl Instruction for TableFill {
+    fn execute(
+        &self,
+        stack: &mut Stack,
+        ctx: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        _: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        let count = u32::try_from(stack.pop_value()?)?;
+        let value = stack.pop_value()?;
+        let offset = u32::try_from(stack.pop_value()?)?;
+        let table = ctx.table_mut(self.table_index)?;
+        if value.t != table.element_type() {
+            return Err(Error::Misc("Operand type mismatch"));
+        }
+        if table.fill(offset, value, count).is_none() {
+            return Ok(ControlInfo::Trap(Trap::TableOutOfBounds));
+        }
+        Ok(ControlInfo::None)
+    }
+
+    fn mnemonic(&self) -> String {
+        "table.fill".to_string()
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        types.pop(PrimitiveType::I32)?;
+        types.pop(types.table_type(self.table_index)?)?;
+        types.pop(PrimitiveType::I32)
+    }
+}
+
+#[derive(Clone)]
+pub struct TableCopy {
+    dst_table_index: usize,
+    src_table_index: usize,
+}
+
+impl TableCopy {
+    pub fn new(dst_table_index: usize, src_table_index: usize) -> Self {
+        Self {
+            dst_table_index,
+            src_table_index,
+        }
+    }
+}
+
+impl Instruction for TableCopy {
+    fn execute(
+        &self,
+        stack: &mut Stack,
+        ctx: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        _: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        let count = u32::try_from(stack.pop_value()?)?;
+        let src = u32::try_from(stack.pop_value()?)?;
+        let dst = u32::try_from(stack.pop_value()?)?;
+        let copied = if self.dst_table_index == self.src_table_index {
+            ctx.table_mut(self.dst_table_index)?
+                .copy_within(dst, src, count)
+        } else {
+            let src_table = ctx.table(self.src_table_index)?;
+            let elements = match src
+                .checked_add(count)
+                .and_then(|end| src_table.get_range(src, end))
+            {
+                Some(e) => e.to_vec(),
+                None => return Ok(ControlInfo::Trap(Trap::TableOutOfBounds)),
+            };
+            ctx.table_mut(self.dst_table_index)?
+                .set_range(dst, &elements)
+        };
+        if copied.is_none() {
+            return Ok(ControlInfo::Trap(Trap::TableOutOfBounds));
+        }
+        Ok(ControlInfo::None)
+    }
+
+    fn mnemonic(&self) -> String {
+        "table.copy".to_string()
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        if types.table_type(self.dst_table_index)? != types.table_type(self.src_table_index)? {
+            return Err(Error::Misc("Tables have different element types"));
+        }
+        for _ in 0..3 {
+            types.pop(PrimitiveType::I32)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct TableInit {
+    element_index: usize,
+    table_index: usize,
+}
+
+impl TableInit {
+    pub fn new(element_index: usize, table_index: usize) -> Self {
+        Self {
+            element_index,
+            table_index,
+        }
+    }
+}
+
+impl Instruction for TableInit {
+    fn execute(
+        &self,
+        stack: &mut Stack,
+        ctx: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        _: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        let count = u32::try_from(stack.pop_value()?)?;
+        let src = u32::try_from(stack.pop_value()?)?;
+        let dst = u32::try_from(stack.pop_value()?)?;
+        let segment = match ctx.elements.get(self.element_index) {
+            Some(s) => s,
+            None => return Err(Error::Misc("Element segment index is not valid")),
+        };
+        let elements = match src
+            .checked_add(count)
+            .and_then(|end| segment.get(src as usize..end as usize))
+        {
+            Some(e) => e,
+            None => return Ok(ControlInfo::Trap(Trap::TableOutOfBounds)),
+        };
+        // The table is borrowed from its field directly, since `elements` is still borrowed
+        let table = match ctx.tables.get_mut(self.table_index) {
+            Some(t) => t,
+            None => return Err(Error::Misc("Table index is not valid")),
+        };
+        if table.init(dst as u64, elements).is_none() {
+            return Ok(ControlInfo::Trap(Trap::TableOutOfBounds));
+        }
+        Ok(ControlInfo::None)
+    }
+
+    fn mnemonic(&self) -> String {
+        "table.init".to_string()
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        if types.element_type(self.element_index)? != types.table_type(self.table_index)? {
+            return Err(Error::Misc("Element segment type doesn't match the table"));
+        }
+        for _ in 0..3 {
+            types.pop(PrimitiveType::I32)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct ElemDrop {
+    element_index: usize,
+}
+
+impl ElemDrop {
+    pub fn new(element_index: usize) -> Self {
+        Self { element_index }
+    }
+}
+
+impl Instruction for ElemDrop {
+    fn execute(
+        &self,
+        _: &mut Stack,
+        ctx: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        _: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        match ctx.elements.get_mut(self.element_index) {
+            Some(s) => s.clear(),
+            None => return Err(Error::Misc("Element segment index is not valid")),
+        }
+        Ok(ControlInfo::None)
+    }
+
+    fn mnemonic(&self) -> String {
+        "elem.drop".to_string()
+    }
+
+    fn check_types(&self, _: &mut TypeStack) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct MemoryInit {
+    data_index: usize,
+    memory_index: usize,
+}
+
+impl MemoryInit {
+    pub fn new(data_index: usize, memory_index: usize) -> Self {
+        Self {
+            data_index,
+            memory_index,
+        }
+    }
+}
+
+impl Instruction for MemoryInit {
+    fn execute(
+        &self,
+        stack: &mut Stack,
+        ctx: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        _: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        let count = u32::try_from(stack.pop_value()?)?;
+        let src = u32::try_from(stack.pop_value()?)?;
+        let dst = u32::try_from(stack.pop_value()?)?;
+        let segment = match ctx.data.get(self.data_index) {
+            Some(s) => s,
+            None => return Err(Error::Misc("Data segment index is not valid")),
+        };
+        let bytes = match src
+            .checked_add(count)
+            .and_then(|end| segment.get(src as usize..end as usize))
+        {
+            Some(b) => b.to_vec(),
+            None => return Ok(ControlInfo::Trap(Trap::MemoryOutOfBounds)),
+        };
+        if ctx
+            .memory_mut(self.memory_index)?
+            .write_bytes(dst as u64, &bytes)
+            .is_none()
+        {
+            return Ok(ControlInfo::Trap(Trap::MemoryOutOfBounds));
+        }
+        Ok(ControlInfo::None)
+    }
+
+    fn mnemonic(&self) -> String {
+        "memory.init".to_string()
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        for _ in 0..3 {
+            types.pop(PrimitiveType::I32)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct DataDrop {
+    data_index: usize,
+}
+
+impl DataDrop {
+    pub fn new(data_index: usize) -> Self {
+        Self { data_index }
+    }
+}
+
+impl Instruction for DataDrop {
+    fn execute(
+        &self,
+        _: &mut Stack,
+        ctx: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        _: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        match ctx.data.get_mut(self.data_index) {
+            Some(s) => s.clear(),
+            None => return Err(Error::Misc("Data segment index is not valid")),
+        }
+        Ok(ControlInfo::None)
+    }
+
+    fn mnemonic(&self) -> String {
+        "data.drop".to_string()
+    }
+
+    fn check_types(&self, _: &mut TypeStack) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct RefNull {
+    t: PrimitiveType,
+}
+
+impl RefNull {
+    pub fn new(t: PrimitiveType) -> Self {
+        Self { t }
+    }
+}
+
+impl Instruction for RefNull {
+    fn execute(
+        &self,
+        stack: &mut Stack,
+        _: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        _: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        stack.push_value(Value::null(self.t))?;
+        Ok(ControlInfo::None)
+    }
+
+    fn mnemonic(&self) -> String {
+        "ref.null".to_string()
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        types.push(self.t);
+        Ok(())
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct RefIsNull {}
+
+impl RefIsNull {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Instruction for RefIsNull {
+    fn execute(
+        &self,
+        stack: &mut Stack,
+        _: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        _: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        let value = stack.pop_value()?;
+        if value.t != PrimitiveType::FuncRef && value.t != PrimitiveType::ExternRef {
+            return Err(Error::Misc("Operand type mismatch"));
+        }
+        let result = value.as_ref_unchecked().is_none() as i32;
+        stack.push_value(Value::from(result))?;
+        Ok(ControlInfo::None)
+    }
+
+    fn mnemonic(&self) -> String {
+        "ref.is_null".to_string()
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        match types.pop_any()? {
+            PrimitiveType::FuncRef | PrimitiveType::ExternRef => {
+                types.push(PrimitiveType::I32);
+                Ok(())
+            }
+            _ => Err(Error::Misc("Operand type mismatch")),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RefFunc {
+    function_index: usize,
+}
+
+impl RefFunc {
+    pub fn new(function_index: usize) -> Self {
+        Self { function_index }
+    }
+}
+
+impl Instruction for RefFunc {
+    fn execute(
+        &self,
+        stack: &mut Stack,
+        _: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        _: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        stack.push_value(Value::func_ref(self.function_index))?;
+        Ok(ControlInfo::None)
+    }
+
+    fn mnemonic(&self) -> String {
+        "ref.func".to_string()
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        types.push(PrimitiveType::FuncRef);
+        Ok(())
+    }
+}
+
+#[derive(Clone)]
+pub struct Branch {
+    branch_index: u32,
+}
+
+impl Branch {
+    pub fn new(branch_index: u32) -> Self {
+        Self { branch_index }
+    }
+}
+
+impl Instruction for Branch {
+    fn execute(
+        &self,
+        _: &mut Stack,
+        _: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        _: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        Ok(ControlInfo::Branch(self.branch_index))
+    }
+
+    fn mnemonic(&self) -> String {
+        "br".to_string()
+    }
+}
+
+#[derive(Clone)]
+pub struct BranchIf {
+    branch_index: u32,
+}
+
+impl BranchIf {
+    pub fn new(branch_index: u32) -> Self {
+        Self { branch_index }
+    }
+}
+
+impl Instruction for BranchIf {
+    fn execute(
+        &self,
+        stack: &mut Stack,
+        _: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        _: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        let condition = stack.pop_value()?;
+        if condition.t != PrimitiveType::I32 {
+            return Err(Error::Misc("Operand type mismatch"));
+        }
+        if condition.as_i32_unchecked() == 0 {
+            Ok(ControlInfo::None)
+        } else {
+            Ok(ControlInfo::Branch(self.branch_index))
+        }
+    }
+
+    fn mnemonic(&self) -> String {
+        "br_if".to_string()
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        // Only the condition is taken if the branch isn't
+        types.pop(PrimitiveType::I32)
+    }
+}
+
+/// Name the function an error came from, unless a function it called already has been
+fn in_function(function: &Function, function_index: usize, e: Error) -> Error {
+    match e {
+        Error::InFunction(..) => e,
+        e => {
+            let name = match function.name() {
+                Some(name) => name.to_string(),
+                None => format!("func[{}]", function_index),
+            };
+            Error::InFunction(name, Box::new(e))
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Call {
+    function_index: usize,
+}
+
+impl Call {
+    pub fn new(function_index: usize) -> Self {
+        Self { function_index }
+    }
+}
+
+impl Instruction for Call {
+    fn execute(
+        &self,
+        stack: &mut Stack,
+        ctx: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        functions: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        let function_index = self.function_index;
+        let called_function = match functions.get(function_index) {
+            Some(f) => f,
+            None => return Err(Error::Misc("Function index is not valid")),
+        };
+        match called_function.name() {
+            Some(name) => trace!(
+                ctx,
+                "Calling function {} (index {})",
+                name,
+                self.function_index
+            ),
+            None => trace!(ctx, "Calling function with index {}", self.function_index),
+        }
+        let mut args = ctx.take_values();
+        for _ in 0..called_function.num_params() {
+            args.push(stack.pop_value()?);
+        }
+        args.reverse();
+        match called_function.call(functions, ctx, args) {
+            Ok(results) => {
+                for v in &results {
+                    stack.push_value(*v)?;
+                }
+                ctx.recycle_values(results);
+            }
+            // Keep unwinding through the caller, which may have a `try` for it
+            Err(Error::UncaughtException(e)) => return Ok(ControlInfo::Throw(e)),
+            Err(e) => return Err(in_function(called_function, function_index, e)),
+        }
+        Ok(ControlInfo::None)
+    }
+
+    fn mnemonic(&self) -> String {
+        "call".to_string()
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        match types.module().get_function(self.function_index) {
+            Some(f) => types.apply(f.signature()),
+            None => Err(Error::Misc("Function index is not valid")),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct CallIndirect {
+    type_index: usize,
+    table_index: usize,
+}
+
+impl CallIndirect {
+    pub fn new(type_index: usize, table_index: usize) -> Self {
+        Self {
+            type_index,
+            table_index,
+        }
+    }
+}
+
+impl Instruction for CallIndirect {
+    fn execute(
+        &self,
+        stack: &mut Stack,
+        ctx: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        functions: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        let table_index = stack.pop_value()?;
+        if table_index.t != PrimitiveType::I32 {
+            return Err(Error::Misc("call_indirect index is not an i32"));
+        }
+        let function_index = match ctx
+            .table(self.table_index)?
+            .get(table_index.as_i32_unchecked() as u32)
+            .and_then(|r| r.as_ref_unchecked())
+        {
+            Some(n) => n,
+            None => return Ok(ControlInfo::Trap(Trap::UndefinedElement)),
+        };
+        let called_function = match functions.get(function_index) {
+            Some(f) => f,
+            None => return Err(Error::Misc("Table entry is not a valid function index")),
+        };
+        match ctx.function_types.get(self.type_index) {
+            Some(t) if t == called_function.signature() => (),
+            Some(_) => return Ok(ControlInfo::Trap(Trap::IndirectCallTypeMismatch)),
+            None => return Err(Error::Misc("call_indirect type index is not valid")),
+        }
+        trace!(
+            ctx,
+            "Calling function with index {} indirectly",
+            function_index
+        );
+        let mut args = ctx.take_values();
+        for _ in 0..called_function.num_params() {
+            args.push(stack.pop_value()?);
+        }
+        args.reverse();
+        match called_function.call(functions, ctx, args) {
+            Ok(results) => {
+                for v in &results {
+                    stack.push_value(*v)?;
+                }
+                ctx.recycle_values(results);
+            }
+            // Keep unwinding through the caller, which may have a `try` for it
+            Err(Error::UncaughtException(e)) => return Ok(ControlInfo::Throw(e)),
+            Err(e) => return Err(in_function(called_function, function_index, e)),
+        }
+        Ok(ControlInfo::None)
+    }
+
+    fn mnemonic(&self) -> String {
+        "call_indirect".to_string()
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        types.pop(PrimitiveType::I32)?;
+        match types.module().function_types().get(self.type_index) {
+            Some(t) => types.apply(t),
+            None => Err(Error::Misc("call_indirect type index is not valid")),
+        }
+    }
+}
+
+#[derive(Clone, Default)]
+pub struct Return {}
+
+impl Return {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl Instruction for Return {
+    fn execute(
+        &self,
+        _: &mut Stack,
+        _: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        _: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        Ok(ControlInfo::Return)
+    }
+
+    fn mnemonic(&self) -> String {
+        "return".to_string()
+    }
+}
+
+#[derive(Clone)]
+pub enum BlockContinuation {
+    Loop,
+    Branch,
+}
+
+#[derive(Clone)]
+pub struct Block {
+    continuation: BlockContinuation,
+    /// Number of values the block takes from the stack when it is entered
+    params: usize,
+    /// Number of values the block leaves on the stack when it is exited
+    arity: usize,
+    /// Types of the params and results, if the block was built from its block type
+    r#type: Option<FunctionType>,
+    instructions: Vec<Box<dyn Instruction>>,
+}
+
+impl Block {
+    pub fn new(
+        continuation: BlockContinuation,
+        arity: usize,
+        instructions: Vec<Box<dyn Instruction>>,
+    ) -> Self {
+        Self {
+            continuation,
+            params: 0,
+            arity,
+            r#type: None,
+            instructions,
+        }
+    }
+
+    /// A block with the params and results of `block_type`
+    pub fn with_type(
+        continuation: BlockContinuation,
+        block_type: &FunctionType,
+        instructions: Vec<Box<dyn Instruction>>,
+    ) -> Self {
+        Self {
+            continuation,
+            params: block_type.num_params(),
+            arity: block_type.returns.len(),
+            r#type: Some(block_type.clone()),
+            instructions,
+        }
+    }
+
+    /// Height of the stack below the block's params, which the block unwinds to when exited
+    fn entry_height(&self, stack: &Stack) -> Result<usize, Error> {
+        match stack.height().checked_sub(self.params) {
+            Some(n) => Ok(n),
+            None => Err(Error::StackViolation),
+        }
+    }
+
+    /// Run the block as if it was entered with the stack at `entry_height`. Anything pushed
+    /// above that height is discarded when the block is exited.
+    fn execute_from(
+        &self,
+        entry_height: usize,
+        stack: &mut Stack,
+        ctx: &mut ExecutionContext,
+        locals: &mut Vec<Value>,
+        functions: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        // This outer loop is being used more as a goto than an actual loop.
+        let mut loop_restart;
+        loop {
+            loop_restart = false;
+            for inst in &self.instructions {
+                if let Err(t) = ctx.before_execute(inst.as_ref()) {
+                    return Ok(ControlInfo::Trap(t));
+                }
+                match inst.execute(stack, ctx, locals, functions) {
+                    // Instruction returned a branch
+                    Ok(ControlInfo::Branch(branch_levels)) => {
+                        if branch_levels == 0 {
+                            // If we are a loop, continue execution from the beginning of our instrucitons.
+                            // Otherwise, halt execution and return to our parent block.
+                            match self.continuation {
+                                BlockContinuation::Loop => {
+                                    // Branching to a loop passes it its params again
+                                    trace!(ctx, "Branching to loop at depth 0");
+                                    stack.unwind(entry_height, self.params)?;
+                                    loop_restart = true;
+                                }
+                                BlockContinuation::Branch => {
+                                    trace!(ctx, "Branching out of a block with depth 0");
+                                    stack.unwind(entry_height, self.arity)?;
+                                    return Ok(ControlInfo::None);
+                                }
+                            }
+                        } else {
+                            // Both loops and branches need to pass the control information up to the higher block
+                            let new_depth = branch_levels - 1;
+                            trace!(
+                                ctx,
+                                "Branching out of block from branch depth {} to {}",
+                                branch_levels,
+                                new_depth
+                            );
+                            return Ok(ControlInfo::Branch(new_depth));
+                        }
+                    }
+                    Ok(ControlInfo::Return) => {
+                        // Unwrap up to the function's call handler
+                        trace!(ctx, "Unwrapping return!");
+                        return Ok(ControlInfo::Return);
+                    }
+                    Ok(ControlInfo::Trap(t)) => {
+                        // Traps abort the whole call, so pass them straight up as well
+                        trace!(ctx, "Unwrapping trap {:?}", t);
+                        return Ok(ControlInfo::Trap(t));
+                    }
+                    Ok(ControlInfo::Throw(e)) => {
+                        trace!(ctx, "Unwinding exception with tag {}", e.tag);
+                        return Ok(ControlInfo::Throw(e));
+                    }
+                    Ok(_) => (),
+                    Err(e) => {
+                        return Err(e);
+                    }
+                }
+                if loop_restart {
+                    break;
+                }
+            }
+            // Getting here implies that we need to fall through the block
+            if !loop_restart {
+                break;
+            }
+        }
+        stack.unwind(entry_height, self.arity)?;
+        Ok(ControlInfo::None)
+    }
+}
+
+impl Instruction for Block {
+    fn execute(
+        &self,
+        stack: &mut Stack,
+        ctx: &mut ExecutionContext,
+        locals: &mut Vec<Value>,
+        functions: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        let entry_height = self.entry_height(stack)?;
+        self.execute_from(entry_height, stack, ctx, locals, functions)
+    }
+
+    fn mnemonic(&self) -> String {
+        match self.continuation {
+            BlockContinuation::Loop => "loop".to_string(),
+            BlockContinuation::Branch => "block".to_string(),
+        }
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        match &self.r#type {
+            Some(t) => types.apply(t),
+            None => Err(Error::Misc("Block type is not known")),
+        }
+    }
+}
+
+/// Runs `then_block` if the popped condition is nonzero, otherwise `else_block`. Without an
+/// `else`, a false condition passes the block's params through as its results
+#[derive(Clone)]
+pub struct If {
+    then_block: Block,
+    else_block: Option<Block>,
+}
+
+impl If {
+    pub fn new(then_block: Block, else_block: Option<Block>) -> Self {
+        Self {
+            then_block,
+            else_block,
+        }
+    }
+}
+
+impl Instruction for If {
+    fn execute(
+        &self,
+        stack: &mut Stack,
+        ctx: &mut ExecutionContext,
+        locals: &mut Vec<Value>,
+        functions: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        let condition = stack.pop_value()?;
+        if condition.t != PrimitiveType::I32 {
+            return Err(Error::Misc("Operand type mismatch"));
+        }
+        // Both arms share the block type, so whichever runs starts from the same height, just
+        // below the params that are left once the condition is popped
+        let entry_height = self.then_block.entry_height(stack)?;
+        if condition.as_i32_unchecked() != 0 {
+            self.then_block
+                .execute_from(entry_height, stack, ctx, locals, functions)
+        } else {
+            match &self.else_block {
+                Some(b) => b.execute_from(entry_height, stack, ctx, locals, functions),
+                None => Ok(ControlInfo::None),
+            }
+        }
+    }
+
+    fn mnemonic(&self) -> String {
+        "if".to_string()
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        types.pop(PrimitiveType::I32)?;
+        self.then_block.check_types(types)
+    }
+}
+
+/// A handler of a `try` block, for exceptions with the given tag or, for `catch_all`, any tag
+#[derive(Clone)]
+pub struct Catch {
+    tag: Option<usize>,
+    handler: Block,
+}
+
+impl Catch {
+    pub fn new(tag: Option<usize>, handler: Block) -> Self {
+        Self { tag, handler }
+    }
+}
+
+/// A block whose exceptions are passed to the first matching handler
+#[derive(Clone)]
+pub struct Try {
+    body: Block,
+    catches: Vec<Catch>,
+}
+
+impl Try {
+    pub fn new(body: Block, catches: Vec<Catch>) -> Self {
+        Self { body, catches }
+    }
+}
+
+impl Instruction for Try {
+    fn execute(
+        &self,
+        stack: &mut Stack,
+        ctx: &mut ExecutionContext,
+        locals: &mut Vec<Value>,
+        functions: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        let entry_height = self.body.entry_height(stack)?;
+        let exception = match self
+            .body
+            .execute_from(entry_height, stack, ctx, locals, functions)?
+        {
+            ControlInfo::Throw(e) => e,
+            c => return Ok(c),
+        };
+        let catch = match self
+            .catches
+            .iter()
+            .find(|c| c.tag.is_none() || c.tag == Some(exception.tag))
+        {
+            Some(c) => c,
+            None => return Ok(ControlInfo::Throw(exception)),
+        };
+        trace!(ctx, "Caught exception with tag {}", exception.tag);
+
+        // The handler replaces the body, starting from the stack as it was on entry
+        stack.unwind(entry_height, 0)?;
+        if catch.tag.is_some() {
+            for v in exception.values {
+                stack.push_value(v)?;
+            }
+        }
+        catch
+            .handler
+            .execute_from(entry_height, stack, ctx, locals, functions)
+    }
+
+    fn mnemonic(&self) -> String {
+        "try".to_string()
+    }
+
+    fn check_types(&self, types: &mut TypeStack) -> Result<(), Error> {
+        self.body.check_types(types)
+    }
+}
+
+#[derive(Clone)]
+pub struct Throw {
+    tag: usize,
+}
+
+impl Throw {
+    pub fn new(tag: usize) -> Self {
+        Self { tag }
+    }
+}
+
+impl Instruction for Throw {
+    fn execute(
+        &self,
+        stack: &mut Stack,
+        ctx: &mut ExecutionContext,
+        _: &mut Vec<Value>,
+        _: &[Function],
+    ) -> Result<ControlInfo, Error> {
+        let num_values = match ctx.tags.get(self.tag) {
+            Some(t) => t.num_params(),
+            None => return Err(Error::Misc("Tag index is not valid")),
+        };
+        let mut values = Vec::new();
+        for _ in 0..num_values {
+            values.push(stack.pop_value()?);
+        }
+        values.reverse();
+        Ok(ControlInfo::Throw(Exception {
+            tag: self.tag,
+            values,
+        }))
+    }
+
+    fn mnemonic(&self) -> String {
+        "throw".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::*;
+
+    /// Execute `inst` on its own, with `operands` on the stack, giving its control info and
+    /// the stack afterwards, bottom first
+    fn execute(
+        inst: &dyn Instruction,
+        ctx: &mut ExecutionContext,
+        operands: &[Value],
+    ) -> Result<(ControlInfo, Vec<TypedValue>), Error> {
+        let mut stack = Stack::with_values(Vec::new(), DEFAULT_MAX_STACK_DEPTH);
+        for v in operands {
+            stack.push_value(*v)?;
+        }
+        let control = inst.execute(&mut stack, ctx, &mut Vec::new(), &[])?;
+        Ok((control, stack.values.iter().map(Value::unpack).collect()))
+    }
+
+    /// Execute `inst` with a fresh context, expecting it to carry on to the next instruction
+    fn results(inst: &dyn Instruction, operands: &[Value]) -> Vec<TypedValue> {
+        match execute(inst, &mut ExecutionContext::default(), operands).unwrap() {
+            (ControlInfo::None, values) => values,
+            _ => panic!(
+                "{} didn't carry on to the next instruction",
+                inst.mnemonic()
+            ),
+        }
+    }
+
+    /// Execute `inst` with a fresh context, expecting it to trap
+    fn trapped(inst: &dyn Instruction, operands: &[Value]) -> Trap {
+        trapped_in(inst, &mut ExecutionContext::default(), operands)
+    }
+
+    /// Execute `inst` in `ctx`, expecting it to trap either way a trap can be reported
+    fn trapped_in(inst: &dyn Instruction, ctx: &mut ExecutionContext, operands: &[Value]) -> Trap {
+        match execute(inst, ctx, operands) {
+            Ok((ControlInfo::Trap(t), _)) => t,
+            Ok((_, values)) => panic!("{} didn't trap, giving {:?}", inst.mnemonic(), values),
+            Err(e) => trap::<()>(Err(e)),
+        }
+    }
+
+    /// A context with one memory of `pages` pages
+    fn with_memory(pages: u32) -> ExecutionContext {
+        let mut ctx = ExecutionContext::default();
+        ctx.memories.push(Memory::new(pages, pages));
+        ctx
+    }
+
+    fn memarg(offset: u32) -> MemArg {
+        MemArg {
+            align: 0,
+            memory_index: 0,
+            offset,
+        }
+    }
+
+    #[test]
+    fn convert_signedness() {
+        let text = r#"(module
+            (func (export "s32") (param i32) (result f64) local.get 0 f64.convert_i32_s)
+            (func (export "u32") (param i32) (result f64) local.get 0 f64.convert_i32_u)
+            (func (export "s64") (param i64) (result f32) local.get 0 f32.convert_i64_s)
+            (func (export "u64") (param i64) (result f32) local.get 0 f32.convert_i64_u))"#;
+        let mut module = instantiate(text);
+        let minus_one = Value::i32(-1);
+        assert_eq!(
+            call(&mut module, "s32", &[minus_one]).unwrap(),
+            [TypedValue::F64(-1.0)]
+        );
+        assert_eq!(
+            call(&mut module, "u32", &[minus_one]).unwrap(),
+            [TypedValue::F64(4294967295.0)]
+        );
+        let minus_one = Value::i64(-1);
+        assert_eq!(
+            call(&mut module, "s64", &[minus_one]).unwrap(),
+            [TypedValue::F32(-1.0)]
+        );
+        assert_eq!(
+            call(&mut module, "u64", &[minus_one]).unwrap(),
+            [TypedValue::F32(18446744073709551615.0)]
+        );
+    }
+
+    #[test]
+    fn every_conversion_takes_its_operand_type() {
+        let conversions = [
+            ("i32.wrap_i64", "i64", "i32"),
+            ("i32.trunc_f32_s", "f32", "i32"),
+            ("i32.trunc_f32_u", "f32", "i32"),
+            ("i32.trunc_f64_s", "f64", "i32"),
+            ("i32.trunc_f64_u", "f64", "i32"),
+            ("i64.extend_i32_s", "i32", "i64"),
+            ("i64.extend_i32_u", "i32", "i64"),
+            ("i64.trunc_f32_s", "f32", "i64"),
+            ("i64.trunc_f32_u", "f32", "i64"),
+            ("i64.trunc_f64_s", "f64", "i64"),
+            ("i64.trunc_f64_u", "f64", "i64"),
+            ("f32.convert_i32_s", "i32", "f32"),
+            ("f32.convert_i32_u", "i32", "f32"),
+            ("f32.convert_i64_s", "i64", "f32"),
+            ("f32.convert_i64_u", "i64", "f32"),
+            ("f32.demote_f64", "f64", "f32"),
+            ("f64.convert_i32_s", "i32", "f64"),
+            ("f64.convert_i32_u", "i32", "f64"),
+            ("f64.convert_i64_s", "i64", "f64"),
+            ("f64.convert_i64_u", "i64", "f64"),
+            ("f64.promote_f32", "f32", "f64"),
+            ("i32.reinterpret_f32", "f32", "i32"),
+            ("i64.reinterpret_f64", "f64", "i64"),
+            ("f32.reinterpret_i32", "i32", "f32"),
+            ("f64.reinterpret_i64", "i64", "f64"),
+            ("i32.trunc_sat_f32_s", "f32", "i32"),
+            ("i32.trunc_sat_f32_u", "f32", "i32"),
+            ("i32.trunc_sat_f64_s", "f64", "i32"),
+            ("i32.trunc_sat_f64_u", "f64", "i32"),
+            ("i64.trunc_sat_f32_s", "f32", "i64"),
+            ("i64.trunc_sat_f32_u", "f32", "i64"),
+            ("i64.trunc_sat_f64_s", "f64", "i64"),
+            ("i64.trunc_sat_f64_u", "f64", "i64"),
+        ];
+        let type_of = |name: &str| match name {
+            "i32" => PrimitiveType::I32,
+            "i64" => PrimitiveType::I64,
+            "f32" => PrimitiveType::F32,
+            _ => PrimitiveType::F64,
+        };
+        for (inst, src, dst) in conversions {
+            let text = format!(
+                r#"(module (func (export "f") (param {}) (result {}) local.get 0 {}))"#,
+                src, dst, inst
+            );
+            let arg = Value::default_for(type_of(src));
+            let results = instantiate(&text).call("f", vec![arg]).unwrap();
+            assert_eq!(results.len(), 1, "{}", inst);
+            assert!(results[0].get_type() == type_of(dst), "{}", inst);
+        }
+    }
+
+    #[test]
+    fn wrap_takes_an_i64() {
+        let wrap = CvtOp::new(CvtOpType::Wrap);
+        assert_eq!(
+            results(&wrap, &[Value::i64(0x1_0000_0002)]),
+            [TypedValue::I32(2)]
+        );
+        let mut ctx = ExecutionContext::default();
+        assert!(execute(&wrap, &mut ctx, &[Value::i32(2)]).is_err());
+    }
+
+    #[test]
+    fn trap_escapes_nested_blocks() {
+        let text = r#"(module (memory 1)
+            (func (export "f") (result i32)
+                (loop (result i32)
+                    (block (result i32)
+                        i32.const 65536
+                        i32.load))))"#;
+        assert_eq!(trap(run(text, "f", &[])), Trap::MemoryOutOfBounds);
+    }
+
+    #[test]
+    fn early_return_inside_if() {
+        let text = r#"(module
+            (func (export "f") (param i32) (result i32)
+                (if (local.get 0) (then (return (i32.const 1))))
+                i32.const 2))"#;
+        let mut module = instantiate(text);
+        assert_eq!(
+            call(&mut module, "f", &[Value::i32(1)]).unwrap(),
+            [TypedValue::I32(1)]
+        );
+        assert_eq!(
+            call(&mut module, "f", &[Value::i32(0)]).unwrap(),
+            [TypedValue::I32(2)]
+        );
+    }
+
+    #[test]
+    fn branch_out_of_block_discards_extra_values() {
+        let text = r#"(module
+            (func (export "f") (result i32)
+                (block (result i32)
+                    i32.const 1
+                    i32.const 2
+                    br 0)))"#;
+        assert_eq!(run(text, "f", &[]).unwrap(), [TypedValue::I32(2)]);
+    }
+
+    #[test]
+    fn binary_ops_reject_mismatched_operands() {
+        use PrimitiveType::*;
+        let mut ops: Vec<(Box<dyn Instruction>, PrimitiveType)> = Vec::new();
+        for t in [I32, I64] {
+            for op in [
+                IBinOpType::Add,
+                IBinOpType::Sub,
+                IBinOpType::Mul,
+                IBinOpType::Div(Signedness::Signed),
+                IBinOpType::Div(Signedness::Unsigned),
+                IBinOpType::Rem(Signedness::Signed),
+                IBinOpType::Rem(Signedness::Unsigned),
+                IBinOpType::And,
+                IBinOpType::Or,
+                IBinOpType::Xor,
+                IBinOpType::Shl,
+                IBinOpType::Shr(Signedness::Signed),
+                IBinOpType::Shr(Signedness::Unsigned),
+                IBinOpType::Rotl,
+                IBinOpType::Rotr,
+            ] {
+                ops.push((Box::new(IBinOp::new(t, op)), t));
+            }
+        }
+        for t in [F32, F64] {
+            for op in [
+                FBinOpType::Add,
+                FBinOpType::Sub,
+                FBinOpType::Mul,
+                FBinOpType::Div,
+                FBinOpType::Min,
+                FBinOpType::Max,
+                FBinOpType::CopySign,
+            ] {
+                ops.push((Box::new(FBinOp::new(t, op)), t));
+            }
+        }
+
+        let module = Module::default();
+        let function = Function::new(FunctionType::default());
+        for (inst, t) in &ops {
+            for other in [I32, I64, F32, F64] {
+                if other == *t {
+                    continue;
+                }
+                // Both operands of the wrong type, and only one of them
+                for (a, b) in [(other, other), (*t, other), (other, *t)] {
+                    let operands = [Value::default_for(a), Value::default_for(b)];
+                    let mut ctx = ExecutionContext::default();
+                    let result = execute(inst.as_ref(), &mut ctx, &operands);
+                    assert!(
+                        matches!(result, Err(Error::Misc(_))),
+                        "{} ran on {} and {}",
+                        inst.mnemonic(),
+                        a,
+                        b
+                    );
+
+                    let mut types = TypeStack::new(&module, &function);
+                    types.push(a);
+                    types.push(b);
+                    assert!(inst.check_types(&mut types).is_err());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn reinterpret_preserves_bits() {
+        let text = r#"(module
+            (func (export "to_f32") (param i32) (result f32)
+                local.get 0
+                f32.reinterpret_i32)
+            (func (export "round_trip32") (param i32) (result i32)
+                local.get 0
+                f32.reinterpret_i32
+                i32.reinterpret_f32)
+            (func (export "to_f64") (param i64) (result f64)
+                local.get 0
+                f64.reinterpret_i64)
+            (func (export "round_trip64") (param i64) (result i64)
+                local.get 0
+                f64.reinterpret_i64
+                i64.reinterpret_f64))"#;
+        let mut module = instantiate(text);
+
+        let nan = module
+            .call("to_f32", vec![Value::i32(0x7FC0_0000)])
+            .unwrap();
+        assert!(nan[0].get_type() == PrimitiveType::F32);
+        assert_eq!(nan[0].as_f32_unchecked().to_bits(), 0x7FC0_0000);
+        // A signaling NaN with a payload must come back unchanged too
+        for bits in [0x7FC0_0000, 0x7F80_0001, -1, i32::MIN] {
+            assert_eq!(
+                call(&mut module, "round_trip32", &[Value::i32(bits)]).unwrap(),
+                [TypedValue::I32(bits)]
+            );
+        }
+
+        let bits = 0x7FF8_0000_0000_0001_i64;
+        let nan = module.call("to_f64", vec![Value::i64(bits)]).unwrap();
+        assert_eq!(nan[0].as_f64_unchecked().to_bits(), bits as u64);
+        for bits in [bits, 0x7FF0_0000_0000_0001, -1, i64::MIN] {
+            assert_eq!(
+                call(&mut module, "round_trip64", &[Value::i64(bits)]).unwrap(),
+                [TypedValue::I64(bits)]
+            );
+        }
+    }
+
+    #[test]
+    fn float_min_max_follow_the_nan_and_zero_rules() {
+        use PrimitiveType::*;
+        let min32 = FBinOp::new(F32, FBinOpType::Min);
+        let max32 = FBinOp::new(F32, FBinOpType::Max);
+        let min64 = FBinOp::new(F64, FBinOpType::Min);
+        let max64 = FBinOp::new(F64, FBinOpType::Max);
+        let bits32 = |inst: &FBinOp, a: f32, b: f32| match results(inst, &[a.into(), b.into()])[..]
+        {
+            [TypedValue::F32(x)] => x.to_bits(),
+            ref other => panic!("unexpected results {:?}", other),
+        };
+        let bits64 = |inst: &FBinOp, a: f64, b: f64| match results(inst, &[a.into(), b.into()])[..]
+        {
+            [TypedValue::F64(x)] => x.to_bits(),
+            ref other => panic!("unexpected results {:?}", other),
+        };
+
+        // Either order of the zeroes gives the same answer
+        for (a, b) in [(0.0, -0.0), (-0.0, 0.0)] {
+            assert_eq!(bits32(&min32, a, b), (-0.0_f32).to_bits());
+            assert_eq!(bits32(&max32, a, b), 0.0_f32.to_bits());
+            assert_eq!(bits64(&min64, a as f64, b as f64), (-0.0_f64).to_bits());
+            assert_eq!(bits64(&max64, a as f64, b as f64), 0.0_f64.to_bits());
+        }
+
+        // A NaN in either place gives the canonical NaN, whatever its payload was
+        let nan32 = f32::from_bits(0x7fa0_0001);
+        let nan64 = f64::from_bits(0x7ff4_0000_0000_0001);
+        for (a, b) in [(nan32, 1.0), (1.0, nan32)] {
+            assert_eq!(bits32(&min32, a, b), 0x7fc0_0000);
+            assert_eq!(bits32(&max32, a, b), 0x7fc0_0000);
+        }
+        for (a, b) in [(nan64, 1.0), (1.0, nan64)] {
+            assert_eq!(bits64(&min64, a, b), 0x7ff8_0000_0000_0000);
+            assert_eq!(bits64(&max64, a, b), 0x7ff8_0000_0000_0000);
+        }
+
+        assert_eq!(bits32(&min32, -1.0, 2.0), (-1.0_f32).to_bits());
+        assert_eq!(bits64(&max64, -1.0, 2.0), 2.0_f64.to_bits());
+    }
+
+    #[test]
+    fn table_get_set_round_trip() {
+        let text = r#"(module
+            (table 2 4 funcref)
+            (func $f)
+            (elem declare func $f)
+            (func (export "set") (param i32)
+                (table.set (local.get 0) (ref.func $f)))
+            (func (export "is_null") (param i32) (result i32)
+                (ref.is_null (table.get (local.get 0))))
+            (func (export "grow") (result i32)
+                (table.grow (ref.null func) (i32.const 2)))
+            (func (export "size") (result i32)
+                table.size)
+            (func (export "fill") (param i32 i32)
+                (table.fill (local.get 0) (ref.func $f) (local.get 1))))"#;
+        let mut module = instantiate(text);
+        let is_null = |module: &mut Module, i| call(module, "is_null", &[Value::i32(i)]);
+        assert_eq!(is_null(&mut module, 1).unwrap(), [TypedValue::I32(1)]);
+        call(&mut module, "set", &[Value::i32(1)]).unwrap();
+        assert_eq!(is_null(&mut module, 1).unwrap(), [TypedValue::I32(0)]);
+        assert_eq!(is_null(&mut module, 0).unwrap(), [TypedValue::I32(1)]);
+        assert_eq!(
+            module.table(0).unwrap().get(1).unwrap().unpack(),
+            TypedValue::FuncRef(Some(0))
+        );
+
+        assert_eq!(trap(is_null(&mut module, 2)), Trap::TableOutOfBounds);
+        assert_eq!(
+            trap(call(&mut module, "set", &[Value::i32(2)])),
+            Trap::TableOutOfBounds
+        );
+
+        assert_eq!(
+            call(&mut module, "grow", &[]).unwrap(),
+            [TypedValue::I32(2)]
+        );
+        assert_eq!(
+            call(&mut module, "size", &[]).unwrap(),
+            [TypedValue::I32(4)]
+        );
+        assert_eq!(
+            call(&mut module, "grow", &[]).unwrap(),
+            [TypedValue::I32(-1)]
+        );
+        call(&mut module, "fill", &[Value::i32(2), Value::i32(2)]).unwrap();
+        assert_eq!(is_null(&mut module, 3).unwrap(), [TypedValue::I32(0)]);
+        let fill = call(&mut module, "fill", &[Value::i32(3), Value::i32(2)]);
+        assert_eq!(trap(fill), Trap::TableOutOfBounds);
+    }
+
+    #[test]
+    fn atomic_add_round_trip_and_unaligned_trap() {
+        let text = r#"(module
+            (memory 1 1 shared)
+            (func (export "store") (param i32 i32)
+                (i32.atomic.store (local.get 0) (local.get 1)))
+            (func (export "load") (param i32) (result i32)
+                (i32.atomic.load (local.get 0)))
+            (func (export "add") (param i32 i32) (result i32)
+                (i32.atomic.rmw.add (local.get 0) (local.get 1)))
+            (func (export "notify") (param i32) (result i32)
+                (memory.atomic.notify (local.get 0) (i32.const 1))))"#;
+        let mut module = instantiate(text);
+        call(&mut module, "store", &[Value::i32(8), Value::i32(40)]).unwrap();
+        // rmw.add gives the value from before the add
+        let added = call(&mut module, "add", &[Value::i32(8), Value::i32(2)]).unwrap();
+        assert_eq!(added, [TypedValue::I32(40)]);
+        let loaded = call(&mut module, "load", &[Value::i32(8)]).unwrap();
+        assert_eq!(loaded, [TypedValue::I32(42)]);
+        // No other thread can be waiting
+        let woken = call(&mut module, "notify", &[Value::i32(8)]).unwrap();
+        assert_eq!(woken, [TypedValue::I32(0)]);
+
+        for name in ["load", "notify"] {
+            let result = call(&mut module, name, &[Value::i32(2)]);
+            assert_eq!(trap(result), Trap::UnalignedAtomic);
+        }
+        let result = call(&mut module, "add", &[Value::i32(6), Value::i32(1)]);
+        assert_eq!(trap(result), Trap::UnalignedAtomic);
+        let result = call(&mut module, "store", &[Value::i32(1), Value::i32(1)]);
+        assert_eq!(trap(result), Trap::UnalignedAtomic);
+    }
+
+    #[test]
+    fn try_catches_a_thrown_i32() {
+        let text = r#"(module
+            (tag $e (param i32))
+            (tag $other)
+            (func $raise (param i32)
+                (throw $e (local.get 0)))
+            (func (export "catch") (param i32) (result i32)
+                try (result i32)
+                    local.get 0
+                    call $raise
+                    i32.const 0
+                catch $e
+                    i32.const 1
+                    i32.add
+                end)
+            (func (export "catch_all") (result i32)
+                try (result i32)
+                    throw $other
+                catch $e
+                catch_all
+                    i32.const 7
+                end)
+            (func (export "uncaught") (local i32)
+                try
+                    throw $other
+                catch $e
+                    local.set 0
+                end)
+            (func (export "no_throw") (result i32)
+                try (result i32)
+                    i32.const 5
+                catch $e
+                end))"#;
+        let mut module = instantiate(text);
+        assert_eq!(
+            call(&mut module, "catch", &[Value::i32(41)]).unwrap(),
+            [TypedValue::I32(42)]
+        );
+        assert_eq!(
+            call(&mut module, "catch_all", &[]).unwrap(),
+            [TypedValue::I32(7)]
+        );
+        assert_eq!(
+            call(&mut module, "no_throw", &[]).unwrap(),
+            [TypedValue::I32(5)]
+        );
+        let uncaught = call(&mut module, "uncaught", &[]);
+        assert!(matches!(
+            uncaught.as_ref().map_err(Error::root),
+            Err(Error::UncaughtException(e)) if e.tag == 1
+        ));
+    }
+
+    #[test]
+    fn integer_binary_ops() {
+        use IBinOpType::*;
+        use Signedness::*;
+        let i32_cases = [
+            (Add, i32::MAX, 1, i32::MIN),
+            (Sub, i32::MIN, 1, i32::MAX),
+            (Mul, 0x1000_0000, 16, 0),
+            (Mul, -3, 7, -21),
+            (Div(Signed), -7, 2, -3),
+            (Div(Unsigned), -7, 2, 0x7FFF_FFFC),
+            (Div(Signed), i32::MIN, 2, -0x4000_0000),
+            (Rem(Signed), -7, 2, -1),
+            (Rem(Unsigned), -7, 2, 1),
+            (Rem(Signed), i32::MIN, -1, 0),
+            (And, 0b1100, 0b1010, 0b1000),
+            (Or, 0b1100, 0b1010, 0b1110),
+            (Xor, 0b1100, 0b1010, 0b0110),
+            (Shl, 1, 31, i32::MIN),
+            (Shl, 1, 33, 2),
+            (Shr(Signed), i32::MIN, 31, -1),
+            (Shr(Unsigned), i32::MIN, 31, 1),
+            (Shr(Unsigned), -1, 32, -1),
+            (Rotl, 0x8000_0001_u32 as i32, 1, 3),
+            (Rotr, 3, 1, 0x8000_0001_u32 as i32),
+            (Rotl, 0x1234_5678, 36, 0x2345_6781),
+        ];
+        for (op, a, b, expected) in i32_cases {
+            let inst = IBinOp::new(PrimitiveType::I32, op);
+            assert_eq!(
+                results(&inst, &[Value::i32(a), Value::i32(b)]),
+                [TypedValue::I32(expected)],
+                "{} {} {}",
+                inst.mnemonic(),
+                a,
+                b
+            );
+        }
+
+        let i64_cases = [
+            (Add, i64::MAX, 1, i64::MIN),
+            (Sub, 0, 1, -1),
+            (Mul, i64::MAX, 2, -2),
+            (Div(Signed), -7, 2, -3),
+            (Div(Unsigned), -2, 2, i64::MAX),
+            (Rem(Signed), -7, -2, -1),
+            (Rem(Unsigned), -1, 10, 5),
+            (Rem(Signed), i64::MIN, -1, 0),
+            (Shl, 1, 65, 2),
+            (Shr(Signed), -8, 1, -4),
+            (Shr(Unsigned), -8, 1, i64::MAX - 3),
+            (Rotl, i64::MIN, 1, 1),
+            (Rotr, 1, 1, i64::MIN),
+        ];
+        for (op, a, b, expected) in i64_cases {
+            let inst = IBinOp::new(PrimitiveType::I64, op);
+            assert_eq!(
+                results(&inst, &[Value::i64(a), Value::i64(b)]),
+                [TypedValue::I64(expected)],
+                "{} {} {}",
+                inst.mnemonic(),
+                a,
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn integer_division_traps() {
+        use IBinOpType::*;
+        use Signedness::*;
+        for op in [Div(Signed), Div(Unsigned), Rem(Signed), Rem(Unsigned)] {
+            let inst = IBinOp::new(PrimitiveType::I32, op.clone());
+            let operands = [Value::i32(1), Value::i32(0)];
+            assert_eq!(trapped(&inst, &operands), Trap::DivideByZero);
+            let inst = IBinOp::new(PrimitiveType::I64, op);
+            let operands = [Value::i64(1), Value::i64(0)];
+            assert_eq!(trapped(&inst, &operands), Trap::DivideByZero);
+        }
+
+        // Only signed division overflows, and it does so with a different trap
+        let inst = IBinOp::new(PrimitiveType::I32, Div(Signed));
+        let operands = [Value::i32(i32::MIN), Value::i32(-1)];
+        assert_eq!(trapped(&inst, &operands), Trap::IntegerOverflow);
+        let inst = IBinOp::new(PrimitiveType::I64, Div(Signed));
+        let operands = [Value::i64(i64::MIN), Value::i64(-1)];
+        assert_eq!(trapped(&inst, &operands), Trap::IntegerOverflow);
+        let inst = IBinOp::new(PrimitiveType::I32, Div(Unsigned));
+        let operands = [Value::i32(i32::MIN), Value::i32(-1)];
+        assert_eq!(results(&inst, &operands), [TypedValue::I32(0)]);
+    }
+
+    #[test]
+    fn float_binary_ops() {
+        use FBinOpType::*;
+        let f32_cases = [
+            (Add, 1.5, 2.25, 3.75),
+            (Sub, 1.0, 3.0, -2.0),
+            (Mul, -2.0, 0.5, -1.0),
+            (Div, 1.0, 0.0, f32::INFINITY),
+            (Div, -1.0, 0.0, f32::NEG_INFINITY),
+            (Min, 1.0, -1.0, -1.0),
+            (Max, 1.0, -1.0, 1.0),
+            (CopySign, 2.0, -0.0, -2.0),
+            (CopySign, -2.0, 1.0, 2.0),
+        ];
+        for (op, a, b, expected) in f32_cases {
+            let inst = FBinOp::new(PrimitiveType::F32, op);
+            assert_eq!(
+                results(&inst, &[Value::f32(a), Value::f32(b)]),
+                [TypedValue::F32(expected)]
+            );
+        }
+        let inst = FBinOp::new(PrimitiveType::F64, Add);
+        assert_eq!(
+            results(&inst, &[Value::f64(0.1), Value::f64(0.2)]),
+            [TypedValue::F64(0.1 + 0.2)]
+        );
+
+        // NaN in gives NaN out, and so does 0 / 0
+        let nan_cases = [
+            (Add, f64::NAN, 1.0),
+            (Sub, 1.0, f64::NAN),
+            (Mul, f64::NAN, 0.0),
+            (Div, 0.0, 0.0),
+            (Add, f64::INFINITY, f64::NEG_INFINITY),
+        ];
+        for (op, a, b) in nan_cases {
+            let inst = FBinOp::new(PrimitiveType::F64, op);
+            match results(&inst, &[Value::f64(a), Value::f64(b)])[..] {
+                [TypedValue::F64(x)] => assert!(x.is_nan(), "{} gave {}", inst.mnemonic(), x),
+                ref other => panic!("unexpected results {:?}", other),
+            }
+        }
+    }
+
+    #[test]
+    fn comparisons() {
+        use RelOpType::*;
+        use Signedness::*;
+        let int_cases = [
+            (Eq, 5, 5, 1),
+            (Neq, 5, 5, 0),
+            (Neq, 5, 6, 1),
+            (Lt(Signed), -1, 1, 1),
+            (Lt(Unsigned), -1, 1, 0),
+            (Gt(Signed), -1, 1, 0),
+            (Gt(Unsigned), -1, 1, 1),
+            (Le(Signed), 1, 1, 1),
+            (Le(Unsigned), 2, 1, 0),
+            (Ge(Signed), i32::MIN, i32::MAX, 0),
+            (Ge(Unsigned), i32::MIN, i32::MAX, 1),
+        ];
+        for (op, a, b, expected) in int_cases {
+            let inst = RelOp::new(PrimitiveType::I32, op.clone());
+            assert_eq!(
+                results(&inst, &[Value::i32(a), Value::i32(b)]),
+                [TypedValue::I32(expected)],
+                "{} {} {}",
+                inst.mnemonic(),
+                a,
+                b
+            );
+            let inst = RelOp::new(PrimitiveType::I64, op);
+            assert_eq!(
+                results(&inst, &[Value::i64(a as i64), Value::i64(b as i64)]),
+                [TypedValue::I32(expected)],
+                "{} {} {}",
+                inst.mnemonic(),
+                a,
+                b
+            );
+        }
+
+        // Every comparison but ne is false when either side is NaN
+        let nan = f32::NAN;
+        let float_cases = [
+            (Eq, nan, nan, 0),
+            (Neq, nan, nan, 1),
+            (Neq, nan, 1.0, 1),
+            (Lt(Signed), nan, 1.0, 0),
+            (Gt(Signed), nan, 1.0, 0),
+            (Le(Signed), 1.0, nan, 0),
+            (Ge(Signed), 1.0, nan, 0),
+            (Eq, 0.0, -0.0, 1),
+            (Lt(Signed), -0.0, 0.0, 0),
+            (Lt(Signed), -1.0, 1.0, 1),
+        ];
+        for (op, a, b, expected) in float_cases {
+            let inst = RelOp::new(PrimitiveType::F32, op.clone());
+            assert_eq!(
+                results(&inst, &[Value::f32(a), Value::f32(b)]),
+                [TypedValue::I32(expected)],
+                "{} {} {}",
+                inst.mnemonic(),
+                a,
+                b
+            );
+            let inst = RelOp::new(PrimitiveType::F64, op);
+            assert_eq!(
+                results(&inst, &[Value::f64(a as f64), Value::f64(b as f64)]),
+                [TypedValue::I32(expected)]
+            );
+        }
+
+        let eqz = ITestOpEqz::new(PrimitiveType::I64);
+        assert_eq!(results(&eqz, &[Value::i64(0)]), [TypedValue::I32(1)]);
+        assert_eq!(results(&eqz, &[Value::i64(i64::MIN)]), [TypedValue::I32(0)]);
+    }
+
+    #[test]
+    fn unary_ops() {
+        let i32_cases = [
+            (IUnOpType::Clz, 1, 31),
+            (IUnOpType::Clz, 0, 32),
+            (IUnOpType::Ctz, i32::MIN, 31),
+            (IUnOpType::Ctz, 0, 32),
+            (IUnOpType::Popcnt, -1, 32),
+            (IUnOpType::ExtendS(8), 0x80, -128),
+            (IUnOpType::ExtendS(8), 0x17f, 127),
+            (IUnOpType::ExtendS(16), 0x8000, -32768),
+        ];
+        for (op, a, expected) in i32_cases {
+            let inst = IUnOp::new(PrimitiveType::I32, op);
+            assert_eq!(
+                results(&inst, &[Value::i32(a)]),
+                [TypedValue::I32(expected)],
+                "{} {}",
+                inst.mnemonic(),
+                a
+            );
+        }
+        let inst = IUnOp::new(PrimitiveType::I64, IUnOpType::ExtendS(32));
+        assert_eq!(
+            results(&inst, &[Value::i64(0x8000_0000)]),
+            [TypedValue::I64(-0x8000_0000)]
+        );
+
+        let f64_cases = [
+            (FUnOpType::Abs, -1.5, 1.5),
+            (FUnOpType::Neg, 1.5, -1.5),
+            (FUnOpType::Sqrt, 2.25, 1.5),
+            (FUnOpType::Ceil, -1.5, -1.0),
+            (FUnOpType::Floor, -1.5, -2.0),
+            (FUnOpType::Trunc, -1.5, -1.0),
+            (FUnOpType::Nearest, 2.5, 2.0),
+            (FUnOpType::Nearest, 3.5, 4.0),
+            (FUnOpType::Nearest, -0.5, -0.0),
+        ];
+        for (op, a, expected) in f64_cases {
+            let inst = FUnOp::new(PrimitiveType::F64, op);
+            match results(&inst, &[Value::f64(a)])[..] {
+                [TypedValue::F64(x)] => {
+                    assert_eq!(
+                        x.to_bits(),
+                        f64::to_bits(expected),
+                        "{} {}",
+                        inst.mnemonic(),
+                        a
+                    )
+                }
+                ref other => panic!("unexpected results {:?}", other),
+            }
+        }
+        let sqrt = FUnOp::new(PrimitiveType::F32, FUnOpType::Sqrt);
+        match results(&sqrt, &[Value::f32(-1.0)])[..] {
+            [TypedValue::F32(x)] => assert!(x.is_nan()),
+            ref other => panic!("unexpected results {:?}", other),
+        }
+    }
+
+    #[test]
+    fn conversion_ops() {
+        use PrimitiveType::*;
+        use Signedness::*;
+        let cases = [
+            (
+                CvtOpType::Wrap,
+                Value::i64(0x1_0000_0005),
+                TypedValue::I32(5),
+            ),
+            (
+                CvtOpType::Extend(Signed),
+                Value::i32(-1),
+                TypedValue::I64(-1),
+            ),
+            (
+                CvtOpType::Extend(Unsigned),
+                Value::i32(-1),
+                TypedValue::I64(0xFFFF_FFFF),
+            ),
+            (
+                CvtOpType::Trunc(Signed, F32, I32),
+                Value::f32(-3.9),
+                TypedValue::I32(-3),
+            ),
+            (
+                CvtOpType::Trunc(Unsigned, F64, I32),
+                Value::f64(3e9),
+                TypedValue::I32(-1294967296),
+            ),
+            (
+                CvtOpType::Trunc(Unsigned, F32, I32),
+                Value::f32(-0.9),
+                TypedValue::I32(0),
+            ),
+            (
+                CvtOpType::TruncSat(Signed, F32, I32),
+                Value::f32(3e9),
+                TypedValue::I32(i32::MAX),
+            ),
+            (
+                CvtOpType::TruncSat(Unsigned, F64, I64),
+                Value::f64(-5.0),
+                TypedValue::I64(0),
+            ),
+            (
+                CvtOpType::TruncSat(Signed, F64, I64),
+                Value::f64(f64::NAN),
+                TypedValue::I64(0),
+            ),
+            (
+                CvtOpType::Convert(Unsigned, I32, F32),
+                Value::i32(-1),
+                TypedValue::F32(4294967296.0),
+            ),
+            (
+                CvtOpType::Convert(Signed, I64, F64),
+                Value::i64(-1),
+                TypedValue::F64(-1.0),
+            ),
+            (
+                CvtOpType::Demote,
+                Value::f64(1e300),
+                TypedValue::F32(f32::INFINITY),
+            ),
+            (
+                CvtOpType::Promote,
+                Value::f32(0.1),
+                TypedValue::F64(0.1_f32 as f64),
+            ),
+            (
+                CvtOpType::Reinterpret(F32),
+                Value::f32(-0.0),
+                TypedValue::I32(i32::MIN),
+            ),
+            (
+                CvtOpType::Reinterpret(I64),
+                Value::i64(0x3FF0_0000_0000_0000),
+                TypedValue::F64(1.0),
+            ),
+        ];
+        for (op, operand, expected) in cases {
+            let inst = CvtOp::new(op);
+            assert_eq!(
+                results(&inst, &[operand]),
+                [expected],
+                "{}",
+                inst.mnemonic()
+            );
+        }
+
+        let traps = [
+            (
+                CvtOpType::Trunc(Signed, F32, I32),
+                Value::f32(f32::NAN),
+                Trap::InvalidConversionToInteger,
+            ),
+            (
+                CvtOpType::Trunc(Signed, F64, I64),
+                Value::f64(f64::NAN),
+                Trap::InvalidConversionToInteger,
+            ),
+            (
+                CvtOpType::Trunc(Signed, F32, I32),
+                Value::f32(3e9),
+                Trap::IntegerOverflow,
+            ),
+            (
+                CvtOpType::Trunc(Signed, F64, I32),
+                Value::f64(-2147483649.0),
+                Trap::IntegerOverflow,
+            ),
+            (
+                CvtOpType::Trunc(Unsigned, F32, I32),
+                Value::f32(-1.0),
+                Trap::IntegerOverflow,
+            ),
+            (
+                CvtOpType::Trunc(Unsigned, F64, I64),
+                Value::f64(f64::INFINITY),
+                Trap::IntegerOverflow,
+            ),
+        ];
+        for (op, operand, expected) in traps {
+            let inst = CvtOp::new(op);
+            assert_eq!(trapped(&inst, &[operand]), expected, "{}", inst.mnemonic());
+        }
+    }
+
+    #[test]
+    fn load_and_store() {
+        let mut ctx = with_memory(1);
+        let store = Store::new(PrimitiveType::I64, 64, memarg(4));
+        let (control, stack) = execute(
+            &store,
+            &mut ctx,
+            &[Value::i32(0), Value::i64(0x0807_0605_0403_0201)],
+        )
+        .unwrap();
+        assert!(matches!(control, ControlInfo::None));
+        assert!(stack.is_empty());
+        assert_eq!(
+            ctx.memory(0).unwrap().as_slice(4, 8).unwrap(),
+            [1, 2, 3, 4, 5, 6, 7, 8]
+        );
+
+        // A narrow store keeps only the low bytes of its value
+        let store8 = Store::new(PrimitiveType::I32, 8, memarg(0));
+        execute(&store8, &mut ctx, &[Value::i32(5), Value::i32(0x1234)]).unwrap();
+        let load = Load::new(PrimitiveType::I32, 32, memarg(0));
+        let (_, stack) = execute(&load, &mut ctx, &[Value::i32(4)]).unwrap();
+        assert_eq!(stack, [TypedValue::I32(0x0403_3401)]);
+
+        let load = Load::new(PrimitiveType::F64, 64, memarg(0));
+        let store = Store::new(PrimitiveType::F64, 64, memarg(0));
+        execute(&store, &mut ctx, &[Value::i32(16), Value::f64(-2.5)]).unwrap();
+        let (_, stack) = execute(&load, &mut ctx, &[Value::i32(16)]).unwrap();
+        assert_eq!(stack, [TypedValue::F64(-2.5)]);
+
+        // The last whole word fits, but one byte further doesn't
+        let load = Load::new(PrimitiveType::I32, 32, memarg(0));
+        assert!(execute(&load, &mut ctx, &[Value::i32(65532)]).is_ok());
+        let past_end = trapped_in(&load, &mut ctx, &[Value::i32(65533)]);
+        assert_eq!(past_end, Trap::MemoryOutOfBounds);
+        let store = Store::new(PrimitiveType::I32, 32, memarg(65533));
+        let past_end = trapped_in(&store, &mut ctx, &[Value::i32(0), Value::i32(1)]);
+        assert_eq!(past_end, Trap::MemoryOutOfBounds);
+    }
+
+    #[test]
+    fn blocks() {
+        let i32_const = |x| -> Box<dyn Instruction> { Box::new(Const::new(Value::i32(x))) };
+
+        // Only the block's results are left, and whatever was below it is kept
+        let block = Block::new(
+            BlockContinuation::Branch,
+            1,
+            vec![
+                i32_const(1),
+                i32_const(2),
+                Box::new(Branch::new(0)),
+                i32_const(3),
+            ],
+        );
+        assert_eq!(
+            results(&block, &[Value::i32(9)]),
+            [TypedValue::I32(9), TypedValue::I32(2)]
+        );
+
+        // A branch further out is passed on with its depth reduced by one
+        let block = Block::new(BlockContinuation::Branch, 0, vec![Box::new(Branch::new(2))]);
+        let (control, _) = execute(&block, &mut ExecutionContext::default(), &[]).unwrap();
+        assert!(matches!(control, ControlInfo::Branch(1)));
+
+        // A trap isn't swallowed by the block, nor by a block around it
+        let inner = Block::new(
+            BlockContinuation::Branch,
+            0,
+            vec![Box::new(Unreachable::new())],
+        );
+        let outer = Block::new(
+            BlockContinuation::Loop,
+            0,
+            vec![Box::new(inner), i32_const(1)],
+        );
+        assert_eq!(trapped(&outer, &[]), Trap::Unreachable);
+
+        let ret = Block::new(
+            BlockContinuation::Branch,
+            0,
+            vec![Box::new(Return::new()), Box::new(Unreachable::new())],
+        );
+        let (control, _) = execute(&ret, &mut ExecutionContext::default(), &[]).unwrap();
+        assert!(matches!(control, ControlInfo::Return));
+    }
+
+    #[test]
+    fn br_if_needs_an_i32_condition() {
+        let br_if = BranchIf::new(0);
+        for condition in [Value::f32(1.0), Value::f64(1.0), Value::i64(1)] {
+            let result = execute(&br_if, &mut ExecutionContext::default(), &[condition]);
+            assert!(matches!(result, Err(Error::Misc(_))));
+        }
+
+        let mut ctx = ExecutionContext::default();
+        let (control, _) = execute(&br_if, &mut ctx, &[Value::i32(2)]).unwrap();
+        assert!(matches!(control, ControlInfo::Branch(0)));
+        assert!(results(&br_if, &[Value::i32(0)]).is_empty());
+    }
+
+    #[test]
+    fn local_index_past_the_declared_locals() {
+        let mut ctx = ExecutionContext::default();
+        let mut locals = vec![Value::i32(1), Value::i64(2)];
+        let mut stack = Stack::with_values(Vec::new(), DEFAULT_MAX_STACK_DEPTH);
+
+        let get = LocalGet::new(1);
+        assert!(get.execute(&mut stack, &mut ctx, &mut locals, &[]).is_ok());
+        assert_eq!(stack.pop_value().unwrap().unpack(), TypedValue::I64(2));
+
+        let get = LocalGet::new(2);
+        let result = get.execute(&mut stack, &mut ctx, &mut locals, &[]);
+        assert!(matches!(
+            result,
+            Err(Error::Misc("Local index is not valid"))
+        ));
+        assert!(stack.values.is_empty());
+
+        for index in [2, usize::MAX] {
+            stack.push_value(Value::i32(3)).unwrap();
+            let set = LocalSet::new(index);
+            let result = set.execute(&mut stack, &mut ctx, &mut locals, &[]);
+            assert!(matches!(
+                result,
+                Err(Error::Misc("Local index is not valid"))
+            ));
+            stack.push_value(Value::i32(3)).unwrap();
+            let tee = LocalTee::new(index);
+            let result = tee.execute(&mut stack, &mut ctx, &mut locals, &[]);
+            assert!(matches!(
+                result,
+                Err(Error::Misc("Local index is not valid"))
+            ));
+        }
+        assert_eq!(locals.len(), 2);
+
+        // The parser turns such a module away before it can run
+        let bytes = wasm(r#"(module (func (param i32) (result i32) local.get 5))"#);
+        assert!(matches!(
+            crate::parser::parse_wasm_bytes(&bytes),
+            Err(Error::UnexpectedData("Expected a valid local index"))
+        ));
+    }
+
+    #[test]
+    fn errors_name_the_callee() {
+        let mut module = instantiate(
+            r#"(module
+                (func $inner unreachable)
+                (func $outer call $inner)
+                (func (export "named") call $outer)
+                (func (export "unnamed") (result i32) call 4)
+                (func (result i32) i32.const 0 i32.const 0 i32.div_s))"#,
+        );
+        assert_eq!(module.function_name(0), Some("inner"));
+        assert_eq!(module.function_name(4), None);
+
+        // Only the innermost function is named
+        let e = call(&mut module, "named", &[]).unwrap_err();
+        assert!(matches!(
+            &e,
+            Error::InFunction(name, e) if name == "inner" && matches!(**e, Error::Trap(Trap::Unreachable))
+        ));
+
+        let e = call(&mut module, "unnamed", &[]).unwrap_err();
+        assert!(matches!(&e, Error::InFunction(name, _) if name == "func[4]"));
+        assert!(matches!(e.root(), Error::Trap(Trap::DivideByZero)));
+    }
+
+    #[test]
+    fn trunc_traps_where_trunc_sat_saturates() {
+        let mut module = instantiate(
+            r#"(module
+                (func (export "i32_s") (param f32) (result i32) local.get 0 i32.trunc_f32_s)
+                (func (export "i32_s_sat") (param f32) (result i32) local.get 0 i32.trunc_sat_f32_s)
+                (func (export "i32_u") (param f64) (result i32) local.get 0 i32.trunc_f64_u)
+                (func (export "i32_u_sat") (param f64) (result i32) local.get 0 i32.trunc_sat_f64_u)
+                (func (export "i64_s") (param f64) (result i64) local.get 0 i64.trunc_f64_s)
+                (func (export "i64_s_sat") (param f64) (result i64) local.get 0 i64.trunc_sat_f64_s))"#,
+        );
+        let f32_nan = Value::f32(f32::NAN);
+        let f64_nan = Value::f64(f64::NAN);
+        let cases = [
+            (
+                "i32_s",
+                Value::f32(3e9),
+                Trap::IntegerOverflow,
+                TypedValue::I32(i32::MAX),
+            ),
+            (
+                "i32_s",
+                Value::f32(-3e9),
+                Trap::IntegerOverflow,
+                TypedValue::I32(i32::MIN),
+            ),
+            (
+                "i32_s",
+                f32_nan,
+                Trap::InvalidConversionToInteger,
+                TypedValue::I32(0),
+            ),
+            (
+                "i32_u",
+                Value::f64(5e9),
+                Trap::IntegerOverflow,
+                TypedValue::I32(-1),
+            ),
+            (
+                "i32_u",
+                Value::f64(-1.0),
+                Trap::IntegerOverflow,
+                TypedValue::I32(0),
+            ),
+            (
+                "i32_u",
+                f64_nan,
+                Trap::InvalidConversionToInteger,
+                TypedValue::I32(0),
+            ),
+            (
+                "i64_s",
+                Value::f64(1e19),
+                Trap::IntegerOverflow,
+                TypedValue::I64(i64::MAX),
+            ),
+            (
+                "i64_s",
+                Value::f64(f64::NEG_INFINITY),
+                Trap::IntegerOverflow,
+                TypedValue::I64(i64::MIN),
+            ),
+            (
+                "i64_s",
+                f64_nan,
+                Trap::InvalidConversionToInteger,
+                TypedValue::I64(0),
+            ),
+        ];
+        for (name, operand, expected_trap, saturated) in cases {
+            let result = call(&mut module, name, &[operand]);
+            assert_eq!(trap(result), expected_trap, "{}({})", name, operand);
+            let sat = format!("{}_sat", name);
+            let result = call(&mut module, &sat, &[operand]).unwrap();
+            assert_eq!(result, [saturated], "{}({})", sat, operand);
+        }
+
+        // In range, both round towards zero
+        for name in ["i32_s", "i32_s_sat"] {
+            let result = call(&mut module, name, &[Value::f32(-2.9)]).unwrap();
+            assert_eq!(result, [TypedValue::I32(-2)]);
+        }
+    }
+
+    #[test]
+    fn br_if_reads_only_the_low_32_bits() {
+        let mut module = instantiate(
+            r#"(module
+                (func (export "count") (param $i i32) (param $end i32) (result i32) (local $n i32)
+                    (loop $again
+                        (local.set $n (i32.add (local.get $n) (i32.const 1)))
+                        (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                        (br_if $again (i32.lt_u (local.get $i) (local.get $end))))
+                    (local.get $n))
+                (func (export "taken") (param i32) (result i32)
+                    (block (br_if 0 (local.get 0)) (return (i32.const 0)))
+                    (i32.const 1)))"#,
+        );
+        // The unsigned counter crosses 0x80000000, where it turns negative as an i32
+        let args = [Value::i32(0x7FFF_FFFC), Value::i32(0x8000_0004_u32 as i32)];
+        assert_eq!(
+            call(&mut module, "count", &args).unwrap(),
+            [TypedValue::I32(8)]
+        );
+
+        for (condition, taken) in [(0, 0), (1, 1), (i32::MIN, 1), (-1, 1)] {
+            assert_eq!(
+                call(&mut module, "taken", &[Value::i32(condition)]).unwrap(),
+                [TypedValue::I32(taken)],
+                "{:#x}",
+                condition
+            );
+        }
+    }
+
+    #[test]
+    fn division_by_zero_ends_the_call_with_an_error() {
+        // No flag is needed: catch_all only catches exceptions, so the trap escapes the try,
+        // and the call that raised it gives Err right away instead of a value to inspect
+        let mut module = instantiate(
+            r#"(module
+                (global $after (mut i32) (i32.const 0))
+                (func $divide (param i32 i32) (result i32)
+                    (i32.div_s (local.get 0) (local.get 1)))
+                (func (export "direct") (param i32) (result i32)
+                    try (result i32)
+                        i32.const 1
+                        local.get 0
+                        i32.div_u
+                    catch_all
+                        i32.const -1
+                    end
+                    i32.const 1
+                    global.set $after)
+                (func (export "nested") (param i32) (result i32)
+                    (call $divide (i32.const 1) (local.get 0))
+                    (global.set $after (i32.const 1)))
+                (func (export "after") (result i32) global.get $after))"#,
+        );
+        let e = call(&mut module, "direct", &[Value::i32(0)]).unwrap_err();
+        assert!(matches!(e, Error::Trap(Trap::DivideByZero)));
+        let e = call(&mut module, "nested", &[Value::i32(0)]).unwrap_err();
+        assert!(matches!(&e, Error::InFunction(name, _) if name == "divide"));
+        assert!(matches!(e.root(), Error::Trap(Trap::DivideByZero)));
+        // Nothing after the division ran
+        assert_eq!(
+            call(&mut module, "after", &[]).unwrap(),
+            [TypedValue::I32(0)]
+        );
+
+        assert_eq!(
+            call(&mut module, "direct", &[Value::i32(1)]).unwrap(),
+            [TypedValue::I32(1)]
+        );
+        assert_eq!(
+            call(&mut module, "after", &[]).unwrap(),
+            [TypedValue::I32(1)]
+        );
+    }
+
+    #[test]
+    fn stores_take_the_address_then_the_value() {
+        // With the address pushed first and the value on top
+        let cases = [
+            (
+                Store::new(PrimitiveType::I32, 8, memarg(0)),
+                Value::i32(0x1234),
+                vec![0x34],
+            ),
+            (
+                Store::new(PrimitiveType::I32, 16, memarg(0)),
+                Value::i32(0x1234),
+                vec![0x34, 0x12],
+            ),
+            (
+                Store::new(PrimitiveType::I32, 32, memarg(0)),
+                Value::i32(9),
+                vec![9, 0, 0, 0],
+            ),
+            (
+                Store::new(PrimitiveType::I64, 8, memarg(0)),
+                Value::i64(-2),
+                vec![0xFE],
+            ),
+            (
+                Store::new(PrimitiveType::I64, 16, memarg(0)),
+                Value::i64(0x10203),
+                vec![3, 2],
+            ),
+            (
+                Store::new(PrimitiveType::I64, 32, memarg(0)),
+                Value::i64(0x7_0000_0001),
+                vec![1, 0, 0, 0],
+            ),
+            (
+                Store::new(PrimitiveType::I64, 64, memarg(0)),
+                Value::i64(5),
+                vec![5, 0, 0, 0, 0, 0, 0, 0],
+            ),
+            (
+                Store::new(PrimitiveType::F32, 32, memarg(0)),
+                Value::f32(1.0),
+                1.0_f32.to_le_bytes().to_vec(),
+            ),
+            (
+                Store::new(PrimitiveType::F64, 64, memarg(0)),
+                Value::f64(-1.0),
+                (-1.0_f64).to_le_bytes().to_vec(),
+            ),
+        ];
+        for (store, value, expected) in cases {
+            let mut ctx = with_memory(1);
+            let (_, stack) = execute(&store, &mut ctx, &[Value::i32(100), value]).unwrap();
+            assert!(stack.is_empty());
+            let memory = ctx.memory(0).unwrap();
+            assert_eq!(
+                memory.as_slice(100, expected.len() as u32).unwrap(),
+                &expected[..],
+                "{}",
+                store.mnemonic()
+            );
+            // Nothing was written at the address the value would give if they were swapped
+            assert!(memory.as_slice(0, 100).unwrap().iter().all(|b| *b == 0));
+        }
+
+        // Swapping two i32 operands stores the other number at the other address
+        let mut ctx = with_memory(1);
+        let store = Store::new(PrimitiveType::I32, 32, memarg(0));
+        execute(&store, &mut ctx, &[Value::i32(8), Value::i32(200)]).unwrap();
+        assert_eq!(ctx.memory(0).unwrap().as_slice(8, 1).unwrap(), [200]);
+        assert_eq!(ctx.memory(0).unwrap().as_slice(200, 1).unwrap(), [0]);
+
+        // Atomic stores take theirs in the same order
+        let mut ctx = with_memory(1);
+        let memarg = MemArg {
+            align: 2,
+            ..memarg(0)
+        };
+        let store = AtomicStore::new(PrimitiveType::I32, 32, memarg);
+        execute(&store, &mut ctx, &[Value::i32(8), Value::i32(200)]).unwrap();
+        assert_eq!(ctx.memory(0).unwrap().as_slice(8, 1).unwrap(), [200]);
+
+        // An address worked out at runtime, stored to by a narrow store
+        let mut module = instantiate(
+            r#"(module
+                (memory (export "memory") 1)
+                (func (export "store") (param $base i32) (param $index i32) (param $value i64)
+                    (i64.store16
+                        (i32.add (local.get $base) (i32.mul (local.get $index) (i32.const 2)))
+                        (local.get $value))))"#,
+        );
+        let args = [Value::i32(16), Value::i32(3), Value::i64(0x1_ABCD)];
+        call(&mut module, "store", &args).unwrap();
+        assert_eq!(
+            module.memory(0).unwrap().as_slice(20, 4).unwrap(),
+            [0, 0, 0xCD, 0xAB]
+        );
+    }
+
+    #[test]
+    fn calls_return_references() {
+        let mut module = instantiate(
+            r#"(module
+                (type $give (func (result funcref)))
+                (table 2 funcref)
+                (elem (i32.const 0) $first $second)
+                (func $first (result funcref) ref.func $first)
+                (func $second (result funcref) ref.null func)
+                (func (export "direct") (result funcref) call $first)
+                (func (export "indirect") (param i32) (result funcref)
+                    (call_indirect (type $give) (local.get 0)))
+                (func (export "is_null") (param i32) (result i32)
+                    (ref.is_null (call_indirect (type $give) (local.get 0))))
+                (func (export "extern") (param externref) (result externref) local.get 0))"#,
+        );
+        assert_eq!(
+            call(&mut module, "direct", &[]).unwrap(),
+            [TypedValue::FuncRef(Some(0))]
+        );
+        assert_eq!(
+            call(&mut module, "indirect", &[Value::i32(0)]).unwrap(),
+            [TypedValue::FuncRef(Some(0))]
+        );
+        assert_eq!(
+            call(&mut module, "indirect", &[Value::i32(1)]).unwrap(),
+            [TypedValue::FuncRef(None)]
+        );
+        assert_eq!(
+            call(&mut module, "is_null", &[Value::i32(1)]).unwrap(),
+            [TypedValue::I32(1)]
+        );
+        let results = module.call("direct", vec![]).unwrap();
+        assert!(results[0].get_type() == PrimitiveType::FuncRef);
+        assert_eq!(
+            call(
+                &mut module,
+                "extern",
+                &[Value::null(PrimitiveType::ExternRef)]
+            )
+            .unwrap(),
+            [TypedValue::ExternRef(None)]
+        );
+    }
+
+    #[test]
+    fn float_equality_follows_ieee() {
+        let mut module = instantiate(
+            r#"(module
+                (func (export "f32.eq") (param f32 f32) (result i32) (f32.eq (local.get 0) (local.get 1)))
+                (func (export "f32.ne") (param f32 f32) (result i32) (f32.ne (local.get 0) (local.get 1)))
+                (func (export "f64.eq") (param f64 f64) (result i32) (f64.eq (local.get 0) (local.get 1)))
+                (func (export "f64.ne") (param f64 f64) (result i32) (f64.ne (local.get 0) (local.get 1))))"#,
+        );
+        let f32_cases = [
+            (-0.0, 0.0, 1),
+            (f32::NAN, f32::NAN, 0),
+            (f32::NAN, 1.0, 0),
+            (1.5, 1.5, 1),
+            (1.5, -1.5, 0),
+        ];
+        for (a, b, equal) in f32_cases {
+            let args = [Value::f32(a), Value::f32(b)];
+            let eq = call(&mut module, "f32.eq", &args).unwrap();
+            assert_eq!(eq, [TypedValue::I32(equal)], "{} == {}", a, b);
+            let ne = call(&mut module, "f32.ne", &args).unwrap();
+            assert_eq!(ne, [TypedValue::I32(1 - equal)], "{} != {}", a, b);
+        }
+        let f64_cases = [
+            (-0.0, 0.0, 1),
+            (f64::NAN, f64::NAN, 0),
+            (-f64::NAN, 1.0, 0),
+            (1.5, 1.5, 1),
+            (f64::INFINITY, f64::MAX, 0),
+        ];
+        for (a, b, equal) in f64_cases {
+            let args = [Value::f64(a), Value::f64(b)];
+            let eq = call(&mut module, "f64.eq", &args).unwrap();
+            assert_eq!(eq, [TypedValue::I32(equal)], "{} == {}", a, b);
+            let ne = call(&mut module, "f64.ne", &args).unwrap();
+            assert_eq!(ne, [TypedValue::I32(1 - equal)], "{} != {}", a, b);
+        }
+    }
+
+    #[test]
+    fn overflow_and_division_by_zero_are_told_apart() {
+        let mut module = instantiate(
+            r#"(module
+                (func (export "div") (param i32 i32) (result i32) (i32.div_s (local.get 0) (local.get 1)))
+                (func (export "rem") (param i32 i32) (result i32) (i32.rem_s (local.get 0) (local.get 1)))
+                (func (export "div64") (param i64 i64) (result i64) (i64.div_s (local.get 0) (local.get 1))))"#,
+        );
+        let overflow = [Value::i32(i32::MIN), Value::i32(-1)];
+        let by_zero = [Value::i32(5), Value::i32(0)];
+        assert_eq!(
+            trap(call(&mut module, "div", &overflow)),
+            Trap::IntegerOverflow
+        );
+        assert_eq!(trap(call(&mut module, "div", &by_zero)), Trap::DivideByZero);
+        // The remainder of the overflowing division is defined
+        assert_eq!(
+            call(&mut module, "rem", &overflow).unwrap(),
+            [TypedValue::I32(0)]
+        );
+        assert_eq!(trap(call(&mut module, "rem", &by_zero)), Trap::DivideByZero);
+
+        let overflow = [Value::i64(i64::MIN), Value::i64(-1)];
+        assert_eq!(
+            trap(call(&mut module, "div64", &overflow)),
+            Trap::IntegerOverflow
+        );
+        let by_zero = [Value::i64(5), Value::i64(0)];
+        assert_eq!(
+            trap(call(&mut module, "div64", &by_zero)),
+            Trap::DivideByZero
+        );
+        assert_eq!(
+            call(&mut module, "div", &[Value::i32(-7), Value::i32(2)]).unwrap(),
+            [TypedValue::I32(-3)]
+        );
+    }
+
+    #[test]
+    fn if_result_comes_from_the_arm_that_ran() {
+        let text = r#"(module
+            (func (export "pick") (param i32) (result i32)
+                i32.const 100
+                (if (result i32) (local.get 0) (then i32.const 1) (else i32.const 2))
+                i32.add)
+            (func (export "with_param") (param i32) (result i32)
+                i32.const 7
+                i32.const 10
+                local.get 0
+                (if (param i32) (result i32)
+                    (then i32.const 1 i32.add)
+                    (else i32.const 2 i32.mul))
+                i32.add))"#;
+        let mut module = instantiate(text);
+        for (condition, pick, with_param) in [(1, 101, 18), (0, 102, 27)].iter() {
+            let args = [Value::i32(*condition)];
+            assert_eq!(
+                call(&mut module, "pick", &args).unwrap(),
+                [TypedValue::I32(*pick)]
+            );
+            // The value below the param survives either arm
+            assert_eq!(
+                call(&mut module, "with_param", &args).unwrap(),
+                [TypedValue::I32(*with_param)]
+            );
+        }
+    }
+
+    #[test]
+    fn table_init_and_copy_check_element_types() {
+        let mut module = Module::default();
+        module.add_table(Table::with_type(PrimitiveType::FuncRef, 1, 1));
+        module.add_table(Table::with_type(PrimitiveType::ExternRef, 1, 1));
+        module.add_element_segment(PrimitiveType::ExternRef, Vec::new());
+        let function = Function::new(FunctionType::default());
+        let check = |inst: &dyn Instruction| {
+            let mut types = TypeStack::new(&module, &function);
+            for _ in 0..3 {
+                types.push(PrimitiveType::I32);
+            }
+            inst.check_types(&mut types)
+        };
+        assert!(check(&TableInit::new(0, 1)).is_ok());
+        assert!(check(&TableInit::new(0, 0)).is_err());
+        assert!(check(&TableInit::new(1, 1)).is_err());
+        assert!(check(&TableCopy::new(1, 1)).is_ok());
+        assert!(check(&TableCopy::new(0, 1)).is_err());
+        assert!(check(&TableCopy::new(1, 0)).is_err());
+    }
+
+    #[test]
+    fn call_to_a_missing_function_is_an_error() {
+        let result = execute(&Call::new(9), &mut ExecutionContext::default(), &[]);
+        assert!(matches!(result, Err(Error::Misc(_))));
+    }
+
+    #[test]
+    fn store_rejects_a_value_of_the_wrong_type() {
+        let mut ctx = with_memory(1);
+        let cases = [
+            (PrimitiveType::I32, 32, Value::f32(1.0)),
+            (PrimitiveType::I32, 8, Value::i64(1)),
+            (PrimitiveType::I64, 64, Value::i32(1)),
+            (PrimitiveType::F64, 64, Value::i64(1)),
+        ];
+        for (value_type, bitwidth, value) in cases.iter() {
+            let store = Store::new(*value_type, *bitwidth, memarg(0));
+            let result = execute(&store, &mut ctx, &[Value::i32(0), *value]);
+            assert!(matches!(result, Err(Error::StackViolation)));
+        }
+        assert_eq!(ctx.memories[0].as_slice(0, 8).unwrap(), [0; 8]);
+
+        let store = Store::new(PrimitiveType::I64, 64, memarg(0));
+        assert!(execute(&store, &mut ctx, &[Value::i32(0), Value::i64(-1)]).is_ok());
+    }
+
+    #[test]
+    fn table_grow_that_cant_be_allocated_fails() {
+        let text = r#"(module
+            (table 1 externref)
+            (func (export "grow") (param i32) (result i32)
+                (table.grow (ref.null extern) (local.get 0))))"#;
+        let mut module = instantiate(text);
+        // Billions of elements take tens of GiB, within the table's limits but not the host's
+        let grow = |module: &mut Module, delta| call(module, "grow", &[Value::i32(delta)]);
+        assert_eq!(grow(&mut module, -2).unwrap(), [TypedValue::I32(-1)]);
+        assert_eq!(module.table(0).unwrap().size(), 1);
+        assert_eq!(grow(&mut module, 1).unwrap(), [TypedValue::I32(1)]);
+    }
 }
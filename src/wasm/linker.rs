@@ -0,0 +1,206 @@
+use alloc::collections::BTreeMap;
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use core::cell::RefCell;
+
+use super::{Export, ImportDescriptor, ImportedGlobals, Module};
+use crate::error::Error;
+
+/// Named modules whose exports other modules can import.
+///
+/// Only functions are linked. Imported functions run in the module that exports them, using
+/// that module's memory and globals. Immutable globals are imported by value, through
+/// `imported_globals`, which is exact since neither side can change them.
+///
+/// Memories, tables and mutable globals can't be shared between modules yet, so `instantiate`
+/// rejects a module importing one of those from a registered module, rather than giving it a
+/// copy that would silently drift apart from the exporter's.
+#[derive(Default)]
+pub struct Linker {
+    instances: BTreeMap<String, Rc<RefCell<Module>>>,
+}
+
+impl Linker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make the exports of `module`, which should already be instantiated, importable from the
+    /// module name `name`. Gives back the shared module so the host can still call it.
+    pub fn register(&mut self, name: &str, module: Module) -> Rc<RefCell<Module>> {
+        let instance = Rc::new(RefCell::new(module));
+        self.instances.insert(name.to_string(), instance.clone());
+        instance
+    }
+
+    /// The current value of every global exported by a registered module, to pass to
+    /// `parse_wasm_with_imports` for a module that imports them
+    pub fn imported_globals(&self) -> ImportedGlobals {
+        let mut globals = ImportedGlobals::new();
+        for (module_name, instance) in &self.instances {
+            let instance = instance.borrow();
+            for (name, export) in &instance.exports {
+                if let Export::Global(index) = export {
+                    if let Some(global) = instance.globals().get(*index) {
+                        globals.insert((module_name.clone(), name.clone()), global.value);
+                    }
+                }
+            }
+        }
+        globals
+    }
+
+    /// Link every function `module` imports to the registered module exporting it, then
+    /// instantiate `module`. Gives `Error::UnresolvedImport` for the first import that no
+    /// registered module exports, and `Error::Misc` for an import that can't be shared.
+    pub fn instantiate(&self, module: &mut Module) -> Result<(), Error> {
+        for import in module.imports() {
+            // Memories, tables and mutable globals would need a handle shared with the exporter
+            if let ImportDescriptor::Function(_) | ImportDescriptor::Global(_, false) =
+                import.descriptor
+            {
+                continue;
+            }
+            let exported = match self.instances.get(&import.module) {
+                Some(instance) => match instance.try_borrow() {
+                    Ok(m) => m.exports.contains_key(&import.name),
+                    Err(_) => return Err(Error::Misc("Linked module is already running")),
+                },
+                None => false,
+            };
+            if exported {
+                return Err(Error::Misc(
+                    "Only functions and immutable globals can be imported from a linked module",
+                ));
+            }
+        }
+        for function in &mut module.functions {
+            let (module_name, name) = match &function.import {
+                Some(import) => import,
+                None => continue,
+            };
+            let unresolved = || Error::UnresolvedImport {
+                module: module_name.clone(),
+                field: name.clone(),
+            };
+            let instance = match self.instances.get(module_name) {
+                Some(m) => m,
+                None => return Err(unresolved()),
+            };
+            let exporter = match instance.try_borrow() {
+                Ok(m) => m,
+                Err(_) => return Err(Error::Misc("Linked module is already running")),
+            };
+            let index = match exporter.exports.get(name) {
+                Some(Export::Function(n)) => *n,
+                _ => return Err(unresolved()),
+            };
+            match exporter.functions.get(index) {
+                Some(f) if f.signature() == function.signature() => (),
+                Some(_) => return Err(Error::Misc("Imported function has the wrong type")),
+                None => {
+                    return Err(Error::Misc(
+                        "Function index given by export section is not valid",
+                    ))
+                }
+            }
+            drop(exporter);
+            function.linked = Some(instance.clone());
+        }
+        module.instantiate()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{parse_wasm_bytes, parse_wasm_bytes_with_imports};
+    use crate::test_util::*;
+    use crate::wasm::{TypedValue, Value};
+
+    fn library() -> Linker {
+        let mut linker = Linker::new();
+        linker.register(
+            "lib",
+            instantiate(
+                r#"(module
+                    (memory (export "memory") 1)
+                    (table (export "table") 1 funcref)
+                    (global (export "base") i32 (i32.const 100))
+                    (global $count (export "count") (mut i32) (i32.const 0))
+                    (func (export "add") (param i32 i32) (result i32)
+                        (global.set $count (i32.add (global.get $count) (i32.const 1)))
+                        (i32.add (local.get 0) (local.get 1))))"#,
+            ),
+        );
+        linker
+    }
+
+    #[test]
+    fn main_calls_an_imported_function() {
+        let linker = library();
+        let bytes = wasm(
+            r#"(module
+                (import "lib" "add" (func $add (param i32 i32) (result i32)))
+                (import "lib" "base" (global $base i32))
+                (func (export "main") (param i32) (result i32)
+                    (call $add (local.get 0) (global.get $base))))"#,
+        );
+        let mut main = parse_wasm_bytes_with_imports(&bytes, &linker.imported_globals()).unwrap();
+        linker.instantiate(&mut main).unwrap();
+        assert_eq!(
+            call(&mut main, "main", &[Value::i32(23)]).unwrap(),
+            [TypedValue::I32(123)]
+        );
+        // The call ran in lib, against lib's own globals
+        let lib = linker.instances["lib"].clone();
+        let count = lib.borrow().get_global("count").unwrap();
+        assert_eq!(count.unpack(), TypedValue::I32(1));
+    }
+
+    #[test]
+    fn only_functions_and_immutable_globals_are_linked() {
+        let linker = library();
+        let expected = "Only functions and immutable globals can be imported from a linked module";
+        for import in [
+            r#"(import "lib" "memory" (memory 1))"#,
+            r#"(import "lib" "table" (table 1 funcref))"#,
+            r#"(import "lib" "count" (global (mut i32)))"#,
+        ] {
+            let bytes = wasm(&format!("(module {})", import));
+            let mut main =
+                parse_wasm_bytes_with_imports(&bytes, &linker.imported_globals()).unwrap();
+            let result = linker.instantiate(&mut main);
+            assert!(
+                matches!(result, Err(Error::Misc(m)) if m == expected),
+                "{}",
+                import
+            );
+        }
+
+        // A memory the host gives the module isn't affected
+        let bytes = wasm(r#"(module (import "env" "memory" (memory 1)))"#);
+        let mut main = parse_wasm_bytes(&bytes).unwrap();
+        assert!(linker.instantiate(&mut main).is_ok());
+    }
+
+    #[test]
+    fn function_imports_must_resolve_with_the_right_type() {
+        let linker = library();
+        let unresolved =
+            wasm(r#"(module (import "lib" "sub" (func (param i32 i32) (result i32))))"#);
+        let mut main = parse_wasm_bytes(&unresolved).unwrap();
+        assert!(matches!(
+            linker.instantiate(&mut main),
+            Err(Error::UnresolvedImport { module, field }) if module == "lib" && field == "sub"
+        ));
+
+        let wrong_type =
+            wasm(r#"(module (import "lib" "add" (func (param i64 i64) (result i64))))"#);
+        let mut main = parse_wasm_bytes(&wrong_type).unwrap();
+        assert!(matches!(
+            linker.instantiate(&mut main),
+            Err(Error::Misc("Imported function has the wrong type"))
+        ));
+    }
+}
@@ -0,0 +1,59 @@
+//! Checks that calls between wasm functions reuse their locals, stacks and results instead of
+//! allocating new ones each time
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use wasm_interpreter::parser::parse_wasm_bytes;
+use wasm_interpreter::wasm::Value;
+
+struct CountingAllocator;
+
+thread_local! {
+    /// Only this thread's allocations are counted, so the test harness can't skew them
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn allocations_do_not_grow_with_the_number_of_calls() {
+    let bytes = wat::parse_str(
+        r#"(module
+            (func $add (param i32 i32) (result i32) (i32.add (local.get 0) (local.get 1)))
+            (func (export "run") (param $n i32) (result i32) (local $acc i32)
+                (loop $again
+                    (local.set $acc (call $add (local.get $acc) (local.get $n)))
+                    (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                    (br_if $again (local.get $n)))
+                (local.get $acc)))"#,
+    )
+    .unwrap();
+    let mut module = parse_wasm_bytes(&bytes).unwrap();
+    module.instantiate().unwrap();
+
+    let mut count = |n: i32| {
+        let before = ALLOCATIONS.with(Cell::get);
+        let results = module.call("run", vec![Value::i32(n)]).unwrap();
+        assert_eq!(results[0].as_i32_unchecked(), n * (n + 1) / 2);
+        ALLOCATIONS.with(Cell::get) - before
+    };
+    // The first call fills the spare vectors, after which every call makes the same few
+    count(10);
+    let few = count(10);
+    let many = count(10_000);
+    assert_eq!(few, many);
+    assert!(few < 10, "{} allocations", few);
+}
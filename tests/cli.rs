@@ -0,0 +1,75 @@
+//! Runs the command line interpreter on small modules, checking what it prints
+
+use std::fs;
+use std::path::PathBuf;
+use std::process::{Command, Output};
+
+/// Assemble `text` into a file named `name` in the test's scratch directory
+fn module_file(name: &str, text: &str) -> PathBuf {
+    let path = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(name);
+    fs::write(&path, wat::parse_str(text).unwrap()).unwrap();
+    path
+}
+
+fn run(module: &PathBuf, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_wasm-interpreter"))
+        .arg(module)
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(output: &Output) -> String {
+    String::from_utf8(output.stdout.clone()).unwrap()
+}
+
+#[test]
+fn squares_an_f64() {
+    let module = module_file(
+        "square.wasm",
+        r#"(module
+            (func (export "square") (param f64) (result f64)
+                (f64.mul (local.get 0) (local.get 0))))"#,
+    );
+    for (arg, expected) in [
+        ("f64:1.5", "Final value: (f64:2.25)"),
+        ("f64:-3", "Final value: (f64:9.0)"),
+        ("f64:-inf", "Final value: (f64:inf)"),
+        ("f64:nan", "Final value: (f64:nan)"),
+        // 2.0 by its bit pattern
+        ("f64:0x4000000000000000", "Final value: (f64:4.0)"),
+    ] {
+        let output = run(&module, &["square", arg]);
+        assert!(output.status.success(), "{}", arg);
+        assert!(
+            stdout(&output).contains(expected),
+            "{}: {}",
+            arg,
+            stdout(&output)
+        );
+    }
+
+    // Arguments are checked against the signature
+    let output = run(&module, &["square", "f32:1.5"]);
+    assert!(!output.status.success());
+    assert!(stdout(&output).contains("square takes (f64), but was given (f32)"));
+    let output = run(&module, &["square", "f64:one"]);
+    assert!(!output.status.success());
+    assert!(stdout(&output).contains("one is not a valid f64"));
+}
+
+#[test]
+fn runs_a_function_without_params() {
+    let module = module_file(
+        "answer.wasm",
+        r#"(module (func (export "main") (result i32) (i32.const 42)))"#,
+    );
+    let output = run(&module, &["main"]);
+    assert!(output.status.success(), "{}", stdout(&output));
+    assert!(stdout(&output).contains("Final value: (i32:42)"));
+
+    // Nothing is passed that wasn't given
+    let output = run(&module, &["main", "i64:100000"]);
+    assert!(!output.status.success());
+    assert!(stdout(&output).contains("main takes (), but was given (i64)"));
+}
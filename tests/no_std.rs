@@ -0,0 +1,18 @@
+//! Checks that the core crate still builds without the std feature
+
+use std::process::Command;
+
+#[test]
+fn builds_without_std() {
+    // A separate target directory, so this doesn't wait on the lock held by the outer build
+    let status = Command::new(env!("CARGO"))
+        .args(["check", "--lib", "--no-default-features", "--quiet"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .env(
+            "CARGO_TARGET_DIR",
+            concat!(env!("CARGO_TARGET_TMPDIR"), "/no_std"),
+        )
+        .status()
+        .unwrap();
+    assert!(status.success());
+}
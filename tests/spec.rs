@@ -0,0 +1,197 @@
+//! Runs the assert_return and assert_trap directives of the .wast scripts in tests/spec
+//! against the interpreter
+
+use std::fs;
+use std::path::Path;
+
+use wasm_interpreter::error::Error;
+use wasm_interpreter::parser::parse_wasm_bytes;
+use wasm_interpreter::wasm::{Module, PrimitiveType, Trap, Value};
+use wast::core::{NanPattern, WastArgCore, WastRetCore};
+use wast::parser::{self, ParseBuffer};
+use wast::{QuoteWat, Wast, WastArg, WastDirective, WastExecute, WastInvoke, WastRet};
+
+#[test]
+fn i32() {
+    run_script("i32.wast");
+}
+
+#[test]
+fn i64() {
+    run_script("i64.wast");
+}
+
+#[test]
+fn conversions() {
+    run_script("conversions.wast");
+}
+
+/// Run every directive in the script, panicking with a list of the ones that failed
+fn run_script(name: &str) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/spec")
+        .join(name);
+    let text = fs::read_to_string(&path).unwrap();
+    let buf = ParseBuffer::new(&text).unwrap();
+    let script = parser::parse::<Wast>(&buf).unwrap();
+
+    let mut module = None;
+    let mut failures = Vec::new();
+    let mut passed = 0;
+    for directive in script.directives {
+        let (line, _) = directive.span().linecol_in(&text);
+        match run_directive(directive, &mut module) {
+            Ok(()) => passed += 1,
+            Err(message) => failures.push(format!("{}:{}: {}", name, line + 1, message)),
+        }
+    }
+    assert!(
+        failures.is_empty(),
+        "{} of {} directives failed:\n{}",
+        failures.len(),
+        failures.len() + passed,
+        failures.join("\n")
+    );
+}
+
+fn run_directive(directive: WastDirective, module: &mut Option<Module>) -> Result<(), String> {
+    match directive {
+        WastDirective::Module(mut wat) => {
+            *module = None;
+            *module = Some(instantiate(&mut wat)?);
+            Ok(())
+        }
+        WastDirective::AssertReturn {
+            exec: WastExecute::Invoke(invoke),
+            results,
+            ..
+        } => {
+            let actual = invoke_export(module, &invoke).map_err(|e| format!("{:?}", e))?;
+            if actual.len() != results.len() {
+                return Err(format!(
+                    "expected {} results, got {:?}",
+                    results.len(),
+                    actual
+                ));
+            }
+            for (actual, expected) in actual.iter().zip(&results) {
+                if !matches_result(actual, expected) {
+                    return Err(format!("expected {:?}, got {:?}", expected, actual));
+                }
+            }
+            Ok(())
+        }
+        WastDirective::AssertTrap {
+            exec: WastExecute::Invoke(invoke),
+            message,
+            ..
+        } => match invoke_export(module, &invoke) {
+            Ok(results) => Err(format!("expected trap {:?}, got {:?}", message, results)),
+            Err(e) => match e.root() {
+                Error::Trap(t) if spec_message(t) == message => Ok(()),
+                e => Err(format!("expected trap {:?}, got {:?}", message, e)),
+            },
+        },
+        WastDirective::Invoke(invoke) => invoke_export(module, &invoke)
+            .map(|_| ())
+            .map_err(|e| format!("{:?}", e)),
+        other => Err(format!(
+            "unsupported directive at offset {}",
+            other.span().offset()
+        )),
+    }
+}
+
+fn instantiate(wat: &mut QuoteWat) -> Result<Module, String> {
+    let bytes = wat.encode().map_err(|e| e.to_string())?;
+    let mut module = parse_wasm_bytes(&bytes).map_err(|e| format!("{:?}", e))?;
+    module.instantiate().map_err(|e| format!("{:?}", e))?;
+    Ok(module)
+}
+
+fn invoke_export(module: &mut Option<Module>, invoke: &WastInvoke) -> Result<Vec<Value>, Error> {
+    let module = module
+        .as_mut()
+        .expect("the script invokes an export before defining a module");
+    let args = invoke.args.iter().map(arg_value).collect();
+    module.call(invoke.name, args)
+}
+
+fn arg_value(arg: &WastArg) -> Value {
+    match arg {
+        WastArg::Core(WastArgCore::I32(x)) => Value::i32(*x),
+        WastArg::Core(WastArgCore::I64(x)) => Value::i64(*x),
+        WastArg::Core(WastArgCore::F32(x)) => Value::from_bits(PrimitiveType::F32, x.bits as u64),
+        WastArg::Core(WastArgCore::F64(x)) => Value::from_bits(PrimitiveType::F64, x.bits),
+        other => panic!("unsupported argument {:?}", other),
+    }
+}
+
+/// Whether `actual` matches the expected result, comparing floats bit for bit
+fn matches_result(actual: &Value, expected: &WastRet) -> bool {
+    let t = actual.get_type();
+    match expected {
+        WastRet::Core(WastRetCore::I32(x)) => {
+            t == PrimitiveType::I32 && actual.to_bits() as u32 == *x as u32
+        }
+        WastRet::Core(WastRetCore::I64(x)) => {
+            t == PrimitiveType::I64 && actual.to_bits() == *x as u64
+        }
+        WastRet::Core(WastRetCore::F32(pattern)) => {
+            let expected = match pattern {
+                NanPattern::CanonicalNan => NanPattern::CanonicalNan,
+                NanPattern::ArithmeticNan => NanPattern::ArithmeticNan,
+                NanPattern::Value(f) => NanPattern::Value(f.bits as u64),
+            };
+            let bits = actual.as_f32_unchecked().to_bits() as u64;
+            t == PrimitiveType::F32 && matches_float(bits, expected, 8, 23)
+        }
+        WastRet::Core(WastRetCore::F64(pattern)) => {
+            let expected = match pattern {
+                NanPattern::CanonicalNan => NanPattern::CanonicalNan,
+                NanPattern::ArithmeticNan => NanPattern::ArithmeticNan,
+                NanPattern::Value(f) => NanPattern::Value(f.bits),
+            };
+            let bits = actual.as_f64_unchecked().to_bits();
+            t == PrimitiveType::F64 && matches_float(bits, expected, 11, 52)
+        }
+        _ => false,
+    }
+}
+
+/// Whether a float with the given layout and `bits` matches `pattern`. A canonical NaN has
+/// only the top mantissa bit set, and an arithmetic NaN has at least that bit set.
+fn matches_float(
+    bits: u64,
+    pattern: NanPattern<u64>,
+    exponent_bits: u32,
+    mantissa_bits: u32,
+) -> bool {
+    let exponent_mask = ((1 << exponent_bits) - 1) << mantissa_bits;
+    let mantissa = bits & ((1 << mantissa_bits) - 1);
+    let quiet = 1 << (mantissa_bits - 1);
+    let is_nan = bits & exponent_mask == exponent_mask && mantissa != 0;
+    match pattern {
+        NanPattern::CanonicalNan => is_nan && mantissa == quiet,
+        NanPattern::ArithmeticNan => is_nan && mantissa & quiet != 0,
+        NanPattern::Value(expected) => bits == expected,
+    }
+}
+
+/// The message the spec tests use for a trap
+fn spec_message(trap: &Trap) -> &'static str {
+    match trap {
+        Trap::MemoryOutOfBounds => "out of bounds memory access",
+        Trap::DivideByZero => "integer divide by zero",
+        Trap::Unreachable => "unreachable",
+        Trap::IndirectCallTypeMismatch => "indirect call type mismatch",
+        Trap::UndefinedElement => "undefined element",
+        Trap::TableOutOfBounds => "out of bounds table access",
+        Trap::UnalignedAtomic => "unaligned atomic",
+        Trap::Timeout => "timeout",
+        Trap::StackOverflow => "call stack exhausted",
+        Trap::CallStackExhausted => "call stack exhausted",
+        Trap::InvalidConversionToInteger => "invalid conversion to integer",
+        Trap::IntegerOverflow => "integer overflow",
+    }
+}
@@ -0,0 +1,947 @@
+;; Assertions follow the official spec test suite's conversions.wast, limited to the
+;; assert_return and assert_trap directives tests/spec.rs runs
+
+(module
+  (func (export "i64.extend_i32_s") (param $x i32) (result i64) (i64.extend_i32_s (local.get $x)))
+  (func (export "i64.extend_i32_u") (param $x i32) (result i64) (i64.extend_i32_u (local.get $x)))
+  (func (export "i32.wrap_i64") (param $x i64) (result i32) (i32.wrap_i64 (local.get $x)))
+  (func (export "i32.trunc_f32_s") (param $x f32) (result i32) (i32.trunc_f32_s (local.get $x)))
+  (func (export "i32.trunc_f32_u") (param $x f32) (result i32) (i32.trunc_f32_u (local.get $x)))
+  (func (export "i32.trunc_sat_f32_s") (param $x f32) (result i32) (i32.trunc_sat_f32_s (local.get $x)))
+  (func (export "i32.trunc_sat_f32_u") (param $x f32) (result i32) (i32.trunc_sat_f32_u (local.get $x)))
+  (func (export "i64.trunc_f32_s") (param $x f32) (result i64) (i64.trunc_f32_s (local.get $x)))
+  (func (export "i64.trunc_f32_u") (param $x f32) (result i64) (i64.trunc_f32_u (local.get $x)))
+  (func (export "i64.trunc_sat_f32_s") (param $x f32) (result i64) (i64.trunc_sat_f32_s (local.get $x)))
+  (func (export "i64.trunc_sat_f32_u") (param $x f32) (result i64) (i64.trunc_sat_f32_u (local.get $x)))
+  (func (export "i32.trunc_f64_s") (param $x f64) (result i32) (i32.trunc_f64_s (local.get $x)))
+  (func (export "i32.trunc_f64_u") (param $x f64) (result i32) (i32.trunc_f64_u (local.get $x)))
+  (func (export "i32.trunc_sat_f64_s") (param $x f64) (result i32) (i32.trunc_sat_f64_s (local.get $x)))
+  (func (export "i32.trunc_sat_f64_u") (param $x f64) (result i32) (i32.trunc_sat_f64_u (local.get $x)))
+  (func (export "i64.trunc_f64_s") (param $x f64) (result i64) (i64.trunc_f64_s (local.get $x)))
+  (func (export "i64.trunc_f64_u") (param $x f64) (result i64) (i64.trunc_f64_u (local.get $x)))
+  (func (export "i64.trunc_sat_f64_s") (param $x f64) (result i64) (i64.trunc_sat_f64_s (local.get $x)))
+  (func (export "i64.trunc_sat_f64_u") (param $x f64) (result i64) (i64.trunc_sat_f64_u (local.get $x)))
+  (func (export "f32.convert_i32_s") (param $x i32) (result f32) (f32.convert_i32_s (local.get $x)))
+  (func (export "f32.convert_i32_u") (param $x i32) (result f32) (f32.convert_i32_u (local.get $x)))
+  (func (export "f64.convert_i32_s") (param $x i32) (result f64) (f64.convert_i32_s (local.get $x)))
+  (func (export "f64.convert_i32_u") (param $x i32) (result f64) (f64.convert_i32_u (local.get $x)))
+  (func (export "f32.convert_i64_s") (param $x i64) (result f32) (f32.convert_i64_s (local.get $x)))
+  (func (export "f32.convert_i64_u") (param $x i64) (result f32) (f32.convert_i64_u (local.get $x)))
+  (func (export "f64.convert_i64_s") (param $x i64) (result f64) (f64.convert_i64_s (local.get $x)))
+  (func (export "f64.convert_i64_u") (param $x i64) (result f64) (f64.convert_i64_u (local.get $x)))
+  (func (export "f64.promote_f32") (param $x f32) (result f64) (f64.promote_f32 (local.get $x)))
+  (func (export "f32.demote_f64") (param $x f64) (result f32) (f32.demote_f64 (local.get $x)))
+  (func (export "f32.reinterpret_i32") (param $x i32) (result f32) (f32.reinterpret_i32 (local.get $x)))
+  (func (export "f64.reinterpret_i64") (param $x i64) (result f64) (f64.reinterpret_i64 (local.get $x)))
+  (func (export "i32.reinterpret_f32") (param $x f32) (result i32) (i32.reinterpret_f32 (local.get $x)))
+  (func (export "i64.reinterpret_f64") (param $x f64) (result i64) (i64.reinterpret_f64 (local.get $x)))
+)
+
+(assert_return (invoke "i64.extend_i32_s" (i32.const 0)) (i64.const 0))
+(assert_return (invoke "i64.extend_i32_s" (i32.const 1)) (i64.const 1))
+(assert_return (invoke "i64.extend_i32_s" (i32.const -1)) (i64.const -1))
+(assert_return (invoke "i64.extend_i32_s" (i32.const 10000)) (i64.const 10000))
+(assert_return (invoke "i64.extend_i32_s" (i32.const -10000)) (i64.const -10000))
+(assert_return (invoke "i64.extend_i32_s" (i32.const 2147483647)) (i64.const 2147483647))
+(assert_return (invoke "i64.extend_i32_s" (i32.const -2147483648)) (i64.const -2147483648))
+(assert_return (invoke "i64.extend_i32_s" (i32.const 1234567890)) (i64.const 1234567890))
+(assert_return (invoke "i64.extend_i32_s" (i32.const 16777217)) (i64.const 16777217))
+(assert_return (invoke "i64.extend_i32_s" (i32.const -16777217)) (i64.const -16777217))
+(assert_return (invoke "i64.extend_i32_s" (i32.const 16777219)) (i64.const 16777219))
+(assert_return (invoke "i64.extend_i32_s" (i32.const -16777219)) (i64.const -16777219))
+(assert_return (invoke "i64.extend_i32_s" (i32.const -1)) (i64.const -1))
+
+(assert_return (invoke "i64.extend_i32_u" (i32.const 0)) (i64.const 0))
+(assert_return (invoke "i64.extend_i32_u" (i32.const 1)) (i64.const 1))
+(assert_return (invoke "i64.extend_i32_u" (i32.const -1)) (i64.const 4294967295))
+(assert_return (invoke "i64.extend_i32_u" (i32.const 10000)) (i64.const 10000))
+(assert_return (invoke "i64.extend_i32_u" (i32.const -10000)) (i64.const 4294957296))
+(assert_return (invoke "i64.extend_i32_u" (i32.const 2147483647)) (i64.const 2147483647))
+(assert_return (invoke "i64.extend_i32_u" (i32.const -2147483648)) (i64.const 2147483648))
+(assert_return (invoke "i64.extend_i32_u" (i32.const 1234567890)) (i64.const 1234567890))
+(assert_return (invoke "i64.extend_i32_u" (i32.const 16777217)) (i64.const 16777217))
+(assert_return (invoke "i64.extend_i32_u" (i32.const -16777217)) (i64.const 4278190079))
+(assert_return (invoke "i64.extend_i32_u" (i32.const 16777219)) (i64.const 16777219))
+(assert_return (invoke "i64.extend_i32_u" (i32.const -16777219)) (i64.const 4278190077))
+(assert_return (invoke "i64.extend_i32_u" (i32.const -1)) (i64.const 4294967295))
+
+(assert_return (invoke "i32.wrap_i64" (i64.const 0)) (i32.const 0))
+(assert_return (invoke "i32.wrap_i64" (i64.const 1)) (i32.const 1))
+(assert_return (invoke "i32.wrap_i64" (i64.const -1)) (i32.const -1))
+(assert_return (invoke "i32.wrap_i64" (i64.const 10000)) (i32.const 10000))
+(assert_return (invoke "i32.wrap_i64" (i64.const -10000)) (i32.const -10000))
+(assert_return (invoke "i32.wrap_i64" (i64.const 9223372036854775807)) (i32.const -1))
+(assert_return (invoke "i32.wrap_i64" (i64.const -9223372036854775808)) (i32.const 0))
+(assert_return (invoke "i32.wrap_i64" (i64.const 314159265358979)) (i32.const -412474237))
+(assert_return (invoke "i32.wrap_i64" (i64.const 16777217)) (i32.const 16777217))
+(assert_return (invoke "i32.wrap_i64" (i64.const -16777217)) (i32.const -16777217))
+(assert_return (invoke "i32.wrap_i64" (i64.const 16777219)) (i32.const 16777219))
+(assert_return (invoke "i32.wrap_i64" (i64.const -16777219)) (i32.const -16777219))
+(assert_return (invoke "i32.wrap_i64" (i64.const 9223371212221054977)) (i32.const 1))
+(assert_return (invoke "i32.wrap_i64" (i64.const -9223371487098961919)) (i32.const 1))
+(assert_return (invoke "i32.wrap_i64" (i64.const 9007199791611905)) (i32.const 536870913))
+(assert_return (invoke "i32.wrap_i64" (i64.const 9007199254740993)) (i32.const 1))
+(assert_return (invoke "i32.wrap_i64" (i64.const -9007199254740993)) (i32.const -1))
+(assert_return (invoke "i32.wrap_i64" (i64.const 9007199254740995)) (i32.const 3))
+(assert_return (invoke "i32.wrap_i64" (i64.const -2147483649)) (i32.const 2147483647))
+(assert_return (invoke "i32.wrap_i64" (i64.const -4294967296)) (i32.const 0))
+(assert_return (invoke "i32.wrap_i64" (i64.const -4294967297)) (i32.const -1))
+(assert_return (invoke "i32.wrap_i64" (i64.const -4294967295)) (i32.const 1))
+(assert_return (invoke "i32.wrap_i64" (i64.const 4294967295)) (i32.const -1))
+(assert_return (invoke "i32.wrap_i64" (i64.const 4294967296)) (i32.const 0))
+(assert_return (invoke "i32.wrap_i64" (i64.const 4294967297)) (i32.const 1))
+(assert_return (invoke "i32.wrap_i64" (i64.const -1)) (i32.const -1))
+
+(assert_return (invoke "i32.trunc_f32_s" (f32.const 0x0.0p+0)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f32_s" (f32.const -0x0.0p+0)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f32_s" (f32.const 0x1.0000000000000p-149)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f32_s" (f32.const -0x1.0000000000000p-149)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f32_s" (f32.const 0x1.0000000000000p-126)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f32_s" (f32.const 0x1.0000000000000p+0)) (i32.const 1))
+(assert_return (invoke "i32.trunc_f32_s" (f32.const 0x1.19999a0000000p+0)) (i32.const 1))
+(assert_return (invoke "i32.trunc_f32_s" (f32.const 0x1.8000000000000p+0)) (i32.const 1))
+(assert_return (invoke "i32.trunc_f32_s" (f32.const -0x1.0000000000000p+0)) (i32.const -1))
+(assert_return (invoke "i32.trunc_f32_s" (f32.const -0x1.19999a0000000p+0)) (i32.const -1))
+(assert_return (invoke "i32.trunc_f32_s" (f32.const -0x1.8000000000000p+0)) (i32.const -1))
+(assert_return (invoke "i32.trunc_f32_s" (f32.const -0x1.e666660000000p+0)) (i32.const -1))
+(assert_return (invoke "i32.trunc_f32_s" (f32.const -0x1.0000000000000p+1)) (i32.const -2))
+(assert_return (invoke "i32.trunc_f32_s" (f32.const -0x1.cccccc0000000p-1)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f32_s" (f32.const 0x1.fffffe0000000p+30)) (i32.const 2147483520))
+(assert_return (invoke "i32.trunc_f32_s" (f32.const -0x1.0000000000000p+31)) (i32.const -2147483648))
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const 0x1.0000000000000p+31)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const -0x1.0000020000000p+31)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const 0x1.fffffe0000000p+31)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const 0x1.0000000000000p+32)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const 0x1.fffffe0000000p+62)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const -0x1.0000000000000p+63)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const 0x1.0000000000000p+63)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const -0x1.0000020000000p+63)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const 0x1.fffffe0000000p+63)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const 0x1.0000000000000p+64)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const inf)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const -inf)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const nan:0x400000)) "invalid conversion to integer")
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const nan:0x200000)) "invalid conversion to integer")
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const -nan:0x400000)) "invalid conversion to integer")
+(assert_trap (invoke "i32.trunc_f32_s" (f32.const -nan:0x200000)) "invalid conversion to integer")
+
+(assert_return (invoke "i32.trunc_f32_u" (f32.const 0x0.0p+0)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f32_u" (f32.const -0x0.0p+0)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f32_u" (f32.const 0x1.0000000000000p-149)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f32_u" (f32.const -0x1.0000000000000p-149)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f32_u" (f32.const 0x1.0000000000000p-126)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f32_u" (f32.const 0x1.0000000000000p+0)) (i32.const 1))
+(assert_return (invoke "i32.trunc_f32_u" (f32.const 0x1.19999a0000000p+0)) (i32.const 1))
+(assert_return (invoke "i32.trunc_f32_u" (f32.const 0x1.8000000000000p+0)) (i32.const 1))
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const -0x1.0000000000000p+0)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const -0x1.19999a0000000p+0)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const -0x1.8000000000000p+0)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const -0x1.e666660000000p+0)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const -0x1.0000000000000p+1)) "integer overflow")
+(assert_return (invoke "i32.trunc_f32_u" (f32.const -0x1.cccccc0000000p-1)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f32_u" (f32.const 0x1.fffffe0000000p+30)) (i32.const 2147483520))
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const -0x1.0000000000000p+31)) "integer overflow")
+(assert_return (invoke "i32.trunc_f32_u" (f32.const 0x1.0000000000000p+31)) (i32.const -2147483648))
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const -0x1.0000020000000p+31)) "integer overflow")
+(assert_return (invoke "i32.trunc_f32_u" (f32.const 0x1.fffffe0000000p+31)) (i32.const -256))
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const 0x1.0000000000000p+32)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const 0x1.fffffe0000000p+62)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const -0x1.0000000000000p+63)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const 0x1.0000000000000p+63)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const -0x1.0000020000000p+63)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const 0x1.fffffe0000000p+63)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const 0x1.0000000000000p+64)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const inf)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const -inf)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const nan:0x400000)) "invalid conversion to integer")
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const nan:0x200000)) "invalid conversion to integer")
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const -nan:0x400000)) "invalid conversion to integer")
+(assert_trap (invoke "i32.trunc_f32_u" (f32.const -nan:0x200000)) "invalid conversion to integer")
+
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const 0x0.0p+0)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const -0x0.0p+0)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const 0x1.0000000000000p-149)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const -0x1.0000000000000p-149)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const 0x1.0000000000000p-126)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const 0x1.0000000000000p+0)) (i32.const 1))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const 0x1.19999a0000000p+0)) (i32.const 1))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const 0x1.8000000000000p+0)) (i32.const 1))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const -0x1.0000000000000p+0)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const -0x1.19999a0000000p+0)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const -0x1.8000000000000p+0)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const -0x1.e666660000000p+0)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const -0x1.0000000000000p+1)) (i32.const -2))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const -0x1.cccccc0000000p-1)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const 0x1.fffffe0000000p+30)) (i32.const 2147483520))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const -0x1.0000000000000p+31)) (i32.const -2147483648))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const 0x1.0000000000000p+31)) (i32.const 2147483647))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const -0x1.0000020000000p+31)) (i32.const -2147483648))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const 0x1.fffffe0000000p+31)) (i32.const 2147483647))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const 0x1.0000000000000p+32)) (i32.const 2147483647))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const 0x1.fffffe0000000p+62)) (i32.const 2147483647))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const -0x1.0000000000000p+63)) (i32.const -2147483648))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const 0x1.0000000000000p+63)) (i32.const 2147483647))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const -0x1.0000020000000p+63)) (i32.const -2147483648))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const 0x1.fffffe0000000p+63)) (i32.const 2147483647))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const 0x1.0000000000000p+64)) (i32.const 2147483647))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const inf)) (i32.const 2147483647))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const -inf)) (i32.const -2147483648))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const nan:0x400000)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const nan:0x200000)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const -nan:0x400000)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_s" (f32.const -nan:0x200000)) (i32.const 0))
+
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const 0x0.0p+0)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const -0x0.0p+0)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const 0x1.0000000000000p-149)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const -0x1.0000000000000p-149)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const 0x1.0000000000000p-126)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const 0x1.0000000000000p+0)) (i32.const 1))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const 0x1.19999a0000000p+0)) (i32.const 1))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const 0x1.8000000000000p+0)) (i32.const 1))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const -0x1.0000000000000p+0)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const -0x1.19999a0000000p+0)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const -0x1.8000000000000p+0)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const -0x1.e666660000000p+0)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const -0x1.0000000000000p+1)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const -0x1.cccccc0000000p-1)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const 0x1.fffffe0000000p+30)) (i32.const 2147483520))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const -0x1.0000000000000p+31)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const 0x1.0000000000000p+31)) (i32.const -2147483648))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const -0x1.0000020000000p+31)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const 0x1.fffffe0000000p+31)) (i32.const -256))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const 0x1.0000000000000p+32)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const 0x1.fffffe0000000p+62)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const -0x1.0000000000000p+63)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const 0x1.0000000000000p+63)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const -0x1.0000020000000p+63)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const 0x1.fffffe0000000p+63)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const 0x1.0000000000000p+64)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const inf)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const -inf)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const nan:0x400000)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const nan:0x200000)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const -nan:0x400000)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f32_u" (f32.const -nan:0x200000)) (i32.const 0))
+
+(assert_return (invoke "i64.trunc_f32_s" (f32.const 0x0.0p+0)) (i64.const 0))
+(assert_return (invoke "i64.trunc_f32_s" (f32.const -0x0.0p+0)) (i64.const 0))
+(assert_return (invoke "i64.trunc_f32_s" (f32.const 0x1.0000000000000p-149)) (i64.const 0))
+(assert_return (invoke "i64.trunc_f32_s" (f32.const -0x1.0000000000000p-149)) (i64.const 0))
+(assert_return (invoke "i64.trunc_f32_s" (f32.const 0x1.0000000000000p-126)) (i64.const 0))
+(assert_return (invoke "i64.trunc_f32_s" (f32.const 0x1.0000000000000p+0)) (i64.const 1))
+(assert_return (invoke "i64.trunc_f32_s" (f32.const 0x1.19999a0000000p+0)) (i64.const 1))
+(assert_return (invoke "i64.trunc_f32_s" (f32.const 0x1.8000000000000p+0)) (i64.const 1))
+(assert_return (invoke "i64.trunc_f32_s" (f32.const -0x1.0000000000000p+0)) (i64.const -1))
+(assert_return (invoke "i64.trunc_f32_s" (f32.const -0x1.19999a0000000p+0)) (i64.const -1))
+(assert_return (invoke "i64.trunc_f32_s" (f32.const -0x1.8000000000000p+0)) (i64.const -1))
+(assert_return (invoke "i64.trunc_f32_s" (f32.const -0x1.e666660000000p+0)) (i64.const -1))
+(assert_return (invoke "i64.trunc_f32_s" (f32.const -0x1.0000000000000p+1)) (i64.const -2))
+(assert_return (invoke "i64.trunc_f32_s" (f32.const -0x1.cccccc0000000p-1)) (i64.const 0))
+(assert_return (invoke "i64.trunc_f32_s" (f32.const 0x1.fffffe0000000p+30)) (i64.const 2147483520))
+(assert_return (invoke "i64.trunc_f32_s" (f32.const -0x1.0000000000000p+31)) (i64.const -2147483648))
+(assert_return (invoke "i64.trunc_f32_s" (f32.const 0x1.0000000000000p+31)) (i64.const 2147483648))
+(assert_return (invoke "i64.trunc_f32_s" (f32.const -0x1.0000020000000p+31)) (i64.const -2147483904))
+(assert_return (invoke "i64.trunc_f32_s" (f32.const 0x1.fffffe0000000p+31)) (i64.const 4294967040))
+(assert_return (invoke "i64.trunc_f32_s" (f32.const 0x1.0000000000000p+32)) (i64.const 4294967296))
+(assert_return (invoke "i64.trunc_f32_s" (f32.const 0x1.fffffe0000000p+62)) (i64.const 9223371487098961920))
+(assert_return (invoke "i64.trunc_f32_s" (f32.const -0x1.0000000000000p+63)) (i64.const -9223372036854775808))
+(assert_trap (invoke "i64.trunc_f32_s" (f32.const 0x1.0000000000000p+63)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f32_s" (f32.const -0x1.0000020000000p+63)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f32_s" (f32.const 0x1.fffffe0000000p+63)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f32_s" (f32.const 0x1.0000000000000p+64)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f32_s" (f32.const inf)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f32_s" (f32.const -inf)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f32_s" (f32.const nan:0x400000)) "invalid conversion to integer")
+(assert_trap (invoke "i64.trunc_f32_s" (f32.const nan:0x200000)) "invalid conversion to integer")
+(assert_trap (invoke "i64.trunc_f32_s" (f32.const -nan:0x400000)) "invalid conversion to integer")
+(assert_trap (invoke "i64.trunc_f32_s" (f32.const -nan:0x200000)) "invalid conversion to integer")
+
+(assert_return (invoke "i64.trunc_f32_u" (f32.const 0x0.0p+0)) (i64.const 0))
+(assert_return (invoke "i64.trunc_f32_u" (f32.const -0x0.0p+0)) (i64.const 0))
+(assert_return (invoke "i64.trunc_f32_u" (f32.const 0x1.0000000000000p-149)) (i64.const 0))
+(assert_return (invoke "i64.trunc_f32_u" (f32.const -0x1.0000000000000p-149)) (i64.const 0))
+(assert_return (invoke "i64.trunc_f32_u" (f32.const 0x1.0000000000000p-126)) (i64.const 0))
+(assert_return (invoke "i64.trunc_f32_u" (f32.const 0x1.0000000000000p+0)) (i64.const 1))
+(assert_return (invoke "i64.trunc_f32_u" (f32.const 0x1.19999a0000000p+0)) (i64.const 1))
+(assert_return (invoke "i64.trunc_f32_u" (f32.const 0x1.8000000000000p+0)) (i64.const 1))
+(assert_trap (invoke "i64.trunc_f32_u" (f32.const -0x1.0000000000000p+0)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f32_u" (f32.const -0x1.19999a0000000p+0)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f32_u" (f32.const -0x1.8000000000000p+0)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f32_u" (f32.const -0x1.e666660000000p+0)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f32_u" (f32.const -0x1.0000000000000p+1)) "integer overflow")
+(assert_return (invoke "i64.trunc_f32_u" (f32.const -0x1.cccccc0000000p-1)) (i64.const 0))
+(assert_return (invoke "i64.trunc_f32_u" (f32.const 0x1.fffffe0000000p+30)) (i64.const 2147483520))
+(assert_trap (invoke "i64.trunc_f32_u" (f32.const -0x1.0000000000000p+31)) "integer overflow")
+(assert_return (invoke "i64.trunc_f32_u" (f32.const 0x1.0000000000000p+31)) (i64.const 2147483648))
+(assert_trap (invoke "i64.trunc_f32_u" (f32.const -0x1.0000020000000p+31)) "integer overflow")
+(assert_return (invoke "i64.trunc_f32_u" (f32.const 0x1.fffffe0000000p+31)) (i64.const 4294967040))
+(assert_return (invoke "i64.trunc_f32_u" (f32.const 0x1.0000000000000p+32)) (i64.const 4294967296))
+(assert_return (invoke "i64.trunc_f32_u" (f32.const 0x1.fffffe0000000p+62)) (i64.const 9223371487098961920))
+(assert_trap (invoke "i64.trunc_f32_u" (f32.const -0x1.0000000000000p+63)) "integer overflow")
+(assert_return (invoke "i64.trunc_f32_u" (f32.const 0x1.0000000000000p+63)) (i64.const -9223372036854775808))
+(assert_trap (invoke "i64.trunc_f32_u" (f32.const -0x1.0000020000000p+63)) "integer overflow")
+(assert_return (invoke "i64.trunc_f32_u" (f32.const 0x1.fffffe0000000p+63)) (i64.const -1099511627776))
+(assert_trap (invoke "i64.trunc_f32_u" (f32.const 0x1.0000000000000p+64)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f32_u" (f32.const inf)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f32_u" (f32.const -inf)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f32_u" (f32.const nan:0x400000)) "invalid conversion to integer")
+(assert_trap (invoke "i64.trunc_f32_u" (f32.const nan:0x200000)) "invalid conversion to integer")
+(assert_trap (invoke "i64.trunc_f32_u" (f32.const -nan:0x400000)) "invalid conversion to integer")
+(assert_trap (invoke "i64.trunc_f32_u" (f32.const -nan:0x200000)) "invalid conversion to integer")
+
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const 0x0.0p+0)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const -0x0.0p+0)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const 0x1.0000000000000p-149)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const -0x1.0000000000000p-149)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const 0x1.0000000000000p-126)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const 0x1.0000000000000p+0)) (i64.const 1))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const 0x1.19999a0000000p+0)) (i64.const 1))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const 0x1.8000000000000p+0)) (i64.const 1))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const -0x1.0000000000000p+0)) (i64.const -1))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const -0x1.19999a0000000p+0)) (i64.const -1))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const -0x1.8000000000000p+0)) (i64.const -1))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const -0x1.e666660000000p+0)) (i64.const -1))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const -0x1.0000000000000p+1)) (i64.const -2))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const -0x1.cccccc0000000p-1)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const 0x1.fffffe0000000p+30)) (i64.const 2147483520))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const -0x1.0000000000000p+31)) (i64.const -2147483648))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const 0x1.0000000000000p+31)) (i64.const 2147483648))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const -0x1.0000020000000p+31)) (i64.const -2147483904))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const 0x1.fffffe0000000p+31)) (i64.const 4294967040))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const 0x1.0000000000000p+32)) (i64.const 4294967296))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const 0x1.fffffe0000000p+62)) (i64.const 9223371487098961920))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const -0x1.0000000000000p+63)) (i64.const -9223372036854775808))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const 0x1.0000000000000p+63)) (i64.const 9223372036854775807))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const -0x1.0000020000000p+63)) (i64.const -9223372036854775808))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const 0x1.fffffe0000000p+63)) (i64.const 9223372036854775807))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const 0x1.0000000000000p+64)) (i64.const 9223372036854775807))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const inf)) (i64.const 9223372036854775807))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const -inf)) (i64.const -9223372036854775808))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const nan:0x400000)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const nan:0x200000)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const -nan:0x400000)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_s" (f32.const -nan:0x200000)) (i64.const 0))
+
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const 0x0.0p+0)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const -0x0.0p+0)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const 0x1.0000000000000p-149)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const -0x1.0000000000000p-149)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const 0x1.0000000000000p-126)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const 0x1.0000000000000p+0)) (i64.const 1))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const 0x1.19999a0000000p+0)) (i64.const 1))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const 0x1.8000000000000p+0)) (i64.const 1))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const -0x1.0000000000000p+0)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const -0x1.19999a0000000p+0)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const -0x1.8000000000000p+0)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const -0x1.e666660000000p+0)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const -0x1.0000000000000p+1)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const -0x1.cccccc0000000p-1)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const 0x1.fffffe0000000p+30)) (i64.const 2147483520))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const -0x1.0000000000000p+31)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const 0x1.0000000000000p+31)) (i64.const 2147483648))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const -0x1.0000020000000p+31)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const 0x1.fffffe0000000p+31)) (i64.const 4294967040))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const 0x1.0000000000000p+32)) (i64.const 4294967296))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const 0x1.fffffe0000000p+62)) (i64.const 9223371487098961920))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const -0x1.0000000000000p+63)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const 0x1.0000000000000p+63)) (i64.const -9223372036854775808))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const -0x1.0000020000000p+63)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const 0x1.fffffe0000000p+63)) (i64.const -1099511627776))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const 0x1.0000000000000p+64)) (i64.const -1))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const inf)) (i64.const -1))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const -inf)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const nan:0x400000)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const nan:0x200000)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const -nan:0x400000)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f32_u" (f32.const -nan:0x200000)) (i64.const 0))
+
+(assert_return (invoke "i32.trunc_f64_s" (f64.const 0x0.0p+0)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f64_s" (f64.const -0x0.0p+0)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f64_s" (f64.const 0x0.0000000000001p-1022)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f64_s" (f64.const -0x0.0000000000001p-1022)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f64_s" (f64.const 0x1.0000000000000p-1022)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f64_s" (f64.const 0x1.0000000000000p+0)) (i32.const 1))
+(assert_return (invoke "i32.trunc_f64_s" (f64.const 0x1.199999999999ap+0)) (i32.const 1))
+(assert_return (invoke "i32.trunc_f64_s" (f64.const 0x1.8000000000000p+0)) (i32.const 1))
+(assert_return (invoke "i32.trunc_f64_s" (f64.const -0x1.0000000000000p+0)) (i32.const -1))
+(assert_return (invoke "i32.trunc_f64_s" (f64.const -0x1.8000000000000p+0)) (i32.const -1))
+(assert_return (invoke "i32.trunc_f64_s" (f64.const -0x1.e666666666666p+0)) (i32.const -1))
+(assert_return (invoke "i32.trunc_f64_s" (f64.const -0x1.0000000000000p+1)) (i32.const -2))
+(assert_return (invoke "i32.trunc_f64_s" (f64.const -0x1.fffffffffffffp-1)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f64_s" (f64.const 0x1.fffffffc00000p+30)) (i32.const 2147483647))
+(assert_return (invoke "i32.trunc_f64_s" (f64.const -0x1.0000000000000p+31)) (i32.const -2147483648))
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const 0x1.0000000000000p+31)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const -0x1.0000000200000p+31)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const 0x1.fffffffe00000p+31)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const 0x1.0000000000000p+32)) "integer overflow")
+(assert_return (invoke "i32.trunc_f64_s" (f64.const 0x1.7d78400000000p+26)) (i32.const 100000000))
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const 0x1.1c37937e08000p+53)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const 0x1.93e5939a08ceap+99)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const 0x1.fffffffffffffp+62)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const -0x1.0000000000000p+63)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const 0x1.0000000000000p+63)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const -0x1.0000000000001p+63)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const 0x1.fffffffffffffp+63)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const 0x1.0000000000000p+64)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const inf)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const -inf)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const nan:0x8000000000000)) "invalid conversion to integer")
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const nan:0x4000000000000)) "invalid conversion to integer")
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const -nan:0x8000000000000)) "invalid conversion to integer")
+(assert_trap (invoke "i32.trunc_f64_s" (f64.const -nan:0x4000000000000)) "invalid conversion to integer")
+
+(assert_return (invoke "i32.trunc_f64_u" (f64.const 0x0.0p+0)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f64_u" (f64.const -0x0.0p+0)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f64_u" (f64.const 0x0.0000000000001p-1022)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f64_u" (f64.const -0x0.0000000000001p-1022)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f64_u" (f64.const 0x1.0000000000000p-1022)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f64_u" (f64.const 0x1.0000000000000p+0)) (i32.const 1))
+(assert_return (invoke "i32.trunc_f64_u" (f64.const 0x1.199999999999ap+0)) (i32.const 1))
+(assert_return (invoke "i32.trunc_f64_u" (f64.const 0x1.8000000000000p+0)) (i32.const 1))
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const -0x1.0000000000000p+0)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const -0x1.8000000000000p+0)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const -0x1.e666666666666p+0)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const -0x1.0000000000000p+1)) "integer overflow")
+(assert_return (invoke "i32.trunc_f64_u" (f64.const -0x1.fffffffffffffp-1)) (i32.const 0))
+(assert_return (invoke "i32.trunc_f64_u" (f64.const 0x1.fffffffc00000p+30)) (i32.const 2147483647))
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const -0x1.0000000000000p+31)) "integer overflow")
+(assert_return (invoke "i32.trunc_f64_u" (f64.const 0x1.0000000000000p+31)) (i32.const -2147483648))
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const -0x1.0000000200000p+31)) "integer overflow")
+(assert_return (invoke "i32.trunc_f64_u" (f64.const 0x1.fffffffe00000p+31)) (i32.const -1))
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const 0x1.0000000000000p+32)) "integer overflow")
+(assert_return (invoke "i32.trunc_f64_u" (f64.const 0x1.7d78400000000p+26)) (i32.const 100000000))
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const 0x1.1c37937e08000p+53)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const 0x1.93e5939a08ceap+99)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const 0x1.fffffffffffffp+62)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const -0x1.0000000000000p+63)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const 0x1.0000000000000p+63)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const -0x1.0000000000001p+63)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const 0x1.fffffffffffffp+63)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const 0x1.0000000000000p+64)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const inf)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const -inf)) "integer overflow")
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const nan:0x8000000000000)) "invalid conversion to integer")
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const nan:0x4000000000000)) "invalid conversion to integer")
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const -nan:0x8000000000000)) "invalid conversion to integer")
+(assert_trap (invoke "i32.trunc_f64_u" (f64.const -nan:0x4000000000000)) "invalid conversion to integer")
+
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const 0x0.0p+0)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const -0x0.0p+0)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const 0x0.0000000000001p-1022)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const -0x0.0000000000001p-1022)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const 0x1.0000000000000p-1022)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const 0x1.0000000000000p+0)) (i32.const 1))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const 0x1.199999999999ap+0)) (i32.const 1))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const 0x1.8000000000000p+0)) (i32.const 1))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const -0x1.0000000000000p+0)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const -0x1.8000000000000p+0)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const -0x1.e666666666666p+0)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const -0x1.0000000000000p+1)) (i32.const -2))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const -0x1.fffffffffffffp-1)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const 0x1.fffffffc00000p+30)) (i32.const 2147483647))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const -0x1.0000000000000p+31)) (i32.const -2147483648))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const 0x1.0000000000000p+31)) (i32.const 2147483647))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const -0x1.0000000200000p+31)) (i32.const -2147483648))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const 0x1.fffffffe00000p+31)) (i32.const 2147483647))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const 0x1.0000000000000p+32)) (i32.const 2147483647))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const 0x1.7d78400000000p+26)) (i32.const 100000000))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const 0x1.1c37937e08000p+53)) (i32.const 2147483647))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const 0x1.93e5939a08ceap+99)) (i32.const 2147483647))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const 0x1.fffffffffffffp+62)) (i32.const 2147483647))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const -0x1.0000000000000p+63)) (i32.const -2147483648))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const 0x1.0000000000000p+63)) (i32.const 2147483647))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const -0x1.0000000000001p+63)) (i32.const -2147483648))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const 0x1.fffffffffffffp+63)) (i32.const 2147483647))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const 0x1.0000000000000p+64)) (i32.const 2147483647))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const inf)) (i32.const 2147483647))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const -inf)) (i32.const -2147483648))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const nan:0x8000000000000)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const nan:0x4000000000000)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const -nan:0x8000000000000)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_s" (f64.const -nan:0x4000000000000)) (i32.const 0))
+
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const 0x0.0p+0)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const -0x0.0p+0)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const 0x0.0000000000001p-1022)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const -0x0.0000000000001p-1022)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const 0x1.0000000000000p-1022)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const 0x1.0000000000000p+0)) (i32.const 1))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const 0x1.199999999999ap+0)) (i32.const 1))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const 0x1.8000000000000p+0)) (i32.const 1))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const -0x1.0000000000000p+0)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const -0x1.8000000000000p+0)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const -0x1.e666666666666p+0)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const -0x1.0000000000000p+1)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const -0x1.fffffffffffffp-1)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const 0x1.fffffffc00000p+30)) (i32.const 2147483647))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const -0x1.0000000000000p+31)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const 0x1.0000000000000p+31)) (i32.const -2147483648))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const -0x1.0000000200000p+31)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const 0x1.fffffffe00000p+31)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const 0x1.0000000000000p+32)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const 0x1.7d78400000000p+26)) (i32.const 100000000))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const 0x1.1c37937e08000p+53)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const 0x1.93e5939a08ceap+99)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const 0x1.fffffffffffffp+62)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const -0x1.0000000000000p+63)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const 0x1.0000000000000p+63)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const -0x1.0000000000001p+63)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const 0x1.fffffffffffffp+63)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const 0x1.0000000000000p+64)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const inf)) (i32.const -1))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const -inf)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const nan:0x8000000000000)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const nan:0x4000000000000)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const -nan:0x8000000000000)) (i32.const 0))
+(assert_return (invoke "i32.trunc_sat_f64_u" (f64.const -nan:0x4000000000000)) (i32.const 0))
+
+(assert_return (invoke "i64.trunc_f64_s" (f64.const 0x0.0p+0)) (i64.const 0))
+(assert_return (invoke "i64.trunc_f64_s" (f64.const -0x0.0p+0)) (i64.const 0))
+(assert_return (invoke "i64.trunc_f64_s" (f64.const 0x0.0000000000001p-1022)) (i64.const 0))
+(assert_return (invoke "i64.trunc_f64_s" (f64.const -0x0.0000000000001p-1022)) (i64.const 0))
+(assert_return (invoke "i64.trunc_f64_s" (f64.const 0x1.0000000000000p-1022)) (i64.const 0))
+(assert_return (invoke "i64.trunc_f64_s" (f64.const 0x1.0000000000000p+0)) (i64.const 1))
+(assert_return (invoke "i64.trunc_f64_s" (f64.const 0x1.199999999999ap+0)) (i64.const 1))
+(assert_return (invoke "i64.trunc_f64_s" (f64.const 0x1.8000000000000p+0)) (i64.const 1))
+(assert_return (invoke "i64.trunc_f64_s" (f64.const -0x1.0000000000000p+0)) (i64.const -1))
+(assert_return (invoke "i64.trunc_f64_s" (f64.const -0x1.8000000000000p+0)) (i64.const -1))
+(assert_return (invoke "i64.trunc_f64_s" (f64.const -0x1.e666666666666p+0)) (i64.const -1))
+(assert_return (invoke "i64.trunc_f64_s" (f64.const -0x1.0000000000000p+1)) (i64.const -2))
+(assert_return (invoke "i64.trunc_f64_s" (f64.const -0x1.fffffffffffffp-1)) (i64.const 0))
+(assert_return (invoke "i64.trunc_f64_s" (f64.const 0x1.fffffffc00000p+30)) (i64.const 2147483647))
+(assert_return (invoke "i64.trunc_f64_s" (f64.const -0x1.0000000000000p+31)) (i64.const -2147483648))
+(assert_return (invoke "i64.trunc_f64_s" (f64.const 0x1.0000000000000p+31)) (i64.const 2147483648))
+(assert_return (invoke "i64.trunc_f64_s" (f64.const -0x1.0000000200000p+31)) (i64.const -2147483649))
+(assert_return (invoke "i64.trunc_f64_s" (f64.const 0x1.fffffffe00000p+31)) (i64.const 4294967295))
+(assert_return (invoke "i64.trunc_f64_s" (f64.const 0x1.0000000000000p+32)) (i64.const 4294967296))
+(assert_return (invoke "i64.trunc_f64_s" (f64.const 0x1.7d78400000000p+26)) (i64.const 100000000))
+(assert_return (invoke "i64.trunc_f64_s" (f64.const 0x1.1c37937e08000p+53)) (i64.const 10000000000000000))
+(assert_trap (invoke "i64.trunc_f64_s" (f64.const 0x1.93e5939a08ceap+99)) "integer overflow")
+(assert_return (invoke "i64.trunc_f64_s" (f64.const 0x1.fffffffffffffp+62)) (i64.const 9223372036854774784))
+(assert_return (invoke "i64.trunc_f64_s" (f64.const -0x1.0000000000000p+63)) (i64.const -9223372036854775808))
+(assert_trap (invoke "i64.trunc_f64_s" (f64.const 0x1.0000000000000p+63)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f64_s" (f64.const -0x1.0000000000001p+63)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f64_s" (f64.const 0x1.fffffffffffffp+63)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f64_s" (f64.const 0x1.0000000000000p+64)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f64_s" (f64.const inf)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f64_s" (f64.const -inf)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f64_s" (f64.const nan:0x8000000000000)) "invalid conversion to integer")
+(assert_trap (invoke "i64.trunc_f64_s" (f64.const nan:0x4000000000000)) "invalid conversion to integer")
+(assert_trap (invoke "i64.trunc_f64_s" (f64.const -nan:0x8000000000000)) "invalid conversion to integer")
+(assert_trap (invoke "i64.trunc_f64_s" (f64.const -nan:0x4000000000000)) "invalid conversion to integer")
+
+(assert_return (invoke "i64.trunc_f64_u" (f64.const 0x0.0p+0)) (i64.const 0))
+(assert_return (invoke "i64.trunc_f64_u" (f64.const -0x0.0p+0)) (i64.const 0))
+(assert_return (invoke "i64.trunc_f64_u" (f64.const 0x0.0000000000001p-1022)) (i64.const 0))
+(assert_return (invoke "i64.trunc_f64_u" (f64.const -0x0.0000000000001p-1022)) (i64.const 0))
+(assert_return (invoke "i64.trunc_f64_u" (f64.const 0x1.0000000000000p-1022)) (i64.const 0))
+(assert_return (invoke "i64.trunc_f64_u" (f64.const 0x1.0000000000000p+0)) (i64.const 1))
+(assert_return (invoke "i64.trunc_f64_u" (f64.const 0x1.199999999999ap+0)) (i64.const 1))
+(assert_return (invoke "i64.trunc_f64_u" (f64.const 0x1.8000000000000p+0)) (i64.const 1))
+(assert_trap (invoke "i64.trunc_f64_u" (f64.const -0x1.0000000000000p+0)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f64_u" (f64.const -0x1.8000000000000p+0)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f64_u" (f64.const -0x1.e666666666666p+0)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f64_u" (f64.const -0x1.0000000000000p+1)) "integer overflow")
+(assert_return (invoke "i64.trunc_f64_u" (f64.const -0x1.fffffffffffffp-1)) (i64.const 0))
+(assert_return (invoke "i64.trunc_f64_u" (f64.const 0x1.fffffffc00000p+30)) (i64.const 2147483647))
+(assert_trap (invoke "i64.trunc_f64_u" (f64.const -0x1.0000000000000p+31)) "integer overflow")
+(assert_return (invoke "i64.trunc_f64_u" (f64.const 0x1.0000000000000p+31)) (i64.const 2147483648))
+(assert_trap (invoke "i64.trunc_f64_u" (f64.const -0x1.0000000200000p+31)) "integer overflow")
+(assert_return (invoke "i64.trunc_f64_u" (f64.const 0x1.fffffffe00000p+31)) (i64.const 4294967295))
+(assert_return (invoke "i64.trunc_f64_u" (f64.const 0x1.0000000000000p+32)) (i64.const 4294967296))
+(assert_return (invoke "i64.trunc_f64_u" (f64.const 0x1.7d78400000000p+26)) (i64.const 100000000))
+(assert_return (invoke "i64.trunc_f64_u" (f64.const 0x1.1c37937e08000p+53)) (i64.const 10000000000000000))
+(assert_trap (invoke "i64.trunc_f64_u" (f64.const 0x1.93e5939a08ceap+99)) "integer overflow")
+(assert_return (invoke "i64.trunc_f64_u" (f64.const 0x1.fffffffffffffp+62)) (i64.const 9223372036854774784))
+(assert_trap (invoke "i64.trunc_f64_u" (f64.const -0x1.0000000000000p+63)) "integer overflow")
+(assert_return (invoke "i64.trunc_f64_u" (f64.const 0x1.0000000000000p+63)) (i64.const -9223372036854775808))
+(assert_trap (invoke "i64.trunc_f64_u" (f64.const -0x1.0000000000001p+63)) "integer overflow")
+(assert_return (invoke "i64.trunc_f64_u" (f64.const 0x1.fffffffffffffp+63)) (i64.const -2048))
+(assert_trap (invoke "i64.trunc_f64_u" (f64.const 0x1.0000000000000p+64)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f64_u" (f64.const inf)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f64_u" (f64.const -inf)) "integer overflow")
+(assert_trap (invoke "i64.trunc_f64_u" (f64.const nan:0x8000000000000)) "invalid conversion to integer")
+(assert_trap (invoke "i64.trunc_f64_u" (f64.const nan:0x4000000000000)) "invalid conversion to integer")
+(assert_trap (invoke "i64.trunc_f64_u" (f64.const -nan:0x8000000000000)) "invalid conversion to integer")
+(assert_trap (invoke "i64.trunc_f64_u" (f64.const -nan:0x4000000000000)) "invalid conversion to integer")
+
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const 0x0.0p+0)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const -0x0.0p+0)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const 0x0.0000000000001p-1022)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const -0x0.0000000000001p-1022)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const 0x1.0000000000000p-1022)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const 0x1.0000000000000p+0)) (i64.const 1))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const 0x1.199999999999ap+0)) (i64.const 1))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const 0x1.8000000000000p+0)) (i64.const 1))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const -0x1.0000000000000p+0)) (i64.const -1))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const -0x1.8000000000000p+0)) (i64.const -1))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const -0x1.e666666666666p+0)) (i64.const -1))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const -0x1.0000000000000p+1)) (i64.const -2))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const -0x1.fffffffffffffp-1)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const 0x1.fffffffc00000p+30)) (i64.const 2147483647))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const -0x1.0000000000000p+31)) (i64.const -2147483648))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const 0x1.0000000000000p+31)) (i64.const 2147483648))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const -0x1.0000000200000p+31)) (i64.const -2147483649))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const 0x1.fffffffe00000p+31)) (i64.const 4294967295))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const 0x1.0000000000000p+32)) (i64.const 4294967296))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const 0x1.7d78400000000p+26)) (i64.const 100000000))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const 0x1.1c37937e08000p+53)) (i64.const 10000000000000000))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const 0x1.93e5939a08ceap+99)) (i64.const 9223372036854775807))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const 0x1.fffffffffffffp+62)) (i64.const 9223372036854774784))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const -0x1.0000000000000p+63)) (i64.const -9223372036854775808))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const 0x1.0000000000000p+63)) (i64.const 9223372036854775807))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const -0x1.0000000000001p+63)) (i64.const -9223372036854775808))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const 0x1.fffffffffffffp+63)) (i64.const 9223372036854775807))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const 0x1.0000000000000p+64)) (i64.const 9223372036854775807))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const inf)) (i64.const 9223372036854775807))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const -inf)) (i64.const -9223372036854775808))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const nan:0x8000000000000)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const nan:0x4000000000000)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const -nan:0x8000000000000)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_s" (f64.const -nan:0x4000000000000)) (i64.const 0))
+
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const 0x0.0p+0)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const -0x0.0p+0)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const 0x0.0000000000001p-1022)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const -0x0.0000000000001p-1022)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const 0x1.0000000000000p-1022)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const 0x1.0000000000000p+0)) (i64.const 1))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const 0x1.199999999999ap+0)) (i64.const 1))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const 0x1.8000000000000p+0)) (i64.const 1))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const -0x1.0000000000000p+0)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const -0x1.8000000000000p+0)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const -0x1.e666666666666p+0)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const -0x1.0000000000000p+1)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const -0x1.fffffffffffffp-1)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const 0x1.fffffffc00000p+30)) (i64.const 2147483647))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const -0x1.0000000000000p+31)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const 0x1.0000000000000p+31)) (i64.const 2147483648))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const -0x1.0000000200000p+31)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const 0x1.fffffffe00000p+31)) (i64.const 4294967295))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const 0x1.0000000000000p+32)) (i64.const 4294967296))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const 0x1.7d78400000000p+26)) (i64.const 100000000))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const 0x1.1c37937e08000p+53)) (i64.const 10000000000000000))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const 0x1.93e5939a08ceap+99)) (i64.const -1))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const 0x1.fffffffffffffp+62)) (i64.const 9223372036854774784))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const -0x1.0000000000000p+63)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const 0x1.0000000000000p+63)) (i64.const -9223372036854775808))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const -0x1.0000000000001p+63)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const 0x1.fffffffffffffp+63)) (i64.const -2048))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const 0x1.0000000000000p+64)) (i64.const -1))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const inf)) (i64.const -1))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const -inf)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const nan:0x8000000000000)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const nan:0x4000000000000)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const -nan:0x8000000000000)) (i64.const 0))
+(assert_return (invoke "i64.trunc_sat_f64_u" (f64.const -nan:0x4000000000000)) (i64.const 0))
+
+(assert_return (invoke "f32.convert_i32_s" (i32.const 0)) (f32.const 0x0.0p+0))
+(assert_return (invoke "f32.convert_i32_s" (i32.const 1)) (f32.const 0x1.0000000000000p+0))
+(assert_return (invoke "f32.convert_i32_s" (i32.const -1)) (f32.const -0x1.0000000000000p+0))
+(assert_return (invoke "f32.convert_i32_s" (i32.const 10000)) (f32.const 0x1.3880000000000p+13))
+(assert_return (invoke "f32.convert_i32_s" (i32.const -10000)) (f32.const -0x1.3880000000000p+13))
+(assert_return (invoke "f32.convert_i32_s" (i32.const 2147483647)) (f32.const 0x1.0000000000000p+31))
+(assert_return (invoke "f32.convert_i32_s" (i32.const -2147483648)) (f32.const -0x1.0000000000000p+31))
+(assert_return (invoke "f32.convert_i32_s" (i32.const 1234567890)) (f32.const 0x1.26580c0000000p+30))
+(assert_return (invoke "f32.convert_i32_s" (i32.const 16777217)) (f32.const 0x1.0000000000000p+24))
+(assert_return (invoke "f32.convert_i32_s" (i32.const -16777217)) (f32.const -0x1.0000000000000p+24))
+(assert_return (invoke "f32.convert_i32_s" (i32.const 16777219)) (f32.const 0x1.0000040000000p+24))
+(assert_return (invoke "f32.convert_i32_s" (i32.const -16777219)) (f32.const -0x1.0000040000000p+24))
+(assert_return (invoke "f32.convert_i32_s" (i32.const -1)) (f32.const -0x1.0000000000000p+0))
+
+(assert_return (invoke "f32.convert_i32_u" (i32.const 0)) (f32.const 0x0.0p+0))
+(assert_return (invoke "f32.convert_i32_u" (i32.const 1)) (f32.const 0x1.0000000000000p+0))
+(assert_return (invoke "f32.convert_i32_u" (i32.const -1)) (f32.const 0x1.0000000000000p+32))
+(assert_return (invoke "f32.convert_i32_u" (i32.const 10000)) (f32.const 0x1.3880000000000p+13))
+(assert_return (invoke "f32.convert_i32_u" (i32.const -10000)) (f32.const 0x1.ffffb20000000p+31))
+(assert_return (invoke "f32.convert_i32_u" (i32.const 2147483647)) (f32.const 0x1.0000000000000p+31))
+(assert_return (invoke "f32.convert_i32_u" (i32.const -2147483648)) (f32.const 0x1.0000000000000p+31))
+(assert_return (invoke "f32.convert_i32_u" (i32.const 1234567890)) (f32.const 0x1.26580c0000000p+30))
+(assert_return (invoke "f32.convert_i32_u" (i32.const 16777217)) (f32.const 0x1.0000000000000p+24))
+(assert_return (invoke "f32.convert_i32_u" (i32.const -16777217)) (f32.const 0x1.fe00000000000p+31))
+(assert_return (invoke "f32.convert_i32_u" (i32.const 16777219)) (f32.const 0x1.0000040000000p+24))
+(assert_return (invoke "f32.convert_i32_u" (i32.const -16777219)) (f32.const 0x1.fe00000000000p+31))
+(assert_return (invoke "f32.convert_i32_u" (i32.const -1)) (f32.const 0x1.0000000000000p+32))
+
+(assert_return (invoke "f64.convert_i32_s" (i32.const 0)) (f64.const 0x0.0p+0))
+(assert_return (invoke "f64.convert_i32_s" (i32.const 1)) (f64.const 0x1.0000000000000p+0))
+(assert_return (invoke "f64.convert_i32_s" (i32.const -1)) (f64.const -0x1.0000000000000p+0))
+(assert_return (invoke "f64.convert_i32_s" (i32.const 10000)) (f64.const 0x1.3880000000000p+13))
+(assert_return (invoke "f64.convert_i32_s" (i32.const -10000)) (f64.const -0x1.3880000000000p+13))
+(assert_return (invoke "f64.convert_i32_s" (i32.const 2147483647)) (f64.const 0x1.fffffffc00000p+30))
+(assert_return (invoke "f64.convert_i32_s" (i32.const -2147483648)) (f64.const -0x1.0000000000000p+31))
+(assert_return (invoke "f64.convert_i32_s" (i32.const 1234567890)) (f64.const 0x1.26580b4800000p+30))
+(assert_return (invoke "f64.convert_i32_s" (i32.const 16777217)) (f64.const 0x1.0000010000000p+24))
+(assert_return (invoke "f64.convert_i32_s" (i32.const -16777217)) (f64.const -0x1.0000010000000p+24))
+(assert_return (invoke "f64.convert_i32_s" (i32.const 16777219)) (f64.const 0x1.0000030000000p+24))
+(assert_return (invoke "f64.convert_i32_s" (i32.const -16777219)) (f64.const -0x1.0000030000000p+24))
+(assert_return (invoke "f64.convert_i32_s" (i32.const -1)) (f64.const -0x1.0000000000000p+0))
+
+(assert_return (invoke "f64.convert_i32_u" (i32.const 0)) (f64.const 0x0.0p+0))
+(assert_return (invoke "f64.convert_i32_u" (i32.const 1)) (f64.const 0x1.0000000000000p+0))
+(assert_return (invoke "f64.convert_i32_u" (i32.const -1)) (f64.const 0x1.fffffffe00000p+31))
+(assert_return (invoke "f64.convert_i32_u" (i32.const 10000)) (f64.const 0x1.3880000000000p+13))
+(assert_return (invoke "f64.convert_i32_u" (i32.const -10000)) (f64.const 0x1.ffffb1e000000p+31))
+(assert_return (invoke "f64.convert_i32_u" (i32.const 2147483647)) (f64.const 0x1.fffffffc00000p+30))
+(assert_return (invoke "f64.convert_i32_u" (i32.const -2147483648)) (f64.const 0x1.0000000000000p+31))
+(assert_return (invoke "f64.convert_i32_u" (i32.const 1234567890)) (f64.const 0x1.26580b4800000p+30))
+(assert_return (invoke "f64.convert_i32_u" (i32.const 16777217)) (f64.const 0x1.0000010000000p+24))
+(assert_return (invoke "f64.convert_i32_u" (i32.const -16777217)) (f64.const 0x1.fdfffffe00000p+31))
+(assert_return (invoke "f64.convert_i32_u" (i32.const 16777219)) (f64.const 0x1.0000030000000p+24))
+(assert_return (invoke "f64.convert_i32_u" (i32.const -16777219)) (f64.const 0x1.fdfffffa00000p+31))
+(assert_return (invoke "f64.convert_i32_u" (i32.const -1)) (f64.const 0x1.fffffffe00000p+31))
+
+(assert_return (invoke "f32.convert_i64_s" (i64.const 0)) (f32.const 0x0.0p+0))
+(assert_return (invoke "f32.convert_i64_s" (i64.const 1)) (f32.const 0x1.0000000000000p+0))
+(assert_return (invoke "f32.convert_i64_s" (i64.const -1)) (f32.const -0x1.0000000000000p+0))
+(assert_return (invoke "f32.convert_i64_s" (i64.const 10000)) (f32.const 0x1.3880000000000p+13))
+(assert_return (invoke "f32.convert_i64_s" (i64.const -10000)) (f32.const -0x1.3880000000000p+13))
+(assert_return (invoke "f32.convert_i64_s" (i64.const 9223372036854775807)) (f32.const 0x1.0000000000000p+63))
+(assert_return (invoke "f32.convert_i64_s" (i64.const -9223372036854775808)) (f32.const -0x1.0000000000000p+63))
+(assert_return (invoke "f32.convert_i64_s" (i64.const 314159265358979)) (f32.const 0x1.1db9e80000000p+48))
+(assert_return (invoke "f32.convert_i64_s" (i64.const 16777217)) (f32.const 0x1.0000000000000p+24))
+(assert_return (invoke "f32.convert_i64_s" (i64.const -16777217)) (f32.const -0x1.0000000000000p+24))
+(assert_return (invoke "f32.convert_i64_s" (i64.const 16777219)) (f32.const 0x1.0000040000000p+24))
+(assert_return (invoke "f32.convert_i64_s" (i64.const -16777219)) (f32.const -0x1.0000040000000p+24))
+(assert_return (invoke "f32.convert_i64_s" (i64.const 9223371212221054977)) (f32.const 0x1.fffffe0000000p+62))
+(assert_return (invoke "f32.convert_i64_s" (i64.const -9223371487098961919)) (f32.const -0x1.fffffe0000000p+62))
+(assert_return (invoke "f32.convert_i64_s" (i64.const 9007199791611905)) (f32.const 0x1.0000020000000p+53))
+(assert_return (invoke "f32.convert_i64_s" (i64.const 9007199254740993)) (f32.const 0x1.0000000000000p+53))
+(assert_return (invoke "f32.convert_i64_s" (i64.const -9007199254740993)) (f32.const -0x1.0000000000000p+53))
+(assert_return (invoke "f32.convert_i64_s" (i64.const 9007199254740995)) (f32.const 0x1.0000000000000p+53))
+(assert_return (invoke "f32.convert_i64_s" (i64.const -2147483649)) (f32.const -0x1.0000000000000p+31))
+(assert_return (invoke "f32.convert_i64_s" (i64.const -4294967296)) (f32.const -0x1.0000000000000p+32))
+(assert_return (invoke "f32.convert_i64_s" (i64.const -4294967297)) (f32.const -0x1.0000000000000p+32))
+(assert_return (invoke "f32.convert_i64_s" (i64.const -4294967295)) (f32.const -0x1.0000000000000p+32))
+(assert_return (invoke "f32.convert_i64_s" (i64.const 4294967295)) (f32.const 0x1.0000000000000p+32))
+(assert_return (invoke "f32.convert_i64_s" (i64.const 4294967296)) (f32.const 0x1.0000000000000p+32))
+(assert_return (invoke "f32.convert_i64_s" (i64.const 4294967297)) (f32.const 0x1.0000000000000p+32))
+(assert_return (invoke "f32.convert_i64_s" (i64.const -1)) (f32.const -0x1.0000000000000p+0))
+
+(assert_return (invoke "f32.convert_i64_u" (i64.const 0)) (f32.const 0x0.0p+0))
+(assert_return (invoke "f32.convert_i64_u" (i64.const 1)) (f32.const 0x1.0000000000000p+0))
+(assert_return (invoke "f32.convert_i64_u" (i64.const -1)) (f32.const 0x1.0000000000000p+64))
+(assert_return (invoke "f32.convert_i64_u" (i64.const 10000)) (f32.const 0x1.3880000000000p+13))
+(assert_return (invoke "f32.convert_i64_u" (i64.const -10000)) (f32.const 0x1.0000000000000p+64))
+(assert_return (invoke "f32.convert_i64_u" (i64.const 9223372036854775807)) (f32.const 0x1.0000000000000p+63))
+(assert_return (invoke "f32.convert_i64_u" (i64.const -9223372036854775808)) (f32.const 0x1.0000000000000p+63))
+(assert_return (invoke "f32.convert_i64_u" (i64.const 314159265358979)) (f32.const 0x1.1db9e80000000p+48))
+(assert_return (invoke "f32.convert_i64_u" (i64.const 16777217)) (f32.const 0x1.0000000000000p+24))
+(assert_return (invoke "f32.convert_i64_u" (i64.const -16777217)) (f32.const 0x1.0000000000000p+64))
+(assert_return (invoke "f32.convert_i64_u" (i64.const 16777219)) (f32.const 0x1.0000040000000p+24))
+(assert_return (invoke "f32.convert_i64_u" (i64.const -16777219)) (f32.const 0x1.0000000000000p+64))
+(assert_return (invoke "f32.convert_i64_u" (i64.const 9223371212221054977)) (f32.const 0x1.fffffe0000000p+62))
+(assert_return (invoke "f32.convert_i64_u" (i64.const -9223371487098961919)) (f32.const 0x1.0000020000000p+63))
+(assert_return (invoke "f32.convert_i64_u" (i64.const 9007199791611905)) (f32.const 0x1.0000020000000p+53))
+(assert_return (invoke "f32.convert_i64_u" (i64.const 9007199254740993)) (f32.const 0x1.0000000000000p+53))
+(assert_return (invoke "f32.convert_i64_u" (i64.const -9007199254740993)) (f32.const 0x1.ffc0000000000p+63))
+(assert_return (invoke "f32.convert_i64_u" (i64.const 9007199254740995)) (f32.const 0x1.0000000000000p+53))
+(assert_return (invoke "f32.convert_i64_u" (i64.const -2147483649)) (f32.const 0x1.0000000000000p+64))
+(assert_return (invoke "f32.convert_i64_u" (i64.const -4294967296)) (f32.const 0x1.0000000000000p+64))
+(assert_return (invoke "f32.convert_i64_u" (i64.const -4294967297)) (f32.const 0x1.0000000000000p+64))
+(assert_return (invoke "f32.convert_i64_u" (i64.const -4294967295)) (f32.const 0x1.0000000000000p+64))
+(assert_return (invoke "f32.convert_i64_u" (i64.const 4294967295)) (f32.const 0x1.0000000000000p+32))
+(assert_return (invoke "f32.convert_i64_u" (i64.const 4294967296)) (f32.const 0x1.0000000000000p+32))
+(assert_return (invoke "f32.convert_i64_u" (i64.const 4294967297)) (f32.const 0x1.0000000000000p+32))
+(assert_return (invoke "f32.convert_i64_u" (i64.const -1)) (f32.const 0x1.0000000000000p+64))
+
+(assert_return (invoke "f64.convert_i64_s" (i64.const 0)) (f64.const 0x0.0p+0))
+(assert_return (invoke "f64.convert_i64_s" (i64.const 1)) (f64.const 0x1.0000000000000p+0))
+(assert_return (invoke "f64.convert_i64_s" (i64.const -1)) (f64.const -0x1.0000000000000p+0))
+(assert_return (invoke "f64.convert_i64_s" (i64.const 10000)) (f64.const 0x1.3880000000000p+13))
+(assert_return (invoke "f64.convert_i64_s" (i64.const -10000)) (f64.const -0x1.3880000000000p+13))
+(assert_return (invoke "f64.convert_i64_s" (i64.const 9223372036854775807)) (f64.const 0x1.0000000000000p+63))
+(assert_return (invoke "f64.convert_i64_s" (i64.const -9223372036854775808)) (f64.const -0x1.0000000000000p+63))
+(assert_return (invoke "f64.convert_i64_s" (i64.const 314159265358979)) (f64.const 0x1.1db9e76a24830p+48))
+(assert_return (invoke "f64.convert_i64_s" (i64.const 16777217)) (f64.const 0x1.0000010000000p+24))
+(assert_return (invoke "f64.convert_i64_s" (i64.const -16777217)) (f64.const -0x1.0000010000000p+24))
+(assert_return (invoke "f64.convert_i64_s" (i64.const 16777219)) (f64.const 0x1.0000030000000p+24))
+(assert_return (invoke "f64.convert_i64_s" (i64.const -16777219)) (f64.const -0x1.0000030000000p+24))
+(assert_return (invoke "f64.convert_i64_s" (i64.const 9223371212221054977)) (f64.const 0x1.fffffd0000000p+62))
+(assert_return (invoke "f64.convert_i64_s" (i64.const -9223371487098961919)) (f64.const -0x1.fffffe0000000p+62))
+(assert_return (invoke "f64.convert_i64_s" (i64.const 9007199791611905)) (f64.const 0x1.0000010000000p+53))
+(assert_return (invoke "f64.convert_i64_s" (i64.const 9007199254740993)) (f64.const 0x1.0000000000000p+53))
+(assert_return (invoke "f64.convert_i64_s" (i64.const -9007199254740993)) (f64.const -0x1.0000000000000p+53))
+(assert_return (invoke "f64.convert_i64_s" (i64.const 9007199254740995)) (f64.const 0x1.0000000000002p+53))
+(assert_return (invoke "f64.convert_i64_s" (i64.const -2147483649)) (f64.const -0x1.0000000200000p+31))
+(assert_return (invoke "f64.convert_i64_s" (i64.const -4294967296)) (f64.const -0x1.0000000000000p+32))
+(assert_return (invoke "f64.convert_i64_s" (i64.const -4294967297)) (f64.const -0x1.0000000100000p+32))
+(assert_return (invoke "f64.convert_i64_s" (i64.const -4294967295)) (f64.const -0x1.fffffffe00000p+31))
+(assert_return (invoke "f64.convert_i64_s" (i64.const 4294967295)) (f64.const 0x1.fffffffe00000p+31))
+(assert_return (invoke "f64.convert_i64_s" (i64.const 4294967296)) (f64.const 0x1.0000000000000p+32))
+(assert_return (invoke "f64.convert_i64_s" (i64.const 4294967297)) (f64.const 0x1.0000000100000p+32))
+(assert_return (invoke "f64.convert_i64_s" (i64.const -1)) (f64.const -0x1.0000000000000p+0))
+
+(assert_return (invoke "f64.convert_i64_u" (i64.const 0)) (f64.const 0x0.0p+0))
+(assert_return (invoke "f64.convert_i64_u" (i64.const 1)) (f64.const 0x1.0000000000000p+0))
+(assert_return (invoke "f64.convert_i64_u" (i64.const -1)) (f64.const 0x1.0000000000000p+64))
+(assert_return (invoke "f64.convert_i64_u" (i64.const 10000)) (f64.const 0x1.3880000000000p+13))
+(assert_return (invoke "f64.convert_i64_u" (i64.const -10000)) (f64.const 0x1.ffffffffffffbp+63))
+(assert_return (invoke "f64.convert_i64_u" (i64.const 9223372036854775807)) (f64.const 0x1.0000000000000p+63))
+(assert_return (invoke "f64.convert_i64_u" (i64.const -9223372036854775808)) (f64.const 0x1.0000000000000p+63))
+(assert_return (invoke "f64.convert_i64_u" (i64.const 314159265358979)) (f64.const 0x1.1db9e76a24830p+48))
+(assert_return (invoke "f64.convert_i64_u" (i64.const 16777217)) (f64.const 0x1.0000010000000p+24))
+(assert_return (invoke "f64.convert_i64_u" (i64.const -16777217)) (f64.const 0x1.fffffffffe000p+63))
+(assert_return (invoke "f64.convert_i64_u" (i64.const 16777219)) (f64.const 0x1.0000030000000p+24))
+(assert_return (invoke "f64.convert_i64_u" (i64.const -16777219)) (f64.const 0x1.fffffffffe000p+63))
+(assert_return (invoke "f64.convert_i64_u" (i64.const 9223371212221054977)) (f64.const 0x1.fffffd0000000p+62))
+(assert_return (invoke "f64.convert_i64_u" (i64.const -9223371487098961919)) (f64.const 0x1.0000010000000p+63))
+(assert_return (invoke "f64.convert_i64_u" (i64.const 9007199791611905)) (f64.const 0x1.0000010000000p+53))
+(assert_return (invoke "f64.convert_i64_u" (i64.const 9007199254740993)) (f64.const 0x1.0000000000000p+53))
+(assert_return (invoke "f64.convert_i64_u" (i64.const -9007199254740993)) (f64.const 0x1.ffc0000000000p+63))
+(assert_return (invoke "f64.convert_i64_u" (i64.const 9007199254740995)) (f64.const 0x1.0000000000002p+53))
+(assert_return (invoke "f64.convert_i64_u" (i64.const -2147483649)) (f64.const 0x1.ffffffff00000p+63))
+(assert_return (invoke "f64.convert_i64_u" (i64.const -4294967296)) (f64.const 0x1.fffffffe00000p+63))
+(assert_return (invoke "f64.convert_i64_u" (i64.const -4294967297)) (f64.const 0x1.fffffffe00000p+63))
+(assert_return (invoke "f64.convert_i64_u" (i64.const -4294967295)) (f64.const 0x1.fffffffe00000p+63))
+(assert_return (invoke "f64.convert_i64_u" (i64.const 4294967295)) (f64.const 0x1.fffffffe00000p+31))
+(assert_return (invoke "f64.convert_i64_u" (i64.const 4294967296)) (f64.const 0x1.0000000000000p+32))
+(assert_return (invoke "f64.convert_i64_u" (i64.const 4294967297)) (f64.const 0x1.0000000100000p+32))
+(assert_return (invoke "f64.convert_i64_u" (i64.const -1)) (f64.const 0x1.0000000000000p+64))
+
+(assert_return (invoke "f64.promote_f32" (f32.const 0x0.0p+0)) (f64.const 0x0.0p+0))
+(assert_return (invoke "f64.promote_f32" (f32.const -0x0.0p+0)) (f64.const -0x0.0p+0))
+(assert_return (invoke "f64.promote_f32" (f32.const 0x1.0000000000000p-149)) (f64.const 0x1.0000000000000p-149))
+(assert_return (invoke "f64.promote_f32" (f32.const -0x1.0000000000000p-149)) (f64.const -0x1.0000000000000p-149))
+(assert_return (invoke "f64.promote_f32" (f32.const 0x1.0000000000000p-126)) (f64.const 0x1.0000000000000p-126))
+(assert_return (invoke "f64.promote_f32" (f32.const 0x1.0000000000000p+0)) (f64.const 0x1.0000000000000p+0))
+(assert_return (invoke "f64.promote_f32" (f32.const 0x1.19999a0000000p+0)) (f64.const 0x1.19999a0000000p+0))
+(assert_return (invoke "f64.promote_f32" (f32.const 0x1.8000000000000p+0)) (f64.const 0x1.8000000000000p+0))
+(assert_return (invoke "f64.promote_f32" (f32.const -0x1.0000000000000p+0)) (f64.const -0x1.0000000000000p+0))
+(assert_return (invoke "f64.promote_f32" (f32.const -0x1.19999a0000000p+0)) (f64.const -0x1.19999a0000000p+0))
+(assert_return (invoke "f64.promote_f32" (f32.const -0x1.8000000000000p+0)) (f64.const -0x1.8000000000000p+0))
+(assert_return (invoke "f64.promote_f32" (f32.const -0x1.e666660000000p+0)) (f64.const -0x1.e666660000000p+0))
+(assert_return (invoke "f64.promote_f32" (f32.const -0x1.0000000000000p+1)) (f64.const -0x1.0000000000000p+1))
+(assert_return (invoke "f64.promote_f32" (f32.const -0x1.cccccc0000000p-1)) (f64.const -0x1.cccccc0000000p-1))
+(assert_return (invoke "f64.promote_f32" (f32.const 0x1.fffffe0000000p+30)) (f64.const 0x1.fffffe0000000p+30))
+(assert_return (invoke "f64.promote_f32" (f32.const -0x1.0000000000000p+31)) (f64.const -0x1.0000000000000p+31))
+(assert_return (invoke "f64.promote_f32" (f32.const 0x1.0000000000000p+31)) (f64.const 0x1.0000000000000p+31))
+(assert_return (invoke "f64.promote_f32" (f32.const -0x1.0000020000000p+31)) (f64.const -0x1.0000020000000p+31))
+(assert_return (invoke "f64.promote_f32" (f32.const 0x1.fffffe0000000p+31)) (f64.const 0x1.fffffe0000000p+31))
+(assert_return (invoke "f64.promote_f32" (f32.const 0x1.0000000000000p+32)) (f64.const 0x1.0000000000000p+32))
+(assert_return (invoke "f64.promote_f32" (f32.const 0x1.fffffe0000000p+62)) (f64.const 0x1.fffffe0000000p+62))
+(assert_return (invoke "f64.promote_f32" (f32.const -0x1.0000000000000p+63)) (f64.const -0x1.0000000000000p+63))
+(assert_return (invoke "f64.promote_f32" (f32.const 0x1.0000000000000p+63)) (f64.const 0x1.0000000000000p+63))
+(assert_return (invoke "f64.promote_f32" (f32.const -0x1.0000020000000p+63)) (f64.const -0x1.0000020000000p+63))
+(assert_return (invoke "f64.promote_f32" (f32.const 0x1.fffffe0000000p+63)) (f64.const 0x1.fffffe0000000p+63))
+(assert_return (invoke "f64.promote_f32" (f32.const 0x1.0000000000000p+64)) (f64.const 0x1.0000000000000p+64))
+(assert_return (invoke "f64.promote_f32" (f32.const inf)) (f64.const inf))
+(assert_return (invoke "f64.promote_f32" (f32.const -inf)) (f64.const -inf))
+(assert_return (invoke "f64.promote_f32" (f32.const nan:0x400000)) (f64.const nan:arithmetic))
+(assert_return (invoke "f64.promote_f32" (f32.const nan:0x200000)) (f64.const nan:arithmetic))
+(assert_return (invoke "f64.promote_f32" (f32.const -nan:0x400000)) (f64.const nan:arithmetic))
+(assert_return (invoke "f64.promote_f32" (f32.const -nan:0x200000)) (f64.const nan:arithmetic))
+
+(assert_return (invoke "f32.demote_f64" (f64.const 0x0.0p+0)) (f32.const 0x0.0p+0))
+(assert_return (invoke "f32.demote_f64" (f64.const -0x0.0p+0)) (f32.const -0x0.0p+0))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x0.0000000000001p-1022)) (f32.const 0x0.0p+0))
+(assert_return (invoke "f32.demote_f64" (f64.const -0x0.0000000000001p-1022)) (f32.const -0x0.0p+0))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.0000000000000p-1022)) (f32.const 0x0.0p+0))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.0000000000000p+0)) (f32.const 0x1.0000000000000p+0))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.199999999999ap+0)) (f32.const 0x1.19999a0000000p+0))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.8000000000000p+0)) (f32.const 0x1.8000000000000p+0))
+(assert_return (invoke "f32.demote_f64" (f64.const -0x1.0000000000000p+0)) (f32.const -0x1.0000000000000p+0))
+(assert_return (invoke "f32.demote_f64" (f64.const -0x1.8000000000000p+0)) (f32.const -0x1.8000000000000p+0))
+(assert_return (invoke "f32.demote_f64" (f64.const -0x1.e666666666666p+0)) (f32.const -0x1.e666660000000p+0))
+(assert_return (invoke "f32.demote_f64" (f64.const -0x1.0000000000000p+1)) (f32.const -0x1.0000000000000p+1))
+(assert_return (invoke "f32.demote_f64" (f64.const -0x1.fffffffffffffp-1)) (f32.const -0x1.0000000000000p+0))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.fffffffc00000p+30)) (f32.const 0x1.0000000000000p+31))
+(assert_return (invoke "f32.demote_f64" (f64.const -0x1.0000000000000p+31)) (f32.const -0x1.0000000000000p+31))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.0000000000000p+31)) (f32.const 0x1.0000000000000p+31))
+(assert_return (invoke "f32.demote_f64" (f64.const -0x1.0000000200000p+31)) (f32.const -0x1.0000000000000p+31))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.fffffffe00000p+31)) (f32.const 0x1.0000000000000p+32))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.0000000000000p+32)) (f32.const 0x1.0000000000000p+32))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.7d78400000000p+26)) (f32.const 0x1.7d78400000000p+26))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.1c37937e08000p+53)) (f32.const 0x1.1c37940000000p+53))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.93e5939a08ceap+99)) (f32.const 0x1.93e5940000000p+99))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.fffffffffffffp+62)) (f32.const 0x1.0000000000000p+63))
+(assert_return (invoke "f32.demote_f64" (f64.const -0x1.0000000000000p+63)) (f32.const -0x1.0000000000000p+63))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.0000000000000p+63)) (f32.const 0x1.0000000000000p+63))
+(assert_return (invoke "f32.demote_f64" (f64.const -0x1.0000000000001p+63)) (f32.const -0x1.0000000000000p+63))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.fffffffffffffp+63)) (f32.const 0x1.0000000000000p+64))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.0000000000000p+64)) (f32.const 0x1.0000000000000p+64))
+(assert_return (invoke "f32.demote_f64" (f64.const inf)) (f32.const inf))
+(assert_return (invoke "f32.demote_f64" (f64.const -inf)) (f32.const -inf))
+(assert_return (invoke "f32.demote_f64" (f64.const nan:0x8000000000000)) (f32.const nan:arithmetic))
+(assert_return (invoke "f32.demote_f64" (f64.const nan:0x4000000000000)) (f32.const nan:arithmetic))
+(assert_return (invoke "f32.demote_f64" (f64.const -nan:0x8000000000000)) (f32.const nan:arithmetic))
+(assert_return (invoke "f32.demote_f64" (f64.const -nan:0x4000000000000)) (f32.const nan:arithmetic))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.fffffe0000000p-127)) (f32.const 0x1.0000000000000p-126))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.fffffe0000000p+127)) (f32.const 0x1.fffffe0000000p+127))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.fffffefffffffp+127)) (f32.const 0x1.fffffe0000000p+127))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.ffffff0000000p+127)) (f32.const inf))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.0000000000000p-119)) (f32.const 0x1.0000000000000p-119))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.8f867e0000000p+125)) (f32.const 0x1.8f867e0000000p+125))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.0000000000001p+0)) (f32.const 0x1.0000000000000p+0))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.fffffffffffffp-1)) (f32.const 0x1.0000000000000p+0))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.000000fffffffp+0)) (f32.const 0x1.0000000000000p+0))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.0000010000000p+0)) (f32.const 0x1.0000000000000p+0))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.0000010000001p+0)) (f32.const 0x1.0000020000000p+0))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.000002fffffffp+0)) (f32.const 0x1.0000020000000p+0))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.0000030000000p+0)) (f32.const 0x1.0000040000000p+0))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.0000000000000p-150)) (f32.const 0x0.0p+0))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.0000000000001p-150)) (f32.const 0x1.0000000000000p-149))
+(assert_return (invoke "f32.demote_f64" (f64.const -0x1.0000000000000p-150)) (f32.const -0x0.0p+0))
+(assert_return (invoke "f32.demote_f64" (f64.const 0x1.dee7a4ad4b81fp-167)) (f32.const 0x0.0p+0))
+(assert_return (invoke "f32.demote_f64" (f64.const -0x1.dee7a4ad4b81fp-167)) (f32.const -0x0.0p+0))
+
+(assert_return (invoke "f32.reinterpret_i32" (i32.const 0)) (f32.const 0x0.0p+0))
+(assert_return (invoke "f32.reinterpret_i32" (i32.const -2147483648)) (f32.const -0x0.0p+0))
+(assert_return (invoke "f32.reinterpret_i32" (i32.const 1)) (f32.const 0x1.0000000000000p-149))
+(assert_return (invoke "f32.reinterpret_i32" (i32.const -1)) (f32.const -nan:0x7fffff))
+(assert_return (invoke "f32.reinterpret_i32" (i32.const 123456789)) (f32.const 0x1.b79a2a0000000p-113))
+(assert_return (invoke "f32.reinterpret_i32" (i32.const -2147483647)) (f32.const -0x1.0000000000000p-149))
+(assert_return (invoke "f32.reinterpret_i32" (i32.const 2139095040)) (f32.const inf))
+(assert_return (invoke "f32.reinterpret_i32" (i32.const -8388608)) (f32.const -inf))
+(assert_return (invoke "f32.reinterpret_i32" (i32.const 2143289344)) (f32.const nan:0x400000))
+(assert_return (invoke "f32.reinterpret_i32" (i32.const -4194304)) (f32.const -nan:0x400000))
+(assert_return (invoke "f32.reinterpret_i32" (i32.const 2141192192)) (f32.const nan:0x200000))
+(assert_return (invoke "f32.reinterpret_i32" (i32.const -6291456)) (f32.const -nan:0x200000))
+(assert_return (invoke "f32.reinterpret_i32" (i32.const 2139095041)) (f32.const nan:0x1))
+(assert_return (invoke "f32.reinterpret_i32" (i32.const 1065353216)) (f32.const 0x1.0000000000000p+0))
+
+(assert_return (invoke "f64.reinterpret_i64" (i64.const 0)) (f64.const 0x0.0p+0))
+(assert_return (invoke "f64.reinterpret_i64" (i64.const -9223372036854775808)) (f64.const -0x0.0p+0))
+(assert_return (invoke "f64.reinterpret_i64" (i64.const 1)) (f64.const 0x0.0000000000001p-1022))
+(assert_return (invoke "f64.reinterpret_i64" (i64.const -1)) (f64.const -nan:0xfffffffffffff))
+(assert_return (invoke "f64.reinterpret_i64" (i64.const 1234567890)) (f64.const 0x0.00000499602d2p-1022))
+(assert_return (invoke "f64.reinterpret_i64" (i64.const -9223372036854775807)) (f64.const -0x0.0000000000001p-1022))
+(assert_return (invoke "f64.reinterpret_i64" (i64.const 9218868437227405312)) (f64.const inf))
+(assert_return (invoke "f64.reinterpret_i64" (i64.const -4503599627370496)) (f64.const -inf))
+(assert_return (invoke "f64.reinterpret_i64" (i64.const 9221120237041090560)) (f64.const nan:0x8000000000000))
+(assert_return (invoke "f64.reinterpret_i64" (i64.const -2251799813685248)) (f64.const -nan:0x8000000000000))
+(assert_return (invoke "f64.reinterpret_i64" (i64.const 9219994337134247936)) (f64.const nan:0x4000000000000))
+(assert_return (invoke "f64.reinterpret_i64" (i64.const -3377699720527872)) (f64.const -nan:0x4000000000000))
+(assert_return (invoke "f64.reinterpret_i64" (i64.const 9218868437227405313)) (f64.const nan:0x1))
+(assert_return (invoke "f64.reinterpret_i64" (i64.const 4607182418800017408)) (f64.const 0x1.0000000000000p+0))
+
+(assert_return (invoke "i32.reinterpret_f32" (f32.const 0x0.0p+0)) (i32.const 0))
+(assert_return (invoke "i32.reinterpret_f32" (f32.const -0x0.0p+0)) (i32.const -2147483648))
+(assert_return (invoke "i32.reinterpret_f32" (f32.const 0x1.0000000000000p-149)) (i32.const 1))
+(assert_return (invoke "i32.reinterpret_f32" (f32.const -nan:0x7fffff)) (i32.const -1))
+(assert_return (invoke "i32.reinterpret_f32" (f32.const 0x1.b79a2a0000000p-113)) (i32.const 123456789))
+(assert_return (invoke "i32.reinterpret_f32" (f32.const -0x1.0000000000000p-149)) (i32.const -2147483647))
+(assert_return (invoke "i32.reinterpret_f32" (f32.const inf)) (i32.const 2139095040))
+(assert_return (invoke "i32.reinterpret_f32" (f32.const -inf)) (i32.const -8388608))
+(assert_return (invoke "i32.reinterpret_f32" (f32.const nan:0x400000)) (i32.const 2143289344))
+(assert_return (invoke "i32.reinterpret_f32" (f32.const -nan:0x400000)) (i32.const -4194304))
+(assert_return (invoke "i32.reinterpret_f32" (f32.const nan:0x200000)) (i32.const 2141192192))
+(assert_return (invoke "i32.reinterpret_f32" (f32.const -nan:0x200000)) (i32.const -6291456))
+(assert_return (invoke "i32.reinterpret_f32" (f32.const nan:0x1)) (i32.const 2139095041))
+(assert_return (invoke "i32.reinterpret_f32" (f32.const 0x1.0000000000000p+0)) (i32.const 1065353216))
+
+(assert_return (invoke "i64.reinterpret_f64" (f64.const 0x0.0p+0)) (i64.const 0))
+(assert_return (invoke "i64.reinterpret_f64" (f64.const -0x0.0p+0)) (i64.const -9223372036854775808))
+(assert_return (invoke "i64.reinterpret_f64" (f64.const 0x0.0000000000001p-1022)) (i64.const 1))
+(assert_return (invoke "i64.reinterpret_f64" (f64.const -nan:0xfffffffffffff)) (i64.const -1))
+(assert_return (invoke "i64.reinterpret_f64" (f64.const 0x0.00000499602d2p-1022)) (i64.const 1234567890))
+(assert_return (invoke "i64.reinterpret_f64" (f64.const -0x0.0000000000001p-1022)) (i64.const -9223372036854775807))
+(assert_return (invoke "i64.reinterpret_f64" (f64.const inf)) (i64.const 9218868437227405312))
+(assert_return (invoke "i64.reinterpret_f64" (f64.const -inf)) (i64.const -4503599627370496))
+(assert_return (invoke "i64.reinterpret_f64" (f64.const nan:0x8000000000000)) (i64.const 9221120237041090560))
+(assert_return (invoke "i64.reinterpret_f64" (f64.const -nan:0x8000000000000)) (i64.const -2251799813685248))
+(assert_return (invoke "i64.reinterpret_f64" (f64.const nan:0x4000000000000)) (i64.const 9219994337134247936))
+(assert_return (invoke "i64.reinterpret_f64" (f64.const -nan:0x4000000000000)) (i64.const -3377699720527872))
+(assert_return (invoke "i64.reinterpret_f64" (f64.const nan:0x1)) (i64.const 9218868437227405313))
+(assert_return (invoke "i64.reinterpret_f64" (f64.const 0x1.0000000000000p+0)) (i64.const 4607182418800017408))
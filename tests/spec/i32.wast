@@ -0,0 +1,478 @@
+;; Assertions follow the official spec test suite's i32.wast, limited to the
+;; assert_return and assert_trap directives tests/spec.rs runs
+
+(module
+  (func (export "add") (param $x i32) (param $y i32) (result i32) (i32.add (local.get $x) (local.get $y)))
+  (func (export "sub") (param $x i32) (param $y i32) (result i32) (i32.sub (local.get $x) (local.get $y)))
+  (func (export "mul") (param $x i32) (param $y i32) (result i32) (i32.mul (local.get $x) (local.get $y)))
+  (func (export "and") (param $x i32) (param $y i32) (result i32) (i32.and (local.get $x) (local.get $y)))
+  (func (export "or") (param $x i32) (param $y i32) (result i32) (i32.or (local.get $x) (local.get $y)))
+  (func (export "xor") (param $x i32) (param $y i32) (result i32) (i32.xor (local.get $x) (local.get $y)))
+  (func (export "shl") (param $x i32) (param $y i32) (result i32) (i32.shl (local.get $x) (local.get $y)))
+  (func (export "shr_s") (param $x i32) (param $y i32) (result i32) (i32.shr_s (local.get $x) (local.get $y)))
+  (func (export "shr_u") (param $x i32) (param $y i32) (result i32) (i32.shr_u (local.get $x) (local.get $y)))
+  (func (export "rotl") (param $x i32) (param $y i32) (result i32) (i32.rotl (local.get $x) (local.get $y)))
+  (func (export "rotr") (param $x i32) (param $y i32) (result i32) (i32.rotr (local.get $x) (local.get $y)))
+  (func (export "div_s") (param $x i32) (param $y i32) (result i32) (i32.div_s (local.get $x) (local.get $y)))
+  (func (export "div_u") (param $x i32) (param $y i32) (result i32) (i32.div_u (local.get $x) (local.get $y)))
+  (func (export "rem_s") (param $x i32) (param $y i32) (result i32) (i32.rem_s (local.get $x) (local.get $y)))
+  (func (export "rem_u") (param $x i32) (param $y i32) (result i32) (i32.rem_u (local.get $x) (local.get $y)))
+  (func (export "clz") (param $x i32) (result i32) (i32.clz (local.get $x)))
+  (func (export "ctz") (param $x i32) (result i32) (i32.ctz (local.get $x)))
+  (func (export "popcnt") (param $x i32) (result i32) (i32.popcnt (local.get $x)))
+  (func (export "extend8_s") (param $x i32) (result i32) (i32.extend8_s (local.get $x)))
+  (func (export "extend16_s") (param $x i32) (result i32) (i32.extend16_s (local.get $x)))
+  (func (export "eqz") (param $x i32) (result i32) (i32.eqz (local.get $x)))
+  (func (export "eq") (param $x i32) (param $y i32) (result i32) (i32.eq (local.get $x) (local.get $y)))
+  (func (export "ne") (param $x i32) (param $y i32) (result i32) (i32.ne (local.get $x) (local.get $y)))
+  (func (export "lt_s") (param $x i32) (param $y i32) (result i32) (i32.lt_s (local.get $x) (local.get $y)))
+  (func (export "lt_u") (param $x i32) (param $y i32) (result i32) (i32.lt_u (local.get $x) (local.get $y)))
+  (func (export "le_s") (param $x i32) (param $y i32) (result i32) (i32.le_s (local.get $x) (local.get $y)))
+  (func (export "le_u") (param $x i32) (param $y i32) (result i32) (i32.le_u (local.get $x) (local.get $y)))
+  (func (export "gt_s") (param $x i32) (param $y i32) (result i32) (i32.gt_s (local.get $x) (local.get $y)))
+  (func (export "gt_u") (param $x i32) (param $y i32) (result i32) (i32.gt_u (local.get $x) (local.get $y)))
+  (func (export "ge_s") (param $x i32) (param $y i32) (result i32) (i32.ge_s (local.get $x) (local.get $y)))
+  (func (export "ge_u") (param $x i32) (param $y i32) (result i32) (i32.ge_u (local.get $x) (local.get $y)))
+)
+
+(assert_return (invoke "add" (i32.const 1) (i32.const 1)) (i32.const 2))
+(assert_return (invoke "add" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "add" (i32.const -1) (i32.const -1)) (i32.const -2))
+(assert_return (invoke "add" (i32.const -1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "add" (i32.const 2147483647) (i32.const 1)) (i32.const -2147483648))
+(assert_return (invoke "add" (i32.const -2147483648) (i32.const -1)) (i32.const 2147483647))
+(assert_return (invoke "add" (i32.const -2147483648) (i32.const -2147483648)) (i32.const 0))
+(assert_return (invoke "add" (i32.const 1073741823) (i32.const 1)) (i32.const 1073741824))
+
+(assert_return (invoke "sub" (i32.const 1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "sub" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "sub" (i32.const -1) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "sub" (i32.const -1) (i32.const 1)) (i32.const -2))
+(assert_return (invoke "sub" (i32.const 2147483647) (i32.const 1)) (i32.const 2147483646))
+(assert_return (invoke "sub" (i32.const -2147483648) (i32.const -1)) (i32.const -2147483647))
+(assert_return (invoke "sub" (i32.const -2147483648) (i32.const -2147483648)) (i32.const 0))
+(assert_return (invoke "sub" (i32.const 1073741823) (i32.const 1)) (i32.const 1073741822))
+(assert_return (invoke "sub" (i32.const 2147483647) (i32.const -1)) (i32.const -2147483648))
+(assert_return (invoke "sub" (i32.const -2147483648) (i32.const 1)) (i32.const 2147483647))
+
+(assert_return (invoke "mul" (i32.const 1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "mul" (i32.const 1) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "mul" (i32.const -1) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "mul" (i32.const 268435456) (i32.const 4096)) (i32.const 0))
+(assert_return (invoke "mul" (i32.const -2147483648) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "mul" (i32.const -2147483648) (i32.const -1)) (i32.const -2147483648))
+(assert_return (invoke "mul" (i32.const 2147483647) (i32.const -1)) (i32.const -2147483647))
+(assert_return (invoke "mul" (i32.const 19088743) (i32.const 1985229328)) (i32.const 898528368))
+(assert_return (invoke "mul" (i32.const 2147483647) (i32.const 2147483647)) (i32.const 1))
+
+(assert_return (invoke "and" (i32.const 1) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "and" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "and" (i32.const 1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "and" (i32.const 0) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "and" (i32.const 2147483647) (i32.const -2147483648)) (i32.const 0))
+(assert_return (invoke "and" (i32.const -2147483648) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "and" (i32.const -1) (i32.const -2147483648)) (i32.const -2147483648))
+(assert_return (invoke "and" (i32.const -1) (i32.const 2147483647)) (i32.const 2147483647))
+(assert_return (invoke "and" (i32.const -252641281) (i32.const -3856)) (i32.const -252645136))
+(assert_return (invoke "and" (i32.const -1) (i32.const -1)) (i32.const -1))
+
+(assert_return (invoke "or" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "or" (i32.const 0) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "or" (i32.const 1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "or" (i32.const 0) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "or" (i32.const 2147483647) (i32.const -2147483648)) (i32.const -1))
+(assert_return (invoke "or" (i32.const -2147483648) (i32.const 0)) (i32.const -2147483648))
+(assert_return (invoke "or" (i32.const -1) (i32.const -2147483648)) (i32.const -1))
+(assert_return (invoke "or" (i32.const -1) (i32.const 2147483647)) (i32.const -1))
+(assert_return (invoke "or" (i32.const -252641281) (i32.const -3856)) (i32.const -1))
+(assert_return (invoke "or" (i32.const -1) (i32.const -1)) (i32.const -1))
+
+(assert_return (invoke "xor" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "xor" (i32.const 0) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "xor" (i32.const 1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "xor" (i32.const 0) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "xor" (i32.const 2147483647) (i32.const -2147483648)) (i32.const -1))
+(assert_return (invoke "xor" (i32.const -2147483648) (i32.const 0)) (i32.const -2147483648))
+(assert_return (invoke "xor" (i32.const -1) (i32.const -2147483648)) (i32.const 2147483647))
+(assert_return (invoke "xor" (i32.const -1) (i32.const 2147483647)) (i32.const -2147483648))
+(assert_return (invoke "xor" (i32.const -252641281) (i32.const -3856)) (i32.const 252645135))
+(assert_return (invoke "xor" (i32.const -1) (i32.const -1)) (i32.const 0))
+
+(assert_return (invoke "shl" (i32.const 1) (i32.const 1)) (i32.const 2))
+(assert_return (invoke "shl" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "shl" (i32.const -1) (i32.const 1)) (i32.const -2))
+(assert_return (invoke "shl" (i32.const 2147483647) (i32.const 1)) (i32.const -2))
+(assert_return (invoke "shl" (i32.const -1) (i32.const 1)) (i32.const -2))
+(assert_return (invoke "shl" (i32.const -2147483648) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "shl" (i32.const 1073741824) (i32.const 1)) (i32.const -2147483648))
+(assert_return (invoke "shl" (i32.const 1) (i32.const 31)) (i32.const -2147483648))
+(assert_return (invoke "shl" (i32.const 1) (i32.const 32)) (i32.const 1))
+(assert_return (invoke "shl" (i32.const 1) (i32.const 33)) (i32.const 2))
+(assert_return (invoke "shl" (i32.const 1) (i32.const -1)) (i32.const -2147483648))
+(assert_return (invoke "shl" (i32.const 1) (i32.const 2147483647)) (i32.const -2147483648))
+(assert_return (invoke "shl" (i32.const 1) (i32.const -2147483648)) (i32.const 1))
+(assert_return (invoke "shl" (i32.const -2147483648) (i32.const 31)) (i32.const 0))
+(assert_return (invoke "shl" (i32.const -1) (i32.const 32)) (i32.const -1))
+(assert_return (invoke "shl" (i32.const -1) (i32.const 33)) (i32.const -2))
+(assert_return (invoke "shl" (i32.const -1) (i32.const -1)) (i32.const -2147483648))
+(assert_return (invoke "shl" (i32.const -1) (i32.const 2147483647)) (i32.const -2147483648))
+(assert_return (invoke "shl" (i32.const -1) (i32.const -2147483648)) (i32.const -1))
+
+(assert_return (invoke "shr_s" (i32.const 1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "shr_s" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "shr_s" (i32.const -1) (i32.const 1)) (i32.const -1))
+(assert_return (invoke "shr_s" (i32.const 2147483647) (i32.const 1)) (i32.const 1073741823))
+(assert_return (invoke "shr_s" (i32.const -1) (i32.const 1)) (i32.const -1))
+(assert_return (invoke "shr_s" (i32.const -2147483648) (i32.const 1)) (i32.const -1073741824))
+(assert_return (invoke "shr_s" (i32.const 1073741824) (i32.const 1)) (i32.const 536870912))
+(assert_return (invoke "shr_s" (i32.const 1) (i32.const 31)) (i32.const 0))
+(assert_return (invoke "shr_s" (i32.const 1) (i32.const 32)) (i32.const 1))
+(assert_return (invoke "shr_s" (i32.const 1) (i32.const 33)) (i32.const 0))
+(assert_return (invoke "shr_s" (i32.const 1) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "shr_s" (i32.const 1) (i32.const 2147483647)) (i32.const 0))
+(assert_return (invoke "shr_s" (i32.const 1) (i32.const -2147483648)) (i32.const 1))
+(assert_return (invoke "shr_s" (i32.const -2147483648) (i32.const 31)) (i32.const -1))
+(assert_return (invoke "shr_s" (i32.const -1) (i32.const 32)) (i32.const -1))
+(assert_return (invoke "shr_s" (i32.const -1) (i32.const 33)) (i32.const -1))
+(assert_return (invoke "shr_s" (i32.const -1) (i32.const -1)) (i32.const -1))
+(assert_return (invoke "shr_s" (i32.const -1) (i32.const 2147483647)) (i32.const -1))
+(assert_return (invoke "shr_s" (i32.const -1) (i32.const -2147483648)) (i32.const -1))
+
+(assert_return (invoke "shr_u" (i32.const 1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "shr_u" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "shr_u" (i32.const -1) (i32.const 1)) (i32.const 2147483647))
+(assert_return (invoke "shr_u" (i32.const 2147483647) (i32.const 1)) (i32.const 1073741823))
+(assert_return (invoke "shr_u" (i32.const -1) (i32.const 1)) (i32.const 2147483647))
+(assert_return (invoke "shr_u" (i32.const -2147483648) (i32.const 1)) (i32.const 1073741824))
+(assert_return (invoke "shr_u" (i32.const 1073741824) (i32.const 1)) (i32.const 536870912))
+(assert_return (invoke "shr_u" (i32.const 1) (i32.const 31)) (i32.const 0))
+(assert_return (invoke "shr_u" (i32.const 1) (i32.const 32)) (i32.const 1))
+(assert_return (invoke "shr_u" (i32.const 1) (i32.const 33)) (i32.const 0))
+(assert_return (invoke "shr_u" (i32.const 1) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "shr_u" (i32.const 1) (i32.const 2147483647)) (i32.const 0))
+(assert_return (invoke "shr_u" (i32.const 1) (i32.const -2147483648)) (i32.const 1))
+(assert_return (invoke "shr_u" (i32.const -2147483648) (i32.const 31)) (i32.const 1))
+(assert_return (invoke "shr_u" (i32.const -1) (i32.const 32)) (i32.const -1))
+(assert_return (invoke "shr_u" (i32.const -1) (i32.const 33)) (i32.const 2147483647))
+(assert_return (invoke "shr_u" (i32.const -1) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "shr_u" (i32.const -1) (i32.const 2147483647)) (i32.const 1))
+(assert_return (invoke "shr_u" (i32.const -1) (i32.const -2147483648)) (i32.const -1))
+
+(assert_return (invoke "rotl" (i32.const 1) (i32.const 1)) (i32.const 2))
+(assert_return (invoke "rotl" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "rotl" (i32.const -1) (i32.const 1)) (i32.const -1))
+(assert_return (invoke "rotl" (i32.const 1) (i32.const 32)) (i32.const 1))
+(assert_return (invoke "rotl" (i32.const -1412589450) (i32.const 1)) (i32.const 1469788397))
+(assert_return (invoke "rotl" (i32.const -33498112) (i32.const 4)) (i32.const -535969777))
+(assert_return (invoke "rotl" (i32.const -1329474845) (i32.const 5)) (i32.const 406477942))
+(assert_return (invoke "rotl" (i32.const 32768) (i32.const 37)) (i32.const 1048576))
+(assert_return (invoke "rotl" (i32.const -1329474845) (i32.const 65285)) (i32.const 406477942))
+(assert_return (invoke "rotl" (i32.const 1989852383) (i32.const -19)) (i32.const 1469837011))
+(assert_return (invoke "rotl" (i32.const 1989852383) (i32.const -2147483635)) (i32.const 1469837011))
+(assert_return (invoke "rotl" (i32.const 1) (i32.const 31)) (i32.const -2147483648))
+(assert_return (invoke "rotl" (i32.const -2147483648) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "rotl" (i32.const -16724992) (i32.const 1)) (i32.const -33449983))
+
+(assert_return (invoke "rotr" (i32.const 1) (i32.const 1)) (i32.const -2147483648))
+(assert_return (invoke "rotr" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "rotr" (i32.const -1) (i32.const 1)) (i32.const -1))
+(assert_return (invoke "rotr" (i32.const 1) (i32.const 32)) (i32.const 1))
+(assert_return (invoke "rotr" (i32.const -1412589450) (i32.const 1)) (i32.const 1441188923))
+(assert_return (invoke "rotr" (i32.const -33498112) (i32.const 4)) (i32.const 266341824))
+(assert_return (invoke "rotr" (i32.const -1329474845) (i32.const 5)) (i32.const 495324823))
+(assert_return (invoke "rotr" (i32.const 32768) (i32.const 37)) (i32.const 1024))
+(assert_return (invoke "rotr" (i32.const -1329474845) (i32.const 65285)) (i32.const 495324823))
+(assert_return (invoke "rotr" (i32.const 1989852383) (i32.const -19)) (i32.const -419711787))
+(assert_return (invoke "rotr" (i32.const 1989852383) (i32.const -2147483635)) (i32.const -419711787))
+(assert_return (invoke "rotr" (i32.const 1) (i32.const 31)) (i32.const 2))
+(assert_return (invoke "rotr" (i32.const -2147483648) (i32.const 1)) (i32.const 1073741824))
+(assert_return (invoke "rotr" (i32.const -16724992) (i32.const 1)) (i32.const 2139121152))
+
+(assert_trap (invoke "div_s" (i32.const 1) (i32.const 0)) "integer divide by zero")
+(assert_trap (invoke "div_s" (i32.const 0) (i32.const 0)) "integer divide by zero")
+(assert_trap (invoke "div_s" (i32.const -2147483648) (i32.const -1)) "integer overflow")
+(assert_trap (invoke "div_s" (i32.const -2147483648) (i32.const 0)) "integer divide by zero")
+(assert_return (invoke "div_s" (i32.const 1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "div_s" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "div_s" (i32.const 0) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "div_s" (i32.const -1) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "div_s" (i32.const -2147483648) (i32.const 2)) (i32.const -1073741824))
+(assert_return (invoke "div_s" (i32.const -2147483647) (i32.const 1000)) (i32.const -2147483))
+(assert_return (invoke "div_s" (i32.const -1880092688) (i32.const 65537)) (i32.const -28687))
+(assert_return (invoke "div_s" (i32.const 5) (i32.const 2)) (i32.const 2))
+(assert_return (invoke "div_s" (i32.const -5) (i32.const 2)) (i32.const -2))
+(assert_return (invoke "div_s" (i32.const 5) (i32.const -2)) (i32.const -2))
+(assert_return (invoke "div_s" (i32.const -5) (i32.const -2)) (i32.const 2))
+(assert_return (invoke "div_s" (i32.const 7) (i32.const 3)) (i32.const 2))
+(assert_return (invoke "div_s" (i32.const -7) (i32.const 3)) (i32.const -2))
+(assert_return (invoke "div_s" (i32.const 7) (i32.const -3)) (i32.const -2))
+(assert_return (invoke "div_s" (i32.const -7) (i32.const -3)) (i32.const 2))
+(assert_return (invoke "div_s" (i32.const 11) (i32.const 5)) (i32.const 2))
+(assert_return (invoke "div_s" (i32.const 17) (i32.const 7)) (i32.const 2))
+(assert_return (invoke "div_s" (i32.const 2147483647) (i32.const -1)) (i32.const -2147483647))
+
+(assert_trap (invoke "div_u" (i32.const 1) (i32.const 0)) "integer divide by zero")
+(assert_trap (invoke "div_u" (i32.const 0) (i32.const 0)) "integer divide by zero")
+(assert_return (invoke "div_u" (i32.const -2147483648) (i32.const -1)) (i32.const 0))
+(assert_trap (invoke "div_u" (i32.const -2147483648) (i32.const 0)) "integer divide by zero")
+(assert_return (invoke "div_u" (i32.const 1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "div_u" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "div_u" (i32.const 0) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "div_u" (i32.const -1) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "div_u" (i32.const -2147483648) (i32.const 2)) (i32.const 1073741824))
+(assert_return (invoke "div_u" (i32.const -2147483647) (i32.const 1000)) (i32.const 2147483))
+(assert_return (invoke "div_u" (i32.const -1880092688) (i32.const 65537)) (i32.const 36847))
+(assert_return (invoke "div_u" (i32.const 5) (i32.const 2)) (i32.const 2))
+(assert_return (invoke "div_u" (i32.const -5) (i32.const 2)) (i32.const 2147483645))
+(assert_return (invoke "div_u" (i32.const 5) (i32.const -2)) (i32.const 0))
+(assert_return (invoke "div_u" (i32.const -5) (i32.const -2)) (i32.const 0))
+(assert_return (invoke "div_u" (i32.const 7) (i32.const 3)) (i32.const 2))
+(assert_return (invoke "div_u" (i32.const -7) (i32.const 3)) (i32.const 1431655763))
+(assert_return (invoke "div_u" (i32.const 7) (i32.const -3)) (i32.const 0))
+(assert_return (invoke "div_u" (i32.const -7) (i32.const -3)) (i32.const 0))
+(assert_return (invoke "div_u" (i32.const 11) (i32.const 5)) (i32.const 2))
+(assert_return (invoke "div_u" (i32.const 17) (i32.const 7)) (i32.const 2))
+(assert_return (invoke "div_u" (i32.const 2147483647) (i32.const -1)) (i32.const 0))
+
+(assert_trap (invoke "rem_s" (i32.const 1) (i32.const 0)) "integer divide by zero")
+(assert_trap (invoke "rem_s" (i32.const 0) (i32.const 0)) "integer divide by zero")
+(assert_return (invoke "rem_s" (i32.const -2147483648) (i32.const -1)) (i32.const 0))
+(assert_trap (invoke "rem_s" (i32.const -2147483648) (i32.const 0)) "integer divide by zero")
+(assert_return (invoke "rem_s" (i32.const 1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "rem_s" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "rem_s" (i32.const 0) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "rem_s" (i32.const -1) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "rem_s" (i32.const -2147483648) (i32.const 2)) (i32.const 0))
+(assert_return (invoke "rem_s" (i32.const -2147483647) (i32.const 1000)) (i32.const -647))
+(assert_return (invoke "rem_s" (i32.const -1880092688) (i32.const 65537)) (i32.const -32769))
+(assert_return (invoke "rem_s" (i32.const 5) (i32.const 2)) (i32.const 1))
+(assert_return (invoke "rem_s" (i32.const -5) (i32.const 2)) (i32.const -1))
+(assert_return (invoke "rem_s" (i32.const 5) (i32.const -2)) (i32.const 1))
+(assert_return (invoke "rem_s" (i32.const -5) (i32.const -2)) (i32.const -1))
+(assert_return (invoke "rem_s" (i32.const 7) (i32.const 3)) (i32.const 1))
+(assert_return (invoke "rem_s" (i32.const -7) (i32.const 3)) (i32.const -1))
+(assert_return (invoke "rem_s" (i32.const 7) (i32.const -3)) (i32.const 1))
+(assert_return (invoke "rem_s" (i32.const -7) (i32.const -3)) (i32.const -1))
+(assert_return (invoke "rem_s" (i32.const 11) (i32.const 5)) (i32.const 1))
+(assert_return (invoke "rem_s" (i32.const 17) (i32.const 7)) (i32.const 3))
+(assert_return (invoke "rem_s" (i32.const 2147483647) (i32.const -1)) (i32.const 0))
+
+(assert_trap (invoke "rem_u" (i32.const 1) (i32.const 0)) "integer divide by zero")
+(assert_trap (invoke "rem_u" (i32.const 0) (i32.const 0)) "integer divide by zero")
+(assert_return (invoke "rem_u" (i32.const -2147483648) (i32.const -1)) (i32.const -2147483648))
+(assert_trap (invoke "rem_u" (i32.const -2147483648) (i32.const 0)) "integer divide by zero")
+(assert_return (invoke "rem_u" (i32.const 1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "rem_u" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "rem_u" (i32.const 0) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "rem_u" (i32.const -1) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "rem_u" (i32.const -2147483648) (i32.const 2)) (i32.const 0))
+(assert_return (invoke "rem_u" (i32.const -2147483647) (i32.const 1000)) (i32.const 649))
+(assert_return (invoke "rem_u" (i32.const -1880092688) (i32.const 65537)) (i32.const 32769))
+(assert_return (invoke "rem_u" (i32.const 5) (i32.const 2)) (i32.const 1))
+(assert_return (invoke "rem_u" (i32.const -5) (i32.const 2)) (i32.const 1))
+(assert_return (invoke "rem_u" (i32.const 5) (i32.const -2)) (i32.const 5))
+(assert_return (invoke "rem_u" (i32.const -5) (i32.const -2)) (i32.const -5))
+(assert_return (invoke "rem_u" (i32.const 7) (i32.const 3)) (i32.const 1))
+(assert_return (invoke "rem_u" (i32.const -7) (i32.const 3)) (i32.const 0))
+(assert_return (invoke "rem_u" (i32.const 7) (i32.const -3)) (i32.const 7))
+(assert_return (invoke "rem_u" (i32.const -7) (i32.const -3)) (i32.const -7))
+(assert_return (invoke "rem_u" (i32.const 11) (i32.const 5)) (i32.const 1))
+(assert_return (invoke "rem_u" (i32.const 17) (i32.const 7)) (i32.const 3))
+(assert_return (invoke "rem_u" (i32.const 2147483647) (i32.const -1)) (i32.const 2147483647))
+
+(assert_return (invoke "clz" (i32.const -1)) (i32.const 0))
+(assert_return (invoke "clz" (i32.const 0)) (i32.const 32))
+(assert_return (invoke "clz" (i32.const 32768)) (i32.const 16))
+(assert_return (invoke "clz" (i32.const 255)) (i32.const 24))
+(assert_return (invoke "clz" (i32.const -2147483648)) (i32.const 0))
+(assert_return (invoke "clz" (i32.const 1)) (i32.const 31))
+(assert_return (invoke "clz" (i32.const 2)) (i32.const 30))
+(assert_return (invoke "clz" (i32.const 2147483647)) (i32.const 1))
+
+(assert_return (invoke "ctz" (i32.const -1)) (i32.const 0))
+(assert_return (invoke "ctz" (i32.const 0)) (i32.const 32))
+(assert_return (invoke "ctz" (i32.const 32768)) (i32.const 15))
+(assert_return (invoke "ctz" (i32.const 65536)) (i32.const 16))
+(assert_return (invoke "ctz" (i32.const -2147483648)) (i32.const 31))
+(assert_return (invoke "ctz" (i32.const 2147483647)) (i32.const 0))
+
+(assert_return (invoke "popcnt" (i32.const -1)) (i32.const 32))
+(assert_return (invoke "popcnt" (i32.const 0)) (i32.const 0))
+(assert_return (invoke "popcnt" (i32.const 32768)) (i32.const 1))
+(assert_return (invoke "popcnt" (i32.const -2147450880)) (i32.const 2))
+(assert_return (invoke "popcnt" (i32.const 2147483647)) (i32.const 31))
+(assert_return (invoke "popcnt" (i32.const -1431655766)) (i32.const 16))
+(assert_return (invoke "popcnt" (i32.const 1431655765)) (i32.const 16))
+(assert_return (invoke "popcnt" (i32.const -559038737)) (i32.const 24))
+
+(assert_return (invoke "extend8_s" (i32.const 0)) (i32.const 0))
+(assert_return (invoke "extend8_s" (i32.const 127)) (i32.const 127))
+(assert_return (invoke "extend8_s" (i32.const 128)) (i32.const -128))
+(assert_return (invoke "extend8_s" (i32.const 255)) (i32.const -1))
+(assert_return (invoke "extend8_s" (i32.const 19088640)) (i32.const 0))
+(assert_return (invoke "extend8_s" (i32.const -19088768)) (i32.const -128))
+(assert_return (invoke "extend8_s" (i32.const -1)) (i32.const -1))
+
+(assert_return (invoke "extend16_s" (i32.const 0)) (i32.const 0))
+(assert_return (invoke "extend16_s" (i32.const 32767)) (i32.const 32767))
+(assert_return (invoke "extend16_s" (i32.const 32768)) (i32.const -32768))
+(assert_return (invoke "extend16_s" (i32.const 65535)) (i32.const -1))
+(assert_return (invoke "extend16_s" (i32.const 19070976)) (i32.const 0))
+(assert_return (invoke "extend16_s" (i32.const -19103744)) (i32.const -32768))
+(assert_return (invoke "extend16_s" (i32.const -1)) (i32.const -1))
+
+(assert_return (invoke "eqz" (i32.const 0)) (i32.const 1))
+(assert_return (invoke "eqz" (i32.const 1)) (i32.const 0))
+(assert_return (invoke "eqz" (i32.const -2147483648)) (i32.const 0))
+(assert_return (invoke "eqz" (i32.const 2147483647)) (i32.const 0))
+(assert_return (invoke "eqz" (i32.const -1)) (i32.const 0))
+
+(assert_return (invoke "eq" (i32.const 0) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "eq" (i32.const 1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "eq" (i32.const -1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "eq" (i32.const -2147483648) (i32.const -2147483648)) (i32.const 1))
+(assert_return (invoke "eq" (i32.const 2147483647) (i32.const 2147483647)) (i32.const 1))
+(assert_return (invoke "eq" (i32.const -1) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "eq" (i32.const 1) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "eq" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "eq" (i32.const -2147483648) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "eq" (i32.const 0) (i32.const -2147483648)) (i32.const 0))
+(assert_return (invoke "eq" (i32.const -2147483648) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "eq" (i32.const -1) (i32.const -2147483648)) (i32.const 0))
+(assert_return (invoke "eq" (i32.const -2147483648) (i32.const 2147483647)) (i32.const 0))
+(assert_return (invoke "eq" (i32.const 2147483647) (i32.const -2147483648)) (i32.const 0))
+
+(assert_return (invoke "ne" (i32.const 0) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "ne" (i32.const 1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "ne" (i32.const -1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "ne" (i32.const -2147483648) (i32.const -2147483648)) (i32.const 0))
+(assert_return (invoke "ne" (i32.const 2147483647) (i32.const 2147483647)) (i32.const 0))
+(assert_return (invoke "ne" (i32.const -1) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "ne" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "ne" (i32.const 0) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "ne" (i32.const -2147483648) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "ne" (i32.const 0) (i32.const -2147483648)) (i32.const 1))
+(assert_return (invoke "ne" (i32.const -2147483648) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "ne" (i32.const -1) (i32.const -2147483648)) (i32.const 1))
+(assert_return (invoke "ne" (i32.const -2147483648) (i32.const 2147483647)) (i32.const 1))
+(assert_return (invoke "ne" (i32.const 2147483647) (i32.const -2147483648)) (i32.const 1))
+
+(assert_return (invoke "lt_s" (i32.const 0) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "lt_s" (i32.const 1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "lt_s" (i32.const -1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "lt_s" (i32.const -2147483648) (i32.const -2147483648)) (i32.const 0))
+(assert_return (invoke "lt_s" (i32.const 2147483647) (i32.const 2147483647)) (i32.const 0))
+(assert_return (invoke "lt_s" (i32.const -1) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "lt_s" (i32.const 1) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "lt_s" (i32.const 0) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "lt_s" (i32.const -2147483648) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "lt_s" (i32.const 0) (i32.const -2147483648)) (i32.const 0))
+(assert_return (invoke "lt_s" (i32.const -2147483648) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "lt_s" (i32.const -1) (i32.const -2147483648)) (i32.const 0))
+(assert_return (invoke "lt_s" (i32.const -2147483648) (i32.const 2147483647)) (i32.const 1))
+(assert_return (invoke "lt_s" (i32.const 2147483647) (i32.const -2147483648)) (i32.const 0))
+
+(assert_return (invoke "lt_u" (i32.const 0) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "lt_u" (i32.const 1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "lt_u" (i32.const -1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "lt_u" (i32.const -2147483648) (i32.const -2147483648)) (i32.const 0))
+(assert_return (invoke "lt_u" (i32.const 2147483647) (i32.const 2147483647)) (i32.const 0))
+(assert_return (invoke "lt_u" (i32.const -1) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "lt_u" (i32.const 1) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "lt_u" (i32.const 0) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "lt_u" (i32.const -2147483648) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "lt_u" (i32.const 0) (i32.const -2147483648)) (i32.const 1))
+(assert_return (invoke "lt_u" (i32.const -2147483648) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "lt_u" (i32.const -1) (i32.const -2147483648)) (i32.const 0))
+(assert_return (invoke "lt_u" (i32.const -2147483648) (i32.const 2147483647)) (i32.const 0))
+(assert_return (invoke "lt_u" (i32.const 2147483647) (i32.const -2147483648)) (i32.const 1))
+
+(assert_return (invoke "le_s" (i32.const 0) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "le_s" (i32.const 1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "le_s" (i32.const -1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "le_s" (i32.const -2147483648) (i32.const -2147483648)) (i32.const 1))
+(assert_return (invoke "le_s" (i32.const 2147483647) (i32.const 2147483647)) (i32.const 1))
+(assert_return (invoke "le_s" (i32.const -1) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "le_s" (i32.const 1) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "le_s" (i32.const 0) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "le_s" (i32.const -2147483648) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "le_s" (i32.const 0) (i32.const -2147483648)) (i32.const 0))
+(assert_return (invoke "le_s" (i32.const -2147483648) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "le_s" (i32.const -1) (i32.const -2147483648)) (i32.const 0))
+(assert_return (invoke "le_s" (i32.const -2147483648) (i32.const 2147483647)) (i32.const 1))
+(assert_return (invoke "le_s" (i32.const 2147483647) (i32.const -2147483648)) (i32.const 0))
+
+(assert_return (invoke "le_u" (i32.const 0) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "le_u" (i32.const 1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "le_u" (i32.const -1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "le_u" (i32.const -2147483648) (i32.const -2147483648)) (i32.const 1))
+(assert_return (invoke "le_u" (i32.const 2147483647) (i32.const 2147483647)) (i32.const 1))
+(assert_return (invoke "le_u" (i32.const -1) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "le_u" (i32.const 1) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "le_u" (i32.const 0) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "le_u" (i32.const -2147483648) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "le_u" (i32.const 0) (i32.const -2147483648)) (i32.const 1))
+(assert_return (invoke "le_u" (i32.const -2147483648) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "le_u" (i32.const -1) (i32.const -2147483648)) (i32.const 0))
+(assert_return (invoke "le_u" (i32.const -2147483648) (i32.const 2147483647)) (i32.const 0))
+(assert_return (invoke "le_u" (i32.const 2147483647) (i32.const -2147483648)) (i32.const 1))
+
+(assert_return (invoke "gt_s" (i32.const 0) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "gt_s" (i32.const 1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "gt_s" (i32.const -1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "gt_s" (i32.const -2147483648) (i32.const -2147483648)) (i32.const 0))
+(assert_return (invoke "gt_s" (i32.const 2147483647) (i32.const 2147483647)) (i32.const 0))
+(assert_return (invoke "gt_s" (i32.const -1) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "gt_s" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "gt_s" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "gt_s" (i32.const -2147483648) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "gt_s" (i32.const 0) (i32.const -2147483648)) (i32.const 1))
+(assert_return (invoke "gt_s" (i32.const -2147483648) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "gt_s" (i32.const -1) (i32.const -2147483648)) (i32.const 1))
+(assert_return (invoke "gt_s" (i32.const -2147483648) (i32.const 2147483647)) (i32.const 0))
+(assert_return (invoke "gt_s" (i32.const 2147483647) (i32.const -2147483648)) (i32.const 1))
+
+(assert_return (invoke "gt_u" (i32.const 0) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "gt_u" (i32.const 1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "gt_u" (i32.const -1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "gt_u" (i32.const -2147483648) (i32.const -2147483648)) (i32.const 0))
+(assert_return (invoke "gt_u" (i32.const 2147483647) (i32.const 2147483647)) (i32.const 0))
+(assert_return (invoke "gt_u" (i32.const -1) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "gt_u" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "gt_u" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "gt_u" (i32.const -2147483648) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "gt_u" (i32.const 0) (i32.const -2147483648)) (i32.const 0))
+(assert_return (invoke "gt_u" (i32.const -2147483648) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "gt_u" (i32.const -1) (i32.const -2147483648)) (i32.const 1))
+(assert_return (invoke "gt_u" (i32.const -2147483648) (i32.const 2147483647)) (i32.const 1))
+(assert_return (invoke "gt_u" (i32.const 2147483647) (i32.const -2147483648)) (i32.const 0))
+
+(assert_return (invoke "ge_s" (i32.const 0) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "ge_s" (i32.const 1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "ge_s" (i32.const -1) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "ge_s" (i32.const -2147483648) (i32.const -2147483648)) (i32.const 1))
+(assert_return (invoke "ge_s" (i32.const 2147483647) (i32.const 2147483647)) (i32.const 1))
+(assert_return (invoke "ge_s" (i32.const -1) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "ge_s" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "ge_s" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "ge_s" (i32.const -2147483648) (i32.const 0)) (i32.const 0))
+(assert_return (invoke "ge_s" (i32.const 0) (i32.const -2147483648)) (i32.const 1))
+(assert_return (invoke "ge_s" (i32.const -2147483648) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "ge_s" (i32.const -1) (i32.const -2147483648)) (i32.const 1))
+(assert_return (invoke "ge_s" (i32.const -2147483648) (i32.const 2147483647)) (i32.const 0))
+(assert_return (invoke "ge_s" (i32.const 2147483647) (i32.const -2147483648)) (i32.const 1))
+
+(assert_return (invoke "ge_u" (i32.const 0) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "ge_u" (i32.const 1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "ge_u" (i32.const -1) (i32.const 1)) (i32.const 1))
+(assert_return (invoke "ge_u" (i32.const -2147483648) (i32.const -2147483648)) (i32.const 1))
+(assert_return (invoke "ge_u" (i32.const 2147483647) (i32.const 2147483647)) (i32.const 1))
+(assert_return (invoke "ge_u" (i32.const -1) (i32.const -1)) (i32.const 1))
+(assert_return (invoke "ge_u" (i32.const 1) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "ge_u" (i32.const 0) (i32.const 1)) (i32.const 0))
+(assert_return (invoke "ge_u" (i32.const -2147483648) (i32.const 0)) (i32.const 1))
+(assert_return (invoke "ge_u" (i32.const 0) (i32.const -2147483648)) (i32.const 0))
+(assert_return (invoke "ge_u" (i32.const -2147483648) (i32.const -1)) (i32.const 0))
+(assert_return (invoke "ge_u" (i32.const -1) (i32.const -2147483648)) (i32.const 1))
+(assert_return (invoke "ge_u" (i32.const -2147483648) (i32.const 2147483647)) (i32.const 1))
+(assert_return (invoke "ge_u" (i32.const 2147483647) (i32.const -2147483648)) (i32.const 0))
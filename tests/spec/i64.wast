@@ -0,0 +1,488 @@
+;; Assertions follow the official spec test suite's i64.wast, limited to the
+;; assert_return and assert_trap directives tests/spec.rs runs
+
+(module
+  (func (export "add") (param $x i64) (param $y i64) (result i64) (i64.add (local.get $x) (local.get $y)))
+  (func (export "sub") (param $x i64) (param $y i64) (result i64) (i64.sub (local.get $x) (local.get $y)))
+  (func (export "mul") (param $x i64) (param $y i64) (result i64) (i64.mul (local.get $x) (local.get $y)))
+  (func (export "and") (param $x i64) (param $y i64) (result i64) (i64.and (local.get $x) (local.get $y)))
+  (func (export "or") (param $x i64) (param $y i64) (result i64) (i64.or (local.get $x) (local.get $y)))
+  (func (export "xor") (param $x i64) (param $y i64) (result i64) (i64.xor (local.get $x) (local.get $y)))
+  (func (export "shl") (param $x i64) (param $y i64) (result i64) (i64.shl (local.get $x) (local.get $y)))
+  (func (export "shr_s") (param $x i64) (param $y i64) (result i64) (i64.shr_s (local.get $x) (local.get $y)))
+  (func (export "shr_u") (param $x i64) (param $y i64) (result i64) (i64.shr_u (local.get $x) (local.get $y)))
+  (func (export "rotl") (param $x i64) (param $y i64) (result i64) (i64.rotl (local.get $x) (local.get $y)))
+  (func (export "rotr") (param $x i64) (param $y i64) (result i64) (i64.rotr (local.get $x) (local.get $y)))
+  (func (export "div_s") (param $x i64) (param $y i64) (result i64) (i64.div_s (local.get $x) (local.get $y)))
+  (func (export "div_u") (param $x i64) (param $y i64) (result i64) (i64.div_u (local.get $x) (local.get $y)))
+  (func (export "rem_s") (param $x i64) (param $y i64) (result i64) (i64.rem_s (local.get $x) (local.get $y)))
+  (func (export "rem_u") (param $x i64) (param $y i64) (result i64) (i64.rem_u (local.get $x) (local.get $y)))
+  (func (export "clz") (param $x i64) (result i64) (i64.clz (local.get $x)))
+  (func (export "ctz") (param $x i64) (result i64) (i64.ctz (local.get $x)))
+  (func (export "popcnt") (param $x i64) (result i64) (i64.popcnt (local.get $x)))
+  (func (export "extend8_s") (param $x i64) (result i64) (i64.extend8_s (local.get $x)))
+  (func (export "extend16_s") (param $x i64) (result i64) (i64.extend16_s (local.get $x)))
+  (func (export "extend32_s") (param $x i64) (result i64) (i64.extend32_s (local.get $x)))
+  (func (export "eqz") (param $x i64) (result i32) (i64.eqz (local.get $x)))
+  (func (export "eq") (param $x i64) (param $y i64) (result i32) (i64.eq (local.get $x) (local.get $y)))
+  (func (export "ne") (param $x i64) (param $y i64) (result i32) (i64.ne (local.get $x) (local.get $y)))
+  (func (export "lt_s") (param $x i64) (param $y i64) (result i32) (i64.lt_s (local.get $x) (local.get $y)))
+  (func (export "lt_u") (param $x i64) (param $y i64) (result i32) (i64.lt_u (local.get $x) (local.get $y)))
+  (func (export "le_s") (param $x i64) (param $y i64) (result i32) (i64.le_s (local.get $x) (local.get $y)))
+  (func (export "le_u") (param $x i64) (param $y i64) (result i32) (i64.le_u (local.get $x) (local.get $y)))
+  (func (export "gt_s") (param $x i64) (param $y i64) (result i32) (i64.gt_s (local.get $x) (local.get $y)))
+  (func (export "gt_u") (param $x i64) (param $y i64) (result i32) (i64.gt_u (local.get $x) (local.get $y)))
+  (func (export "ge_s") (param $x i64) (param $y i64) (result i32) (i64.ge_s (local.get $x) (local.get $y)))
+  (func (export "ge_u") (param $x i64) (param $y i64) (result i32) (i64.ge_u (local.get $x) (local.get $y)))
+)
+
+(assert_return (invoke "add" (i64.const 1) (i64.const 1)) (i64.const 2))
+(assert_return (invoke "add" (i64.const 1) (i64.const 0)) (i64.const 1))
+(assert_return (invoke "add" (i64.const -1) (i64.const -1)) (i64.const -2))
+(assert_return (invoke "add" (i64.const -1) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "add" (i64.const 9223372036854775807) (i64.const 1)) (i64.const -9223372036854775808))
+(assert_return (invoke "add" (i64.const -9223372036854775808) (i64.const -1)) (i64.const 9223372036854775807))
+(assert_return (invoke "add" (i64.const -9223372036854775808) (i64.const -9223372036854775808)) (i64.const 0))
+(assert_return (invoke "add" (i64.const 1073741823) (i64.const 1)) (i64.const 1073741824))
+
+(assert_return (invoke "sub" (i64.const 1) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "sub" (i64.const 1) (i64.const 0)) (i64.const 1))
+(assert_return (invoke "sub" (i64.const -1) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "sub" (i64.const -1) (i64.const 1)) (i64.const -2))
+(assert_return (invoke "sub" (i64.const 9223372036854775807) (i64.const 1)) (i64.const 9223372036854775806))
+(assert_return (invoke "sub" (i64.const -9223372036854775808) (i64.const -1)) (i64.const -9223372036854775807))
+(assert_return (invoke "sub" (i64.const -9223372036854775808) (i64.const -9223372036854775808)) (i64.const 0))
+(assert_return (invoke "sub" (i64.const 1073741823) (i64.const 1)) (i64.const 1073741822))
+(assert_return (invoke "sub" (i64.const 9223372036854775807) (i64.const -1)) (i64.const -9223372036854775808))
+(assert_return (invoke "sub" (i64.const -9223372036854775808) (i64.const 1)) (i64.const 9223372036854775807))
+
+(assert_return (invoke "mul" (i64.const 1) (i64.const 1)) (i64.const 1))
+(assert_return (invoke "mul" (i64.const 1) (i64.const 0)) (i64.const 0))
+(assert_return (invoke "mul" (i64.const -1) (i64.const -1)) (i64.const 1))
+(assert_return (invoke "mul" (i64.const 1152921504606846976) (i64.const 4096)) (i64.const 0))
+(assert_return (invoke "mul" (i64.const -9223372036854775808) (i64.const 0)) (i64.const 0))
+(assert_return (invoke "mul" (i64.const -9223372036854775808) (i64.const -1)) (i64.const -9223372036854775808))
+(assert_return (invoke "mul" (i64.const 9223372036854775807) (i64.const -1)) (i64.const -9223372036854775807))
+(assert_return (invoke "mul" (i64.const 81985529216486895) (i64.const -81985529216486896)) (i64.const 2465395958572223728))
+(assert_return (invoke "mul" (i64.const 9223372036854775807) (i64.const 9223372036854775807)) (i64.const 1))
+
+(assert_return (invoke "and" (i64.const 1) (i64.const 0)) (i64.const 0))
+(assert_return (invoke "and" (i64.const 0) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "and" (i64.const 1) (i64.const 1)) (i64.const 1))
+(assert_return (invoke "and" (i64.const 0) (i64.const 0)) (i64.const 0))
+(assert_return (invoke "and" (i64.const 9223372036854775807) (i64.const -9223372036854775808)) (i64.const 0))
+(assert_return (invoke "and" (i64.const -9223372036854775808) (i64.const 0)) (i64.const 0))
+(assert_return (invoke "and" (i64.const -1) (i64.const -9223372036854775808)) (i64.const -9223372036854775808))
+(assert_return (invoke "and" (i64.const -1) (i64.const 9223372036854775807)) (i64.const 9223372036854775807))
+(assert_return (invoke "and" (i64.const 4042326015) (i64.const 4294963440)) (i64.const 4042322160))
+(assert_return (invoke "and" (i64.const -1) (i64.const -1)) (i64.const -1))
+
+(assert_return (invoke "or" (i64.const 1) (i64.const 0)) (i64.const 1))
+(assert_return (invoke "or" (i64.const 0) (i64.const 1)) (i64.const 1))
+(assert_return (invoke "or" (i64.const 1) (i64.const 1)) (i64.const 1))
+(assert_return (invoke "or" (i64.const 0) (i64.const 0)) (i64.const 0))
+(assert_return (invoke "or" (i64.const 9223372036854775807) (i64.const -9223372036854775808)) (i64.const -1))
+(assert_return (invoke "or" (i64.const -9223372036854775808) (i64.const 0)) (i64.const -9223372036854775808))
+(assert_return (invoke "or" (i64.const -1) (i64.const -9223372036854775808)) (i64.const -1))
+(assert_return (invoke "or" (i64.const -1) (i64.const 9223372036854775807)) (i64.const -1))
+(assert_return (invoke "or" (i64.const 4042326015) (i64.const 4294963440)) (i64.const 4294967295))
+(assert_return (invoke "or" (i64.const -1) (i64.const -1)) (i64.const -1))
+
+(assert_return (invoke "xor" (i64.const 1) (i64.const 0)) (i64.const 1))
+(assert_return (invoke "xor" (i64.const 0) (i64.const 1)) (i64.const 1))
+(assert_return (invoke "xor" (i64.const 1) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "xor" (i64.const 0) (i64.const 0)) (i64.const 0))
+(assert_return (invoke "xor" (i64.const 9223372036854775807) (i64.const -9223372036854775808)) (i64.const -1))
+(assert_return (invoke "xor" (i64.const -9223372036854775808) (i64.const 0)) (i64.const -9223372036854775808))
+(assert_return (invoke "xor" (i64.const -1) (i64.const -9223372036854775808)) (i64.const 9223372036854775807))
+(assert_return (invoke "xor" (i64.const -1) (i64.const 9223372036854775807)) (i64.const -9223372036854775808))
+(assert_return (invoke "xor" (i64.const 4042326015) (i64.const 4294963440)) (i64.const 252645135))
+(assert_return (invoke "xor" (i64.const -1) (i64.const -1)) (i64.const 0))
+
+(assert_return (invoke "shl" (i64.const 1) (i64.const 1)) (i64.const 2))
+(assert_return (invoke "shl" (i64.const 1) (i64.const 0)) (i64.const 1))
+(assert_return (invoke "shl" (i64.const -1) (i64.const 1)) (i64.const -2))
+(assert_return (invoke "shl" (i64.const 9223372036854775807) (i64.const 1)) (i64.const -2))
+(assert_return (invoke "shl" (i64.const -1) (i64.const 1)) (i64.const -2))
+(assert_return (invoke "shl" (i64.const -9223372036854775808) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "shl" (i64.const 4611686018427387904) (i64.const 1)) (i64.const -9223372036854775808))
+(assert_return (invoke "shl" (i64.const 1) (i64.const 63)) (i64.const -9223372036854775808))
+(assert_return (invoke "shl" (i64.const 1) (i64.const 64)) (i64.const 1))
+(assert_return (invoke "shl" (i64.const 1) (i64.const 65)) (i64.const 2))
+(assert_return (invoke "shl" (i64.const 1) (i64.const -1)) (i64.const -9223372036854775808))
+(assert_return (invoke "shl" (i64.const 1) (i64.const 9223372036854775807)) (i64.const -9223372036854775808))
+(assert_return (invoke "shl" (i64.const 1) (i64.const -9223372036854775808)) (i64.const 1))
+(assert_return (invoke "shl" (i64.const -9223372036854775808) (i64.const 63)) (i64.const 0))
+(assert_return (invoke "shl" (i64.const -1) (i64.const 64)) (i64.const -1))
+(assert_return (invoke "shl" (i64.const -1) (i64.const 65)) (i64.const -2))
+(assert_return (invoke "shl" (i64.const -1) (i64.const -1)) (i64.const -9223372036854775808))
+(assert_return (invoke "shl" (i64.const -1) (i64.const 9223372036854775807)) (i64.const -9223372036854775808))
+(assert_return (invoke "shl" (i64.const -1) (i64.const -9223372036854775808)) (i64.const -1))
+
+(assert_return (invoke "shr_s" (i64.const 1) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "shr_s" (i64.const 1) (i64.const 0)) (i64.const 1))
+(assert_return (invoke "shr_s" (i64.const -1) (i64.const 1)) (i64.const -1))
+(assert_return (invoke "shr_s" (i64.const 9223372036854775807) (i64.const 1)) (i64.const 4611686018427387903))
+(assert_return (invoke "shr_s" (i64.const -1) (i64.const 1)) (i64.const -1))
+(assert_return (invoke "shr_s" (i64.const -9223372036854775808) (i64.const 1)) (i64.const -4611686018427387904))
+(assert_return (invoke "shr_s" (i64.const 4611686018427387904) (i64.const 1)) (i64.const 2305843009213693952))
+(assert_return (invoke "shr_s" (i64.const 1) (i64.const 63)) (i64.const 0))
+(assert_return (invoke "shr_s" (i64.const 1) (i64.const 64)) (i64.const 1))
+(assert_return (invoke "shr_s" (i64.const 1) (i64.const 65)) (i64.const 0))
+(assert_return (invoke "shr_s" (i64.const 1) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "shr_s" (i64.const 1) (i64.const 9223372036854775807)) (i64.const 0))
+(assert_return (invoke "shr_s" (i64.const 1) (i64.const -9223372036854775808)) (i64.const 1))
+(assert_return (invoke "shr_s" (i64.const -9223372036854775808) (i64.const 63)) (i64.const -1))
+(assert_return (invoke "shr_s" (i64.const -1) (i64.const 64)) (i64.const -1))
+(assert_return (invoke "shr_s" (i64.const -1) (i64.const 65)) (i64.const -1))
+(assert_return (invoke "shr_s" (i64.const -1) (i64.const -1)) (i64.const -1))
+(assert_return (invoke "shr_s" (i64.const -1) (i64.const 9223372036854775807)) (i64.const -1))
+(assert_return (invoke "shr_s" (i64.const -1) (i64.const -9223372036854775808)) (i64.const -1))
+
+(assert_return (invoke "shr_u" (i64.const 1) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "shr_u" (i64.const 1) (i64.const 0)) (i64.const 1))
+(assert_return (invoke "shr_u" (i64.const -1) (i64.const 1)) (i64.const 9223372036854775807))
+(assert_return (invoke "shr_u" (i64.const 9223372036854775807) (i64.const 1)) (i64.const 4611686018427387903))
+(assert_return (invoke "shr_u" (i64.const -1) (i64.const 1)) (i64.const 9223372036854775807))
+(assert_return (invoke "shr_u" (i64.const -9223372036854775808) (i64.const 1)) (i64.const 4611686018427387904))
+(assert_return (invoke "shr_u" (i64.const 4611686018427387904) (i64.const 1)) (i64.const 2305843009213693952))
+(assert_return (invoke "shr_u" (i64.const 1) (i64.const 63)) (i64.const 0))
+(assert_return (invoke "shr_u" (i64.const 1) (i64.const 64)) (i64.const 1))
+(assert_return (invoke "shr_u" (i64.const 1) (i64.const 65)) (i64.const 0))
+(assert_return (invoke "shr_u" (i64.const 1) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "shr_u" (i64.const 1) (i64.const 9223372036854775807)) (i64.const 0))
+(assert_return (invoke "shr_u" (i64.const 1) (i64.const -9223372036854775808)) (i64.const 1))
+(assert_return (invoke "shr_u" (i64.const -9223372036854775808) (i64.const 63)) (i64.const 1))
+(assert_return (invoke "shr_u" (i64.const -1) (i64.const 64)) (i64.const -1))
+(assert_return (invoke "shr_u" (i64.const -1) (i64.const 65)) (i64.const 9223372036854775807))
+(assert_return (invoke "shr_u" (i64.const -1) (i64.const -1)) (i64.const 1))
+(assert_return (invoke "shr_u" (i64.const -1) (i64.const 9223372036854775807)) (i64.const 1))
+(assert_return (invoke "shr_u" (i64.const -1) (i64.const -9223372036854775808)) (i64.const -1))
+
+(assert_return (invoke "rotl" (i64.const 1) (i64.const 1)) (i64.const 2))
+(assert_return (invoke "rotl" (i64.const 1) (i64.const 0)) (i64.const 1))
+(assert_return (invoke "rotl" (i64.const -1) (i64.const 1)) (i64.const -1))
+(assert_return (invoke "rotl" (i64.const 1) (i64.const 64)) (i64.const 1))
+(assert_return (invoke "rotl" (i64.const -6067025490386449714) (i64.const 1)) (i64.const 6312693092936652189))
+(assert_return (invoke "rotl" (i64.const -144115184384868352) (i64.const 4)) (i64.const -2305842950157893617))
+(assert_return (invoke "rotl" (i64.const -6067173104435169271) (i64.const 53)) (i64.const 87109505680009935))
+(assert_return (invoke "rotl" (i64.const -6066028401059725156) (i64.const 63)) (i64.const 6190357836324913230))
+(assert_return (invoke "rotl" (i64.const -6067173104435169271) (i64.const 245)) (i64.const 87109505680009935))
+(assert_return (invoke "rotl" (i64.const -6067067139002042359) (i64.const -19)) (i64.const -3530481836149793302))
+(assert_return (invoke "rotl" (i64.const -6067173104435169271) (i64.const -9223372036854775755)) (i64.const 87109505680009935))
+(assert_return (invoke "rotl" (i64.const 1) (i64.const 63)) (i64.const -9223372036854775808))
+(assert_return (invoke "rotl" (i64.const -9223372036854775808) (i64.const 1)) (i64.const 1))
+
+(assert_return (invoke "rotr" (i64.const 1) (i64.const 1)) (i64.const -9223372036854775808))
+(assert_return (invoke "rotr" (i64.const 1) (i64.const 0)) (i64.const 1))
+(assert_return (invoke "rotr" (i64.const -1) (i64.const 1)) (i64.const -1))
+(assert_return (invoke "rotr" (i64.const 1) (i64.const 64)) (i64.const 1))
+(assert_return (invoke "rotr" (i64.const -6067025490386449714) (i64.const 1)) (i64.const 6189859291661550951))
+(assert_return (invoke "rotr" (i64.const -144115184384868352) (i64.const 4)) (i64.const 1143914305582792704))
+(assert_return (invoke "rotr" (i64.const -6067173104435169271) (i64.const 53)) (i64.const 7534987797011123550))
+(assert_return (invoke "rotr" (i64.const -6066028401059725156) (i64.const 63)) (i64.const 6314687271590101305))
+(assert_return (invoke "rotr" (i64.const -6067173104435169271) (i64.const 245)) (i64.const 7534987797011123550))
+(assert_return (invoke "rotr" (i64.const -6067067139002042359) (i64.const -19)) (i64.const -7735078922541506965))
+(assert_return (invoke "rotr" (i64.const -6067173104435169271) (i64.const -9223372036854775755)) (i64.const 7534987797011123550))
+(assert_return (invoke "rotr" (i64.const 1) (i64.const 63)) (i64.const 2))
+(assert_return (invoke "rotr" (i64.const -9223372036854775808) (i64.const 1)) (i64.const 4611686018427387904))
+
+(assert_trap (invoke "div_s" (i64.const 1) (i64.const 0)) "integer divide by zero")
+(assert_trap (invoke "div_s" (i64.const 0) (i64.const 0)) "integer divide by zero")
+(assert_trap (invoke "div_s" (i64.const -9223372036854775808) (i64.const -1)) "integer overflow")
+(assert_trap (invoke "div_s" (i64.const -9223372036854775808) (i64.const 0)) "integer divide by zero")
+(assert_return (invoke "div_s" (i64.const 1) (i64.const 1)) (i64.const 1))
+(assert_return (invoke "div_s" (i64.const 0) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "div_s" (i64.const 0) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "div_s" (i64.const -1) (i64.const -1)) (i64.const 1))
+(assert_return (invoke "div_s" (i64.const -9223372036854775808) (i64.const 2)) (i64.const -4611686018427387904))
+(assert_return (invoke "div_s" (i64.const -9223372036854775807) (i64.const 1000)) (i64.const -9223372036854775))
+(assert_return (invoke "div_s" (i64.const -8074936608141340688) (i64.const 4294967297)) (i64.const -1880092687))
+(assert_return (invoke "div_s" (i64.const 5) (i64.const 2)) (i64.const 2))
+(assert_return (invoke "div_s" (i64.const -5) (i64.const 2)) (i64.const -2))
+(assert_return (invoke "div_s" (i64.const 5) (i64.const -2)) (i64.const -2))
+(assert_return (invoke "div_s" (i64.const -5) (i64.const -2)) (i64.const 2))
+(assert_return (invoke "div_s" (i64.const 7) (i64.const 3)) (i64.const 2))
+(assert_return (invoke "div_s" (i64.const -7) (i64.const 3)) (i64.const -2))
+(assert_return (invoke "div_s" (i64.const 7) (i64.const -3)) (i64.const -2))
+(assert_return (invoke "div_s" (i64.const -7) (i64.const -3)) (i64.const 2))
+(assert_return (invoke "div_s" (i64.const 11) (i64.const 5)) (i64.const 2))
+(assert_return (invoke "div_s" (i64.const 17) (i64.const 7)) (i64.const 2))
+(assert_return (invoke "div_s" (i64.const 9223372036854775807) (i64.const -1)) (i64.const -9223372036854775807))
+
+(assert_trap (invoke "div_u" (i64.const 1) (i64.const 0)) "integer divide by zero")
+(assert_trap (invoke "div_u" (i64.const 0) (i64.const 0)) "integer divide by zero")
+(assert_return (invoke "div_u" (i64.const -9223372036854775808) (i64.const -1)) (i64.const 0))
+(assert_trap (invoke "div_u" (i64.const -9223372036854775808) (i64.const 0)) "integer divide by zero")
+(assert_return (invoke "div_u" (i64.const 1) (i64.const 1)) (i64.const 1))
+(assert_return (invoke "div_u" (i64.const 0) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "div_u" (i64.const 0) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "div_u" (i64.const -1) (i64.const -1)) (i64.const 1))
+(assert_return (invoke "div_u" (i64.const -9223372036854775808) (i64.const 2)) (i64.const 4611686018427387904))
+(assert_return (invoke "div_u" (i64.const -9223372036854775807) (i64.const 1000)) (i64.const 9223372036854775))
+(assert_return (invoke "div_u" (i64.const -8074936608141340688) (i64.const 4294967297)) (i64.const 2414874607))
+(assert_return (invoke "div_u" (i64.const 5) (i64.const 2)) (i64.const 2))
+(assert_return (invoke "div_u" (i64.const -5) (i64.const 2)) (i64.const 9223372036854775805))
+(assert_return (invoke "div_u" (i64.const 5) (i64.const -2)) (i64.const 0))
+(assert_return (invoke "div_u" (i64.const -5) (i64.const -2)) (i64.const 0))
+(assert_return (invoke "div_u" (i64.const 7) (i64.const 3)) (i64.const 2))
+(assert_return (invoke "div_u" (i64.const -7) (i64.const 3)) (i64.const 6148914691236517203))
+(assert_return (invoke "div_u" (i64.const 7) (i64.const -3)) (i64.const 0))
+(assert_return (invoke "div_u" (i64.const -7) (i64.const -3)) (i64.const 0))
+(assert_return (invoke "div_u" (i64.const 11) (i64.const 5)) (i64.const 2))
+(assert_return (invoke "div_u" (i64.const 17) (i64.const 7)) (i64.const 2))
+(assert_return (invoke "div_u" (i64.const 9223372036854775807) (i64.const -1)) (i64.const 0))
+
+(assert_trap (invoke "rem_s" (i64.const 1) (i64.const 0)) "integer divide by zero")
+(assert_trap (invoke "rem_s" (i64.const 0) (i64.const 0)) "integer divide by zero")
+(assert_return (invoke "rem_s" (i64.const -9223372036854775808) (i64.const -1)) (i64.const 0))
+(assert_trap (invoke "rem_s" (i64.const -9223372036854775808) (i64.const 0)) "integer divide by zero")
+(assert_return (invoke "rem_s" (i64.const 1) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "rem_s" (i64.const 0) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "rem_s" (i64.const 0) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "rem_s" (i64.const -1) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "rem_s" (i64.const -9223372036854775808) (i64.const 2)) (i64.const 0))
+(assert_return (invoke "rem_s" (i64.const -9223372036854775807) (i64.const 1000)) (i64.const -807))
+(assert_return (invoke "rem_s" (i64.const -8074936608141340688) (i64.const 4294967297)) (i64.const -2147483649))
+(assert_return (invoke "rem_s" (i64.const 5) (i64.const 2)) (i64.const 1))
+(assert_return (invoke "rem_s" (i64.const -5) (i64.const 2)) (i64.const -1))
+(assert_return (invoke "rem_s" (i64.const 5) (i64.const -2)) (i64.const 1))
+(assert_return (invoke "rem_s" (i64.const -5) (i64.const -2)) (i64.const -1))
+(assert_return (invoke "rem_s" (i64.const 7) (i64.const 3)) (i64.const 1))
+(assert_return (invoke "rem_s" (i64.const -7) (i64.const 3)) (i64.const -1))
+(assert_return (invoke "rem_s" (i64.const 7) (i64.const -3)) (i64.const 1))
+(assert_return (invoke "rem_s" (i64.const -7) (i64.const -3)) (i64.const -1))
+(assert_return (invoke "rem_s" (i64.const 11) (i64.const 5)) (i64.const 1))
+(assert_return (invoke "rem_s" (i64.const 17) (i64.const 7)) (i64.const 3))
+(assert_return (invoke "rem_s" (i64.const 9223372036854775807) (i64.const -1)) (i64.const 0))
+
+(assert_trap (invoke "rem_u" (i64.const 1) (i64.const 0)) "integer divide by zero")
+(assert_trap (invoke "rem_u" (i64.const 0) (i64.const 0)) "integer divide by zero")
+(assert_return (invoke "rem_u" (i64.const -9223372036854775808) (i64.const -1)) (i64.const -9223372036854775808))
+(assert_trap (invoke "rem_u" (i64.const -9223372036854775808) (i64.const 0)) "integer divide by zero")
+(assert_return (invoke "rem_u" (i64.const 1) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "rem_u" (i64.const 0) (i64.const 1)) (i64.const 0))
+(assert_return (invoke "rem_u" (i64.const 0) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "rem_u" (i64.const -1) (i64.const -1)) (i64.const 0))
+(assert_return (invoke "rem_u" (i64.const -9223372036854775808) (i64.const 2)) (i64.const 0))
+(assert_return (invoke "rem_u" (i64.const -9223372036854775807) (i64.const 1000)) (i64.const 809))
+(assert_return (invoke "rem_u" (i64.const -8074936608141340688) (i64.const 4294967297)) (i64.const 2147483649))
+(assert_return (invoke "rem_u" (i64.const 5) (i64.const 2)) (i64.const 1))
+(assert_return (invoke "rem_u" (i64.const -5) (i64.const 2)) (i64.const 1))
+(assert_return (invoke "rem_u" (i64.const 5) (i64.const -2)) (i64.const 5))
+(assert_return (invoke "rem_u" (i64.const -5) (i64.const -2)) (i64.const -5))
+(assert_return (invoke "rem_u" (i64.const 7) (i64.const 3)) (i64.const 1))
+(assert_return (invoke "rem_u" (i64.const -7) (i64.const 3)) (i64.const 0))
+(assert_return (invoke "rem_u" (i64.const 7) (i64.const -3)) (i64.const 7))
+(assert_return (invoke "rem_u" (i64.const -7) (i64.const -3)) (i64.const -7))
+(assert_return (invoke "rem_u" (i64.const 11) (i64.const 5)) (i64.const 1))
+(assert_return (invoke "rem_u" (i64.const 17) (i64.const 7)) (i64.const 3))
+(assert_return (invoke "rem_u" (i64.const 9223372036854775807) (i64.const -1)) (i64.const 9223372036854775807))
+
+(assert_return (invoke "clz" (i64.const -1)) (i64.const 0))
+(assert_return (invoke "clz" (i64.const 0)) (i64.const 64))
+(assert_return (invoke "clz" (i64.const 32768)) (i64.const 48))
+(assert_return (invoke "clz" (i64.const 255)) (i64.const 56))
+(assert_return (invoke "clz" (i64.const -9223372036854775808)) (i64.const 0))
+(assert_return (invoke "clz" (i64.const 1)) (i64.const 63))
+(assert_return (invoke "clz" (i64.const 2)) (i64.const 62))
+(assert_return (invoke "clz" (i64.const 9223372036854775807)) (i64.const 1))
+
+(assert_return (invoke "ctz" (i64.const -1)) (i64.const 0))
+(assert_return (invoke "ctz" (i64.const 0)) (i64.const 64))
+(assert_return (invoke "ctz" (i64.const 32768)) (i64.const 15))
+(assert_return (invoke "ctz" (i64.const 65536)) (i64.const 16))
+(assert_return (invoke "ctz" (i64.const -9223372036854775808)) (i64.const 63))
+(assert_return (invoke "ctz" (i64.const 9223372036854775807)) (i64.const 0))
+
+(assert_return (invoke "popcnt" (i64.const -1)) (i64.const 64))
+(assert_return (invoke "popcnt" (i64.const 0)) (i64.const 0))
+(assert_return (invoke "popcnt" (i64.const 32768)) (i64.const 1))
+(assert_return (invoke "popcnt" (i64.const -9223231297218904064)) (i64.const 4))
+(assert_return (invoke "popcnt" (i64.const 9223372036854775807)) (i64.const 63))
+(assert_return (invoke "popcnt" (i64.const -6148914692668172971)) (i64.const 32))
+(assert_return (invoke "popcnt" (i64.const -7378697629197489494)) (i64.const 32))
+(assert_return (invoke "popcnt" (i64.const -2401053088876216593)) (i64.const 48))
+
+(assert_return (invoke "extend8_s" (i64.const 0)) (i64.const 0))
+(assert_return (invoke "extend8_s" (i64.const 127)) (i64.const 127))
+(assert_return (invoke "extend8_s" (i64.const 128)) (i64.const -128))
+(assert_return (invoke "extend8_s" (i64.const 255)) (i64.const -1))
+(assert_return (invoke "extend8_s" (i64.const 81985529216486656)) (i64.const 0))
+(assert_return (invoke "extend8_s" (i64.const -81985529216486784)) (i64.const -128))
+(assert_return (invoke "extend8_s" (i64.const -1)) (i64.const -1))
+
+(assert_return (invoke "extend16_s" (i64.const 0)) (i64.const 0))
+(assert_return (invoke "extend16_s" (i64.const 32767)) (i64.const 32767))
+(assert_return (invoke "extend16_s" (i64.const 32768)) (i64.const -32768))
+(assert_return (invoke "extend16_s" (i64.const 65535)) (i64.const -1))
+(assert_return (invoke "extend16_s" (i64.const 1311768467463733248)) (i64.const 0))
+(assert_return (invoke "extend16_s" (i64.const -81985529216466944)) (i64.const -32768))
+(assert_return (invoke "extend16_s" (i64.const -1)) (i64.const -1))
+
+(assert_return (invoke "extend32_s" (i64.const 0)) (i64.const 0))
+(assert_return (invoke "extend32_s" (i64.const 32767)) (i64.const 32767))
+(assert_return (invoke "extend32_s" (i64.const 32768)) (i64.const 32768))
+(assert_return (invoke "extend32_s" (i64.const 65535)) (i64.const 65535))
+(assert_return (invoke "extend32_s" (i64.const 2147483647)) (i64.const 2147483647))
+(assert_return (invoke "extend32_s" (i64.const 2147483648)) (i64.const -2147483648))
+(assert_return (invoke "extend32_s" (i64.const 4294967295)) (i64.const -1))
+(assert_return (invoke "extend32_s" (i64.const 81985526906748928)) (i64.const 0))
+(assert_return (invoke "extend32_s" (i64.const -81985529054232576)) (i64.const -2147483648))
+(assert_return (invoke "extend32_s" (i64.const -1)) (i64.const -1))
+
+(assert_return (invoke "eqz" (i64.const 0)) (i32.const 1))
+(assert_return (invoke "eqz" (i64.const 1)) (i32.const 0))
+(assert_return (invoke "eqz" (i64.const -9223372036854775808)) (i32.const 0))
+(assert_return (invoke "eqz" (i64.const 9223372036854775807)) (i32.const 0))
+(assert_return (invoke "eqz" (i64.const -1)) (i32.const 0))
+
+(assert_return (invoke "eq" (i64.const 0) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "eq" (i64.const 1) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "eq" (i64.const -1) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "eq" (i64.const -9223372036854775808) (i64.const -9223372036854775808)) (i32.const 1))
+(assert_return (invoke "eq" (i64.const 9223372036854775807) (i64.const 9223372036854775807)) (i32.const 1))
+(assert_return (invoke "eq" (i64.const -1) (i64.const -1)) (i32.const 1))
+(assert_return (invoke "eq" (i64.const 1) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "eq" (i64.const 0) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "eq" (i64.const -9223372036854775808) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "eq" (i64.const 0) (i64.const -9223372036854775808)) (i32.const 0))
+(assert_return (invoke "eq" (i64.const -9223372036854775808) (i64.const -1)) (i32.const 0))
+(assert_return (invoke "eq" (i64.const -1) (i64.const -9223372036854775808)) (i32.const 0))
+(assert_return (invoke "eq" (i64.const -9223372036854775808) (i64.const 9223372036854775807)) (i32.const 0))
+(assert_return (invoke "eq" (i64.const 9223372036854775807) (i64.const -9223372036854775808)) (i32.const 0))
+
+(assert_return (invoke "ne" (i64.const 0) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "ne" (i64.const 1) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "ne" (i64.const -1) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "ne" (i64.const -9223372036854775808) (i64.const -9223372036854775808)) (i32.const 0))
+(assert_return (invoke "ne" (i64.const 9223372036854775807) (i64.const 9223372036854775807)) (i32.const 0))
+(assert_return (invoke "ne" (i64.const -1) (i64.const -1)) (i32.const 0))
+(assert_return (invoke "ne" (i64.const 1) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "ne" (i64.const 0) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "ne" (i64.const -9223372036854775808) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "ne" (i64.const 0) (i64.const -9223372036854775808)) (i32.const 1))
+(assert_return (invoke "ne" (i64.const -9223372036854775808) (i64.const -1)) (i32.const 1))
+(assert_return (invoke "ne" (i64.const -1) (i64.const -9223372036854775808)) (i32.const 1))
+(assert_return (invoke "ne" (i64.const -9223372036854775808) (i64.const 9223372036854775807)) (i32.const 1))
+(assert_return (invoke "ne" (i64.const 9223372036854775807) (i64.const -9223372036854775808)) (i32.const 1))
+
+(assert_return (invoke "lt_s" (i64.const 0) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "lt_s" (i64.const 1) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "lt_s" (i64.const -1) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "lt_s" (i64.const -9223372036854775808) (i64.const -9223372036854775808)) (i32.const 0))
+(assert_return (invoke "lt_s" (i64.const 9223372036854775807) (i64.const 9223372036854775807)) (i32.const 0))
+(assert_return (invoke "lt_s" (i64.const -1) (i64.const -1)) (i32.const 0))
+(assert_return (invoke "lt_s" (i64.const 1) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "lt_s" (i64.const 0) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "lt_s" (i64.const -9223372036854775808) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "lt_s" (i64.const 0) (i64.const -9223372036854775808)) (i32.const 0))
+(assert_return (invoke "lt_s" (i64.const -9223372036854775808) (i64.const -1)) (i32.const 1))
+(assert_return (invoke "lt_s" (i64.const -1) (i64.const -9223372036854775808)) (i32.const 0))
+(assert_return (invoke "lt_s" (i64.const -9223372036854775808) (i64.const 9223372036854775807)) (i32.const 1))
+(assert_return (invoke "lt_s" (i64.const 9223372036854775807) (i64.const -9223372036854775808)) (i32.const 0))
+
+(assert_return (invoke "lt_u" (i64.const 0) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "lt_u" (i64.const 1) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "lt_u" (i64.const -1) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "lt_u" (i64.const -9223372036854775808) (i64.const -9223372036854775808)) (i32.const 0))
+(assert_return (invoke "lt_u" (i64.const 9223372036854775807) (i64.const 9223372036854775807)) (i32.const 0))
+(assert_return (invoke "lt_u" (i64.const -1) (i64.const -1)) (i32.const 0))
+(assert_return (invoke "lt_u" (i64.const 1) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "lt_u" (i64.const 0) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "lt_u" (i64.const -9223372036854775808) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "lt_u" (i64.const 0) (i64.const -9223372036854775808)) (i32.const 1))
+(assert_return (invoke "lt_u" (i64.const -9223372036854775808) (i64.const -1)) (i32.const 1))
+(assert_return (invoke "lt_u" (i64.const -1) (i64.const -9223372036854775808)) (i32.const 0))
+(assert_return (invoke "lt_u" (i64.const -9223372036854775808) (i64.const 9223372036854775807)) (i32.const 0))
+(assert_return (invoke "lt_u" (i64.const 9223372036854775807) (i64.const -9223372036854775808)) (i32.const 1))
+
+(assert_return (invoke "le_s" (i64.const 0) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "le_s" (i64.const 1) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "le_s" (i64.const -1) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "le_s" (i64.const -9223372036854775808) (i64.const -9223372036854775808)) (i32.const 1))
+(assert_return (invoke "le_s" (i64.const 9223372036854775807) (i64.const 9223372036854775807)) (i32.const 1))
+(assert_return (invoke "le_s" (i64.const -1) (i64.const -1)) (i32.const 1))
+(assert_return (invoke "le_s" (i64.const 1) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "le_s" (i64.const 0) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "le_s" (i64.const -9223372036854775808) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "le_s" (i64.const 0) (i64.const -9223372036854775808)) (i32.const 0))
+(assert_return (invoke "le_s" (i64.const -9223372036854775808) (i64.const -1)) (i32.const 1))
+(assert_return (invoke "le_s" (i64.const -1) (i64.const -9223372036854775808)) (i32.const 0))
+(assert_return (invoke "le_s" (i64.const -9223372036854775808) (i64.const 9223372036854775807)) (i32.const 1))
+(assert_return (invoke "le_s" (i64.const 9223372036854775807) (i64.const -9223372036854775808)) (i32.const 0))
+
+(assert_return (invoke "le_u" (i64.const 0) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "le_u" (i64.const 1) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "le_u" (i64.const -1) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "le_u" (i64.const -9223372036854775808) (i64.const -9223372036854775808)) (i32.const 1))
+(assert_return (invoke "le_u" (i64.const 9223372036854775807) (i64.const 9223372036854775807)) (i32.const 1))
+(assert_return (invoke "le_u" (i64.const -1) (i64.const -1)) (i32.const 1))
+(assert_return (invoke "le_u" (i64.const 1) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "le_u" (i64.const 0) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "le_u" (i64.const -9223372036854775808) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "le_u" (i64.const 0) (i64.const -9223372036854775808)) (i32.const 1))
+(assert_return (invoke "le_u" (i64.const -9223372036854775808) (i64.const -1)) (i32.const 1))
+(assert_return (invoke "le_u" (i64.const -1) (i64.const -9223372036854775808)) (i32.const 0))
+(assert_return (invoke "le_u" (i64.const -9223372036854775808) (i64.const 9223372036854775807)) (i32.const 0))
+(assert_return (invoke "le_u" (i64.const 9223372036854775807) (i64.const -9223372036854775808)) (i32.const 1))
+
+(assert_return (invoke "gt_s" (i64.const 0) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "gt_s" (i64.const 1) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "gt_s" (i64.const -1) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "gt_s" (i64.const -9223372036854775808) (i64.const -9223372036854775808)) (i32.const 0))
+(assert_return (invoke "gt_s" (i64.const 9223372036854775807) (i64.const 9223372036854775807)) (i32.const 0))
+(assert_return (invoke "gt_s" (i64.const -1) (i64.const -1)) (i32.const 0))
+(assert_return (invoke "gt_s" (i64.const 1) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "gt_s" (i64.const 0) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "gt_s" (i64.const -9223372036854775808) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "gt_s" (i64.const 0) (i64.const -9223372036854775808)) (i32.const 1))
+(assert_return (invoke "gt_s" (i64.const -9223372036854775808) (i64.const -1)) (i32.const 0))
+(assert_return (invoke "gt_s" (i64.const -1) (i64.const -9223372036854775808)) (i32.const 1))
+(assert_return (invoke "gt_s" (i64.const -9223372036854775808) (i64.const 9223372036854775807)) (i32.const 0))
+(assert_return (invoke "gt_s" (i64.const 9223372036854775807) (i64.const -9223372036854775808)) (i32.const 1))
+
+(assert_return (invoke "gt_u" (i64.const 0) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "gt_u" (i64.const 1) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "gt_u" (i64.const -1) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "gt_u" (i64.const -9223372036854775808) (i64.const -9223372036854775808)) (i32.const 0))
+(assert_return (invoke "gt_u" (i64.const 9223372036854775807) (i64.const 9223372036854775807)) (i32.const 0))
+(assert_return (invoke "gt_u" (i64.const -1) (i64.const -1)) (i32.const 0))
+(assert_return (invoke "gt_u" (i64.const 1) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "gt_u" (i64.const 0) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "gt_u" (i64.const -9223372036854775808) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "gt_u" (i64.const 0) (i64.const -9223372036854775808)) (i32.const 0))
+(assert_return (invoke "gt_u" (i64.const -9223372036854775808) (i64.const -1)) (i32.const 0))
+(assert_return (invoke "gt_u" (i64.const -1) (i64.const -9223372036854775808)) (i32.const 1))
+(assert_return (invoke "gt_u" (i64.const -9223372036854775808) (i64.const 9223372036854775807)) (i32.const 1))
+(assert_return (invoke "gt_u" (i64.const 9223372036854775807) (i64.const -9223372036854775808)) (i32.const 0))
+
+(assert_return (invoke "ge_s" (i64.const 0) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "ge_s" (i64.const 1) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "ge_s" (i64.const -1) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "ge_s" (i64.const -9223372036854775808) (i64.const -9223372036854775808)) (i32.const 1))
+(assert_return (invoke "ge_s" (i64.const 9223372036854775807) (i64.const 9223372036854775807)) (i32.const 1))
+(assert_return (invoke "ge_s" (i64.const -1) (i64.const -1)) (i32.const 1))
+(assert_return (invoke "ge_s" (i64.const 1) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "ge_s" (i64.const 0) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "ge_s" (i64.const -9223372036854775808) (i64.const 0)) (i32.const 0))
+(assert_return (invoke "ge_s" (i64.const 0) (i64.const -9223372036854775808)) (i32.const 1))
+(assert_return (invoke "ge_s" (i64.const -9223372036854775808) (i64.const -1)) (i32.const 0))
+(assert_return (invoke "ge_s" (i64.const -1) (i64.const -9223372036854775808)) (i32.const 1))
+(assert_return (invoke "ge_s" (i64.const -9223372036854775808) (i64.const 9223372036854775807)) (i32.const 0))
+(assert_return (invoke "ge_s" (i64.const 9223372036854775807) (i64.const -9223372036854775808)) (i32.const 1))
+
+(assert_return (invoke "ge_u" (i64.const 0) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "ge_u" (i64.const 1) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "ge_u" (i64.const -1) (i64.const 1)) (i32.const 1))
+(assert_return (invoke "ge_u" (i64.const -9223372036854775808) (i64.const -9223372036854775808)) (i32.const 1))
+(assert_return (invoke "ge_u" (i64.const 9223372036854775807) (i64.const 9223372036854775807)) (i32.const 1))
+(assert_return (invoke "ge_u" (i64.const -1) (i64.const -1)) (i32.const 1))
+(assert_return (invoke "ge_u" (i64.const 1) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "ge_u" (i64.const 0) (i64.const 1)) (i32.const 0))
+(assert_return (invoke "ge_u" (i64.const -9223372036854775808) (i64.const 0)) (i32.const 1))
+(assert_return (invoke "ge_u" (i64.const 0) (i64.const -9223372036854775808)) (i32.const 0))
+(assert_return (invoke "ge_u" (i64.const -9223372036854775808) (i64.const -1)) (i32.const 0))
+(assert_return (invoke "ge_u" (i64.const -1) (i64.const -9223372036854775808)) (i32.const 1))
+(assert_return (invoke "ge_u" (i64.const -9223372036854775808) (i64.const 9223372036854775807)) (i32.const 1))
+(assert_return (invoke "ge_u" (i64.const 9223372036854775807) (i64.const -9223372036854775808)) (i32.const 0))